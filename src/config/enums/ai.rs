@@ -33,4 +33,5 @@ pub enum GeminiThinkingLevel {
 pub enum AiService {
     OpenAi,
     Gemini,
+    Replay,
 }
\ No newline at end of file
@@ -1,7 +1,11 @@
 mod ai;
 mod weather;
 mod screen_recorder;
+mod overview;
+mod notifications;
 
 pub use ai::*;
 pub use weather::*;
-pub use screen_recorder::*;
\ No newline at end of file
+pub use screen_recorder::*;
+pub use overview::*;
+pub use notifications::*;
\ No newline at end of file
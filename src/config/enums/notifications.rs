@@ -0,0 +1,23 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumString;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum NotificationUrgency {
+    Low = 0,
+    #[default]
+    Normal = 1,
+    Critical = 2,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum NotificationRuleAction {
+    /// Shows the notification as usual, with the rule's other changes.
+    #[default]
+    Show,
+    /// Only keeps the notification in the history.
+    Silent,
+    /// Discards the notification, the history only keeps it among the dropped ones.
+    Drop,
+}
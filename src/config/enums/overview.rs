@@ -0,0 +1,12 @@
+use serde::{Deserialize, Serialize};
+use strum::EnumString;
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, EnumString)]
+#[strum(ascii_case_insensitive)]
+pub enum OverviewPluginMode {
+    #[default]
+    #[strum(serialize = "perquery", serialize = "per_query")]
+    PerQuery,
+    #[strum(serialize = "longrunning", serialize = "long_running")]
+    LongRunning,
+}
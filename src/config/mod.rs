@@ -23,16 +23,26 @@ pub use enums::{
     ScreenRecorderFramerateMode,
     ScreenRecorderBitrateMode,
     ScreenRecorderColorRange,
+    OverviewPluginMode,
+    NotificationUrgency,
+    NotificationRuleAction,
 };
 
 use structs::{
     AiConfig,
     OpenAiConfig,
     GeminiConfig,
+    AiReplayConfig,
     AiFeatures,
     WeatherConfig,
     WeatherAlertsConfig,
     ScreenRecorderConfig,
+    OverviewConfig,
+    OverviewFilesConfig,
+    OverviewBrowserConfig,
+    OverviewSshConfig,
+    SearchConfig,
+    NotificationsConfig,
 };
 
 use crate::utils::filesystem::get_config_directory;
@@ -49,6 +59,12 @@ pub struct Config {
     pub ai: AiConfig,
     pub weather: WeatherConfig,
     pub screen_recorder: ScreenRecorderConfig,
+    #[serde(default)]
+    pub overview: OverviewConfig,
+    #[serde(default)]
+    pub search: SearchConfig,
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
 }
 
 impl Default for Config {
@@ -73,10 +89,16 @@ impl Default for Config {
                     thinking_budget: -1,
                     thinking_level: GeminiThinkingLevel::Budget,
                 },
+                replay: AiReplayConfig::default(),
+                text_actions: Default::default(),
                 features: AiFeatures {
                     power_control: true,
                     mpris_control: true,
                     weather_info: true,
+                    memory: false,
+                    filesystem_access: false,
+                    filesystem_roots: vec![],
+                    filesystem_confirm_roots: vec!["~".to_owned()],
                 },
             },
             weather: WeatherConfig {
@@ -112,6 +134,14 @@ impl Default for Config {
                 audio_device_targets: vec![],
                 audio_codec: ScreenRecorderAudioCodec::Auto,
             },
+            overview: OverviewConfig {
+                plugins: vec![],
+                files: OverviewFilesConfig::default(),
+                browser: OverviewBrowserConfig::default(),
+                ssh: OverviewSshConfig::default(),
+            },
+            search: SearchConfig::default(),
+            notifications: NotificationsConfig::default(),
         }
     }
 }
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use serde::{Deserialize, Serialize};
 
 use super::deserialize_insensitive;
@@ -13,6 +14,18 @@ pub struct AiFeatures {
     pub power_control: bool,
     pub mpris_control: bool,
     pub weather_info: bool,
+    #[serde(default)]
+    pub memory: bool,
+    #[serde(default)]
+    pub filesystem_access: bool,
+    #[serde(default)]
+    pub filesystem_roots: Vec<String>,
+    #[serde(default = "default_filesystem_confirm_roots")]
+    pub filesystem_confirm_roots: Vec<String>,
+}
+
+fn default_filesystem_confirm_roots() -> Vec<String> {
+    vec!["~".to_owned()]
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,6 +47,22 @@ pub struct GeminiConfig {
     pub thinking_level: GeminiThinkingLevel,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AiReplayConfig {
+    pub fixture_path: String,
+    pub loop_responses: bool,
+}
+
+impl Default for AiReplayConfig {
+    fn default() -> Self {
+        AiReplayConfig {
+            fixture_path: "ai_replay.json".to_owned(),
+            loop_responses: false,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AiConfig {
     pub enabled: bool,
@@ -45,5 +74,9 @@ pub struct AiConfig {
     pub assistant_icon_path: Option<String>,
     pub openai: OpenAiConfig,
     pub gemini: GeminiConfig,
+    #[serde(default)]
+    pub replay: AiReplayConfig,
+    #[serde(default)]
+    pub text_actions: BTreeMap<String, String>,
     pub features: AiFeatures,
 }
\ No newline at end of file
@@ -1,10 +1,16 @@
 mod ai;
 mod weather;
 mod screen_recorder;
+mod overview;
+mod search;
+mod notifications;
 
 pub use ai::*;
 pub use weather::*;
 pub use screen_recorder::*;
+pub use overview::*;
+pub use search::*;
+pub use notifications::*;
 
 pub fn deserialize_insensitive<'de, T, D>(deserializer: D) -> Result<T, D::Error>
 where
@@ -15,4 +21,17 @@ where
     use serde::Deserialize as _;
     let s = String::deserialize(deserializer)?;
     T::from_str(&s).map_err(serde::de::Error::custom)
-}
\ No newline at end of file
+}
+
+/// Like `deserialize_insensitive`, for fields that can be left out.
+pub fn deserialize_insensitive_option<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    T: std::str::FromStr,
+    T::Err: std::fmt::Display,
+    D: serde::Deserializer<'de>,
+{
+    use serde::Deserialize as _;
+    Option::<String>::deserialize(deserializer)?
+        .map(|s| T::from_str(&s).map_err(serde::de::Error::custom))
+        .transpose()
+}
@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+
+use super::{deserialize_insensitive, deserialize_insensitive_option};
+use super::super::enums::{NotificationRuleAction, NotificationUrgency};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct NotificationHistoryConfig {
+    pub enabled: bool,
+    /// The most notifications kept; the oldest are removed first.
+    pub max_entries: usize,
+    /// Notifications older than this many days are removed. 0 keeps them forever.
+    pub max_age_days: u64,
+}
+
+impl Default for NotificationHistoryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_entries: 5000,
+            max_age_days: 30,
+        }
+    }
+}
+
+/// Matches notifications and changes how they're handled. Every given criterion must match,
+/// and only the first matching rule applies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NotificationRuleConfig {
+    /// Shown in the history next to the notifications the rule matched.
+    pub name: String,
+
+    /// Regex matched against the app name.
+    #[serde(default)]
+    pub app_name: Option<String>,
+    /// Regex matched against the summary.
+    #[serde(default)]
+    pub summary: Option<String>,
+    /// Regex matched against the body.
+    #[serde(default)]
+    pub body: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default, deserialize_with = "deserialize_insensitive_option")]
+    pub urgency: Option<NotificationUrgency>,
+
+    #[serde(default, deserialize_with = "deserialize_insensitive")]
+    pub action: NotificationRuleAction,
+    #[serde(default, deserialize_with = "deserialize_insensitive_option")]
+    pub set_urgency: Option<NotificationUrgency>,
+    /// How long the popup stays up. 0 keeps it up until it's dismissed.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// Shows the popup even with Do Not Disturb on.
+    #[serde(default)]
+    pub ignore_dnd: bool,
+    /// Run with `bash -c`, with the notification in `NOTIFICATION_*` environment variables.
+    #[serde(default)]
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct NotificationsConfig {
+    #[serde(default)]
+    pub history: NotificationHistoryConfig,
+    #[serde(default)]
+    pub rules: Vec<NotificationRuleConfig>,
+}
@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+
+use super::deserialize_insensitive;
+use super::super::enums::OverviewPluginMode;
+
+fn default_plugin_timeout_ms() -> u64 {
+    500
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OverviewPluginConfig {
+    pub name: String,
+    pub command: String,
+    pub prefix: String,
+    pub icon: String,
+    #[serde(default, deserialize_with = "deserialize_insensitive")]
+    pub mode: OverviewPluginMode,
+    #[serde(default = "default_plugin_timeout_ms")]
+    pub timeout_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverviewFilesConfig {
+    pub roots: Vec<String>,
+    pub show_hidden: bool,
+    pub respect_gitignore: bool,
+    pub max_files: usize,
+}
+
+impl Default for OverviewFilesConfig {
+    fn default() -> Self {
+        Self {
+            roots: vec!["~".to_owned()],
+            show_hidden: false,
+            respect_gitignore: true,
+            max_files: 100_000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverviewBrowserConfig {
+    pub enabled: bool,
+    /// Profile directories to index. Every profile that's found is indexed when empty.
+    pub profiles: Vec<String>,
+    pub include_history: bool,
+    pub max_history_entries: usize,
+    pub refresh_interval: u64,
+}
+
+impl Default for OverviewBrowserConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            profiles: vec![],
+            include_history: true,
+            max_history_entries: 5000,
+            refresh_interval: 300,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct OverviewSshConfig {
+    /// Hosts to offer besides those in `~/.ssh/config` and `known_hosts`, e.g. "admin@10.0.0.5".
+    pub hosts: Vec<String>,
+    pub include_known_hosts: bool,
+}
+
+impl Default for OverviewSshConfig {
+    fn default() -> Self {
+        Self {
+            hosts: vec![],
+            include_known_hosts: true,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OverviewConfig {
+    #[serde(default)]
+    pub plugins: Vec<OverviewPluginConfig>,
+    #[serde(default)]
+    pub files: OverviewFilesConfig,
+    #[serde(default)]
+    pub browser: OverviewBrowserConfig,
+    #[serde(default)]
+    pub ssh: OverviewSshConfig,
+}
@@ -0,0 +1,45 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchEngineConfig {
+    pub name: String,
+    /// `{query}` is replaced with the URL-encoded query. URLs without it get the query appended.
+    pub url: String,
+    /// Picks this engine when the query has `!<bang>` at its start or end.
+    #[serde(default)]
+    pub bang: Option<String>,
+}
+
+impl SearchEngineConfig {
+    fn new(name: &str, url: &str, bang: &str) -> Self {
+        Self {
+            name: name.to_owned(),
+            url: url.to_owned(),
+            bang: Some(bang.to_owned()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SearchConfig {
+    /// The name of the engine used when no bang is given.
+    pub default_engine: String,
+    pub engines: Vec<SearchEngineConfig>,
+}
+
+impl Default for SearchConfig {
+    fn default() -> Self {
+        Self {
+            default_engine: "DuckDuckGo".to_owned(),
+            engines: vec![
+                SearchEngineConfig::new("DuckDuckGo", "https://duckduckgo.com/?q={query}", "ddg"),
+                SearchEngineConfig::new("Google", "https://www.google.com/search?q={query}", "g"),
+                SearchEngineConfig::new("Bing", "https://www.bing.com/search?q={query}", "b"),
+                SearchEngineConfig::new("Wikipedia", "https://en.wikipedia.org/w/index.php?search={query}", "w"),
+                SearchEngineConfig::new("YouTube", "https://www.youtube.com/results?search_query={query}", "yt"),
+                SearchEngineConfig::new("GitHub", "https://github.com/search?q={query}", "gh"),
+            ],
+        }
+    }
+}
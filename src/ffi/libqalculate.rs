@@ -1,11 +1,32 @@
 /*
     Minimal Rust FFI bindings for libqalculate, a C++ library for advanced calculations.
-    For this project's current use case, we only need a Calculator object, definition loading,
-    and expression unlocalization and calculation. This may be extended in the future.
+    We need a Calculator object, definition loading, exchange rate fetching, variables, and
+    calculating expressions into a few printed forms. This may be extended in the future.
 */
 
 #[cxx::bridge]
 pub mod ffi {
+    #[derive(Debug, Clone)]
+    struct CalculationMessage {
+        text: String,
+        is_error: bool,
+    }
+
+    /// An expression's result printed in several forms. Forms that don't apply, or that
+    /// print the same as `result`, are left empty.
+    #[derive(Debug, Clone)]
+    struct CalculationResult {
+        expression: String,
+        result: String,
+        exact: String,
+        decimal: String,
+        hexadecimal: String,
+        binary: String,
+        octal: String,
+        simplified_units: String,
+        messages: Vec<CalculationMessage>,
+    }
+
     unsafe extern "C++" {
         include!("main.h");
 
@@ -14,7 +35,12 @@ pub mod ffi {
         pub fn loadExchangeRates() -> bool;
         pub fn loadGlobalDefinitions() -> bool;
         pub fn loadLocalDefinitions() -> bool;
-        pub fn unlocalizeExpression(str: String) -> String;
-        pub fn calculateAndPrint(str: String, msecs: u32) -> String;
+        pub fn calculate(str: String, msecs: u32) -> CalculationResult;
+
+        pub fn setVariable(name: String, expression: String) -> bool;
+
+        /// Unix time of the loaded exchange rates, or 0 if there aren't any.
+        pub fn exchangeRatesTime() -> i64;
+        pub fn fetchExchangeRates(timeout_secs: i32) -> bool;
     }
-}
\ No newline at end of file
+}
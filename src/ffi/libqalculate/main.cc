@@ -29,16 +29,108 @@ bool loadLocalDefinitions() {
     return calculator->loadLocalDefinitions();
 }
 
-rust::String unlocalizeExpression(rust::String str) {
+static std::string print_with(const MathStructure &mstruct, const PrintOptions &po) {
+    MathStructure formatted(mstruct);
+    formatted.format(po);
+    return formatted.print(po);
+}
+
+// Only keeps forms that add something over the default result
+static rust::String alternative_form(const std::string &form, const std::string &result) {
+    return form == result ? rust::String() : rust::String(form);
+}
+
+CalculationResult calculate(rust::String str, rust::u32 msecs) {
+    assert_calculator();
+    CalculationResult out;
+
+    EvaluationOptions eo = default_user_evaluation_options;
+    eo.approximation = APPROXIMATION_TRY_EXACT;
+
+    std::string expression = calculator->unlocalizeExpression(str.c_str(), eo.parse_options);
+    calculator->clearMessages();
+
+    MathStructure result;
+    MathStructure parsed;
+    if (!calculator->calculate(&result, expression, msecs, eo, &parsed)) {
+        out.messages.push_back(CalculationMessage { rust::String("Calculation timed out"), true });
+        return out;
+    }
+
+    PrintOptions po = default_print_options;
+    po.number_fraction_format = FRACTION_DECIMAL;
+    po.interval_display = INTERVAL_DISPLAY_SIGNIFICANT_DIGITS;
+
+    std::string printed = print_with(result, po);
+    out.expression = rust::String(print_with(parsed, po));
+    out.result = rust::String(printed);
+
+    PrintOptions exact_po = po;
+    exact_po.number_fraction_format = FRACTION_FRACTIONAL;
+    out.exact = alternative_form(print_with(result, exact_po), printed);
+
+    EvaluationOptions approximate_eo = eo;
+    approximate_eo.approximation = APPROXIMATION_APPROXIMATE;
+    MathStructure approximate(result);
+    approximate.eval(approximate_eo);
+    out.decimal = alternative_form(print_with(approximate, po), printed);
+
+    if (result.isInteger()) {
+        PrintOptions base_po = po;
+
+        base_po.base = BASE_HEXADECIMAL;
+        out.hexadecimal = alternative_form(print_with(result, base_po), printed);
+
+        base_po.base = BASE_BINARY;
+        out.binary = alternative_form(print_with(result, base_po), printed);
+
+        base_po.base = BASE_OCTAL;
+        out.octal = alternative_form(print_with(result, base_po), printed);
+    }
+
+    if (result.containsType(STRUCT_UNIT)) {
+        MathStructure simplified = calculator->convertToOptimalUnit(result, eo, true);
+        out.simplified_units = alternative_form(print_with(simplified, po), printed);
+    }
+
+    for (CalculatorMessage *message = calculator->message(); message; message = calculator->nextMessage()) {
+        out.messages.push_back(CalculationMessage {
+            rust::String(message->message()),
+            message->type() == MESSAGE_ERROR
+        });
+    }
+
+    return out;
+}
+
+bool setVariable(rust::String name, rust::String expression) {
     assert_calculator();
-    std::string in = str.c_str();
-    std::string out = calculator->unlocalizeExpression(in);
-    return rust::String(std::move(out));
+    std::string variable_name = name.c_str();
+    if (!calculator->variableNameIsValid(variable_name)) {
+        return false;
+    }
+
+    Variable *variable = calculator->getActiveVariable(variable_name);
+    if (variable) {
+        // Never replace built-in variables like pi or e
+        if (!variable->isLocal() || !variable->isKnown()) {
+            return false;
+        }
+
+        ((KnownVariable*) variable)->set(expression.c_str());
+        return true;
+    }
+
+    calculator->addVariable(new KnownVariable("", variable_name, expression.c_str()));
+    return true;
 }
 
-rust::String calculateAndPrint(rust::String str, rust::u32 msecs) {
+rust::i64 exchangeRatesTime() {
     assert_calculator();
-    std::string in = str.c_str();
-    std::string out = calculator->calculateAndPrint(in, msecs);
-    return rust::String(std::move(out));
-}
\ No newline at end of file
+    return (rust::i64) calculator->getExchangeRatesTime();
+}
+
+bool fetchExchangeRates(rust::i32 timeout_secs) {
+    assert_calculator();
+    return calculator->fetchExchangeRates(timeout_secs);
+}
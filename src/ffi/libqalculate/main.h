@@ -1,18 +1,26 @@
 #pragma once
 #include <libqalculate/qalculate.h>
+#include <gray-meadows-shell/src/ffi/libqalculate.rs.h>
 #include <rust/cxx.h>
 
+struct CalculationMessage;
+struct CalculationResult;
+
 void init_calc();
 
 bool loadExchangeRates();
 bool loadGlobalDefinitions();
 bool loadLocalDefinitions();
 
-rust::String unlocalizeExpression(
-    rust::String str
-);
-
-rust::String calculateAndPrint(
+CalculationResult calculate(
     rust::String str,
     rust::u32 msecs
-);
\ No newline at end of file
+);
+
+bool setVariable(
+    rust::String name,
+    rust::String expression
+);
+
+rust::i64 exchangeRatesTime();
+bool fetchExchangeRates(rust::i32 timeout_secs);
@@ -103,6 +103,7 @@ fn activate(application: &Application) {
         windows.insert("session".into(), Box::new(widgets::windows::session::new(application)));
         windows.insert("left_sidebar".into(), Box::new(widgets::windows::sidebar_left::new(application)));
         windows.insert("right_sidebar".into(), Box::new(widgets::windows::sidebar_right::new(application)));
+        windows.insert("text_action".into(), Box::new(widgets::windows::text_action::new(application)));
 
         // optional features
         if process::is_command_available("cliphist") && process::is_command_available("wl-copy") {
@@ -0,0 +1,47 @@
+// Lets tools ask the user before doing something sensitive. A request is shown in the chat,
+// and the tool call waits until it is answered, denied by stopping the cycle, or expires.
+
+use std::collections::HashMap;
+use std::sync::{LazyLock, Mutex};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Duration;
+use tokio::sync::oneshot;
+
+use super::{AiChannelMessage, CHANNEL};
+
+const CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(120);
+
+static PENDING: LazyLock<Mutex<HashMap<u64, oneshot::Sender<bool>>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_ID: AtomicU64 = AtomicU64::new(1);
+
+/// Asks the user to confirm an action, returning whether it was allowed.
+pub async fn request(description: &str) -> bool {
+    let Some(channel) = CHANNEL.get() else {
+        return false;
+    };
+
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let (sender, receiver) = oneshot::channel();
+    PENDING.lock().unwrap().insert(id, sender);
+
+    channel.send(AiChannelMessage::ConfirmationRequested(id, description.to_owned())).await;
+
+    let allowed = matches!(tokio::time::timeout(CONFIRMATION_TIMEOUT, receiver).await, Ok(Ok(true)));
+    PENDING.lock().unwrap().remove(&id);
+
+    channel.send(AiChannelMessage::ConfirmationResolved(id, allowed)).await;
+    allowed
+}
+
+pub fn respond(id: u64, allowed: bool) {
+    if let Some(sender) = PENDING.lock().unwrap().remove(&id) {
+        let _ = sender.send(allowed);
+    }
+}
+
+/// Denies every pending confirmation, used when the request cycle is stopped.
+pub fn deny_all() {
+    for (_, sender) in PENDING.lock().unwrap().drain() {
+        let _ = sender.send(false);
+    }
+}
@@ -43,17 +43,28 @@ pub async fn load_conversation(id: i64) {
     }
 }
 
+/// Loads the most relevant conversation that isn't archived, adding one if there are none,
+/// so the user is never left without a conversation to type into.
 pub async fn load_first_conversation() {
-    if let Some(first_conversation) = aichats::get_all_conversations()
+    let first_conversation_id = aichats::get_all_conversations()
         .await
         .unwrap_or_default()
-        .first()
-    {
-        load_conversation(first_conversation.id).await;
+        .iter()
+        .find(|conversation| !conversation.archived)
+        .map(|conversation| conversation.id);
+
+    let first_conversation_id = match first_conversation_id {
+        Some(id) => Some(id),
+        None => add_conversation("Untitled").await,
+    };
+
+    if let Some(id) = first_conversation_id {
+        load_conversation(id).await;
     }
 }
 
-pub async fn add_conversation(title: &str) {
+/// Adds a conversation and returns its ID.
+pub async fn add_conversation(title: &str) -> Option<i64> {
     match aichats::add_conversation(title).await {
         Ok(conversation_id) => {
             match aichats::get_conversation(conversation_id).await {
@@ -65,10 +76,13 @@ pub async fn add_conversation(title: &str) {
                     error!(%err, "Failed to load newly added AI chat conversation from database");
                 }
             }
+
+            Some(conversation_id)
         },
 
         Err(err) => {
             error!(%err, "Failed to add AI chat conversation to database");
+            None
         }
     }
 }
@@ -92,23 +106,101 @@ pub async fn rename_conversation(conversation_id: i64, new_title: &str) {
 }
 
 pub async fn delete_conversation(conversation_id: i64) {
+    delete_conversations(&[conversation_id]).await;
+}
+
+pub async fn delete_conversations(conversation_ids: &[i64]) {
     if let Some(session) = SESSION.get() {
-        if let Err(err) = aichats::delete_conversation(conversation_id).await {
-            error!(%err, "Failed to delete AI chat conversation from database");
+        if let Err(err) = aichats::delete_conversations(conversation_ids).await {
+            error!(%err, "Failed to delete AI chat conversations from database");
             return;
         }
 
+        if let Some(channel) = CHANNEL.get() {
+            for conversation_id in conversation_ids {
+                channel.spawn_send(AiChannelMessage::ConversationDeleted(*conversation_id));
+            }
+        }
+
         let current_conversation_id = session.conversation.read().unwrap().as_ref().map(|c| c.id);
-        if current_conversation_id == Some(conversation_id) {
+        if current_conversation_id.is_some_and(|id| conversation_ids.contains(&id)) {
             load_first_conversation().await;
         }
+    }
+}
 
-        if let Some(channel) = CHANNEL.get() {
-            channel.spawn_send(AiChannelMessage::ConversationDeleted(conversation_id));
+/// Reloads a conversation's info after it changed and lets everyone know.
+async fn send_conversation_updated(conversation_id: i64) {
+    match aichats::get_conversation(conversation_id).await {
+        Ok(updated) => {
+            if let Some(session) = SESSION.get() {
+                let mut conversation = session.conversation.write().unwrap();
+                if let Some(current) = &mut *conversation && current.id == conversation_id {
+                    *current = updated.clone();
+                }
+            }
+
+            if let Some(channel) = CHANNEL.get() {
+                channel.spawn_send(AiChannelMessage::ConversationUpdated(updated));
+            }
+        },
+
+        Err(err) => {
+            error!(%err, "Failed to reload AI chat conversation info from database");
         }
     }
 }
 
+pub async fn set_conversation_pinned(conversation_id: i64, pinned: bool) {
+    if let Err(err) = aichats::set_conversation_pinned(conversation_id, pinned).await {
+        error!(%err, "Failed to pin AI chat conversation in database");
+        return;
+    }
+
+    send_conversation_updated(conversation_id).await;
+}
+
+pub async fn set_conversation_archived(conversation_id: i64, archived: bool) {
+    if let Err(err) = aichats::set_conversation_archived(conversation_id, archived).await {
+        error!(%err, "Failed to archive AI chat conversation in database");
+        return;
+    }
+
+    send_conversation_updated(conversation_id).await;
+}
+
+/// Moves a conversation into a folder, an empty name removes it from its folder.
+pub async fn set_conversation_folder(conversation_id: i64, folder: &str) {
+    let folder = Some(folder.trim()).filter(|folder| !folder.is_empty());
+    if let Err(err) = aichats::set_conversation_folder(conversation_id, folder).await {
+        error!(%err, "Failed to move AI chat conversation to folder in database");
+        return;
+    }
+
+    send_conversation_updated(conversation_id).await;
+}
+
+/// Parses comma or whitespace separated tags, a leading `#` is optional.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags = Vec::new();
+    for tag in input.split(|c: char| c == ',' || c.is_whitespace()) {
+        let tag = tag.trim().trim_start_matches('#').to_lowercase();
+        if !tag.is_empty() && !tags.contains(&tag) {
+            tags.push(tag);
+        }
+    }
+    tags
+}
+
+pub async fn set_conversation_tags(conversation_id: i64, tags: &[String]) {
+    if let Err(err) = aichats::set_conversation_tags(conversation_id, tags).await {
+        error!(%err, "Failed to tag AI chat conversation in database");
+        return;
+    }
+
+    send_conversation_updated(conversation_id).await;
+}
+
 pub async fn clear_conversation(conversation_id: i64) {
     // trim to 0
     if let Err(err) = aichats::trim_items(conversation_id, 0).await {
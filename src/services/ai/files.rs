@@ -0,0 +1,125 @@
+use std::path::Path;
+use base64::Engine as _;
+use uuid::Uuid;
+
+use crate::utils::process;
+use super::images::get_ai_images_directory;
+
+/// Files larger than this are rejected before they are attached.
+pub const MAX_FILE_SIZE: u64 = 20 * 1024 * 1024;
+
+/// Files larger than this are never sent natively, their extracted text is inlined instead.
+pub const MAX_NATIVE_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// The maximum amount of characters of extracted text that is inlined into a conversation.
+pub const MAX_INLINE_TEXT_CHARS: usize = 100_000;
+
+const TEXT_MIME_TYPES: &[&str] = &[
+    "application/json",
+    "application/xml",
+    "application/toml",
+    "application/yaml",
+    "application/x-yaml",
+    "application/javascript",
+    "application/x-shellscript",
+    "application/x-desktop",
+    "application/sql",
+];
+
+pub fn is_text_mime_type(mime_type: &str) -> bool {
+    mime_type.starts_with("text/") || TEXT_MIME_TYPES.contains(&mime_type)
+}
+
+pub fn is_supported_mime_type(mime_type: &str) -> bool {
+    is_text_mime_type(mime_type) || mime_type == "application/pdf"
+}
+
+pub fn guess_mime_type(name: &str, data: &[u8]) -> String {
+    let (content_type, _) = gio::content_type_guess(Some(name), data);
+    gio::content_type_get_mime_type(&content_type)
+        .map_or_else(|| "application/octet-stream".to_owned(), |mime_type| mime_type.to_string())
+}
+
+/// Extracts the text out of a file, if it is a text file or a PDF.
+/// PDFs require `pdftotext` from poppler to be installed, and a path to read from.
+pub fn extract_text(mime_type: &str, path: Option<&Path>, data: &[u8]) -> Option<String> {
+    if is_text_mime_type(mime_type) {
+        Some(String::from_utf8_lossy(data).to_string())
+    } else if mime_type == "application/pdf"
+        && let Some(path) = path
+        && process::is_command_available("pdftotext")
+    {
+        let output = std::process::Command::new("pdftotext")
+            .arg("-layout")
+            .arg(path)
+            .arg("-")
+            .output()
+            .ok()?;
+
+        output.status.success().then(|| String::from_utf8_lossy(&output.stdout).to_string())
+    } else {
+        None
+    }
+}
+
+pub fn uuid_to_attachment_path(uuid: &str) -> String {
+    format!("{}/{}.file", get_ai_images_directory(), uuid)
+}
+
+pub fn uuid_to_text_path(uuid: &str) -> String {
+    format!("{}/{}.txt", get_ai_images_directory(), uuid)
+}
+
+/// The names of every file in the cache directory that belongs to a file attachment.
+pub fn cached_file_names(uuid: &str) -> [String; 2] {
+    [format!("{}.file", uuid), format!("{}.txt", uuid)]
+}
+
+pub fn cache_file_data(data: &[u8], text: Option<&str>) -> Result<String, anyhow::Error> {
+    let files_dir = get_ai_images_directory();
+    std::fs::create_dir_all(&files_dir)?;
+
+    let uuid = Uuid::new_v4().to_string();
+    std::fs::write(uuid_to_attachment_path(&uuid), data)?;
+
+    if let Some(text) = text {
+        std::fs::write(uuid_to_text_path(&uuid), text)?;
+    }
+
+    Ok(uuid)
+}
+
+/// Loads a cached file as base64 if the provider accepts its MIME type natively and it fits
+/// within the native size limit.
+pub fn load_native_file_data(uuid: &str, mime_type: &str, supported_mime_types: &[&str]) -> Option<String> {
+    if !supported_mime_types.contains(&mime_type) {
+        return None;
+    }
+
+    let path = uuid_to_attachment_path(uuid);
+    let size = std::fs::metadata(&path).ok()?.len();
+    if size > MAX_NATIVE_FILE_SIZE {
+        return None;
+    }
+
+    let data = std::fs::read(&path).ok()?;
+    Some(base64::engine::general_purpose::STANDARD.encode(&data))
+}
+
+/// Formats the extracted text of a cached file so it can be inlined into a user message.
+pub fn inline_file_text(uuid: &str, name: &str, mime_type: &str) -> String {
+    let Ok(text) = std::fs::read_to_string(uuid_to_text_path(uuid)) else {
+        return format!("[Attached file: {} ({}), its contents could not be read]", name, mime_type);
+    };
+
+    let char_count = text.chars().count();
+    if char_count > MAX_INLINE_TEXT_CHARS {
+        let truncated = text.chars().take(MAX_INLINE_TEXT_CHARS).collect::<String>();
+        format!(
+            "[Attached file: {} ({}), truncated to the first {} of {} characters]\n```\n{}\n```",
+            name, mime_type, MAX_INLINE_TEXT_CHARS, char_count, truncated
+        )
+    } else {
+        format!("[Attached file: {} ({})]\n```\n{}\n```", name, mime_type, text)
+    }
+}
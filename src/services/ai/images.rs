@@ -4,8 +4,9 @@ use uuid::Uuid;
 
 use crate::utils::filesystem;
 use crate::sql::wrappers::aichats;
+use super::files::cached_file_names;
 
-fn get_ai_images_directory() -> String {
+pub(super) fn get_ai_images_directory() -> String {
     let local_data_dir = filesystem::get_local_data_directory();
     format!("{}/ai_images", local_data_dir)
 }
@@ -36,9 +37,11 @@ pub fn load_image_data(uuid: &str) -> Result<String, anyhow::Error> {
 
 pub async fn collect_garbage() {
     let images_dir = get_ai_images_directory();
-    let used_uuids = aichats::get_all_image_item_uuids().await.unwrap_or_default();
-    let used_set: HashSet<String> = used_uuids.into_iter()
+    let used_image_uuids = aichats::get_all_image_item_uuids().await.unwrap_or_default();
+    let used_file_uuids = aichats::get_all_file_item_uuids().await.unwrap_or_default();
+    let used_set: HashSet<String> = used_image_uuids.into_iter()
         .map(|uuid| format!("{}.png", uuid))
+        .chain(used_file_uuids.iter().flat_map(|uuid| cached_file_names(uuid)))
         .collect();
 
     if let Ok(entries) = std::fs::read_dir(&images_dir) {
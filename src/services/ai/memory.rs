@@ -0,0 +1,124 @@
+use std::collections::HashSet;
+use std::sync::{LazyLock, RwLock};
+
+use crate::config::read_config;
+use crate::sql::wrappers::aimemories;
+use super::types::{AiConversationItemPayload, AiMemory};
+use super::{AiChannelMessage, CHANNEL, SESSION};
+
+/// The maximum amount of related memories injected into the system prompt.
+const MAX_PROMPT_MEMORIES: usize = 30;
+
+/// The maximum amount of memories returned by a single recall.
+const MAX_RECALLED_MEMORIES: usize = 20;
+
+static MEMORIES: LazyLock<RwLock<Vec<AiMemory>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+pub fn is_enabled() -> bool {
+    read_config().ai.features.memory
+}
+
+pub async fn init() {
+    refresh().await;
+}
+
+async fn refresh() {
+    match aimemories::get_all_memories().await {
+        Ok(memories) => *MEMORIES.write().unwrap() = memories,
+        Err(err) => error!(%err, "Could not get AI memories"),
+    }
+
+    if let Some(channel) = CHANNEL.get() {
+        channel.spawn_send(AiChannelMessage::MemoriesChanged);
+    }
+}
+
+/// Returns every stored memory, most recently updated first.
+pub fn get_all() -> Vec<AiMemory> {
+    MEMORIES.read().unwrap().clone()
+}
+
+pub async fn remember(content: &str) -> anyhow::Result<i64> {
+    let id = aimemories::add_memory(content.trim()).await?;
+    refresh().await;
+    Ok(id)
+}
+
+pub async fn update(memory_id: i64, content: &str) -> anyhow::Result<()> {
+    aimemories::update_memory(memory_id, content.trim()).await?;
+    refresh().await;
+    Ok(())
+}
+
+pub async fn forget(memory_id: i64) -> anyhow::Result<bool> {
+    let existed = aimemories::delete_memory(memory_id).await?;
+    refresh().await;
+    Ok(existed)
+}
+
+fn words(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() > 2)
+        .map(str::to_lowercase)
+        .collect()
+}
+
+/// Ranks memories by how many words they share with the query, ties are broken by recency.
+/// Memories that share no words with a non-empty query are dropped.
+fn rank(memories: Vec<AiMemory>, query: &str) -> Vec<AiMemory> {
+    let query_words = words(query);
+    let mut scored = memories.into_iter()
+        .map(|memory| {
+            let score = words(&memory.content).intersection(&query_words).count();
+            (score, memory)
+        })
+        .filter(|(score, _)| query_words.is_empty() || *score > 0)
+        .collect::<Vec<_>>();
+
+    // The sort is stable, and memories are already ordered by recency
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, memory)| memory).collect()
+}
+
+/// Looks up the memories that are relevant to a query.
+pub fn recall(query: &str) -> Vec<AiMemory> {
+    let mut memories = rank(get_all(), query);
+    memories.truncate(MAX_RECALLED_MEMORIES);
+    memories
+}
+
+fn latest_user_message() -> Option<String> {
+    let session = SESSION.get()?;
+    let items = session.items.read().unwrap();
+    items.iter().rev().find_map(|item| match &item.payload {
+        AiConversationItemPayload::Message { role, content, .. } if role == "user" => Some(content.clone()),
+        _ => None,
+    })
+}
+
+/// Formats the memories related to the latest user message for the system prompt. The
+/// others can still be looked up with the recall tool.
+pub fn prompt_section() -> String {
+    let memories = get_all();
+    if memories.is_empty() {
+        return "No memories stored yet.".to_owned();
+    }
+
+    // Without any words to go by, nothing counts as related
+    let query = latest_user_message().unwrap_or_default();
+    let mut memories = if words(&query).is_empty() {
+        Vec::new()
+    } else {
+        rank(memories, &query)
+    };
+    memories.truncate(MAX_PROMPT_MEMORIES);
+
+    if memories.is_empty() {
+        return "None related to this message.".to_owned();
+    }
+
+    memories.iter()
+        .map(|memory| format!("- [{}] {}", memory.id, memory.content))
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -2,12 +2,16 @@ mod tools;
 mod variables;
 mod services;
 pub mod images;
+pub mod files;
+pub mod memory;
+pub mod confirmation;
+pub mod text_actions;
 pub mod types;
 pub mod conversation;
 
 use std::sync::{Arc, LazyLock, OnceLock, RwLock};
 
-use crate::config::read_config;
+use crate::config::{AiService as AiConfigService, read_config};
 use crate::sql::wrappers::aichats;
 use crate::utils::broadcast::BroadcastChannel;
 use self::types::{
@@ -21,6 +25,7 @@ pub static SESSION: OnceLock<AiSession> = OnceLock::new();
 static SERVICES: LazyLock<Vec<Box<dyn services::AiService>>> = LazyLock::new(|| vec![
     Box::new(services::openai::OpenAiService::default()),
     Box::new(services::gemini::GeminiService {}),
+    Box::new(services::replay::ReplayService::default()),
 ]);
 
 pub const TIMESTAMP_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
@@ -40,6 +45,10 @@ pub enum AiChannelMessage {
     ConversationAdded(AiConversation),
     ConversationRenamed(i64, String), // (conversation ID, new title)
     ConversationDeleted(i64), // conversation ID
+    ConversationUpdated(AiConversation), // pinned, archived, folder or tags changed
+    MemoriesChanged,
+    ConfirmationRequested(u64, String), // (confirmation ID, description)
+    ConfirmationResolved(u64, bool), // (confirmation ID, allowed)
 }
 
 pub fn is_currently_in_cycle() -> bool {
@@ -145,7 +154,8 @@ pub async fn trim_items(down_to_item_id: i64) {
 
 pub async fn activate() {
     let app_config = read_config().clone();
-    if !app_config.ai.enabled || (app_config.ai.openai.api_key.is_empty() && app_config.ai.gemini.api_key.is_empty()) {
+    let has_api_key = !app_config.ai.openai.api_key.is_empty() || !app_config.ai.gemini.api_key.is_empty();
+    if !app_config.ai.enabled || (!has_api_key && app_config.ai.service != AiConfigService::Replay) {
         return;
     }
 
@@ -163,6 +173,8 @@ pub async fn activate() {
     let _ = SESSION.set(session);
     let _ = CHANNEL.set(BroadcastChannel::new(100));
 
+    memory::init().await;
+
     if let Ok(Some(id)) = aichats::get_state_conversation_id().await {
         conversation::load_conversation(id).await;
     } else {
@@ -178,6 +190,28 @@ pub async fn start_request_cycle() {
         return;
     };
 
+    let Some(channel) = CHANNEL.get() else {
+        warn!("AI channel not initialized");
+        return;
+    };
+
+    let config = read_config().clone();
+    let service = SERVICES.iter()
+        .find(|s| s.service() == config.ai.service)
+        .unwrap_or(&SERVICES[0]);
+
+    run_request_cycle(session, channel, service.as_ref(), config.ai.user_message_timestamps, write_item_payload).await;
+}
+
+/// Requests from `service` until it stops asking for more, calling the tools it asks for in
+/// between. Every item, including the tool outputs, is stored with `write_item`.
+async fn run_request_cycle<F: Future<Output = i64>>(
+    session: &AiSession,
+    channel: &BroadcastChannel<AiChannelMessage>,
+    service: &dyn services::AiService,
+    user_message_timestamps: bool,
+    write_item: impl Fn(AiConversationItemPayload) -> F,
+) {
     {
         let mut currently_in_cycle = session.currently_in_cycle.write().unwrap();
         if *currently_in_cycle {
@@ -187,25 +221,15 @@ pub async fn start_request_cycle() {
         *currently_in_cycle = true;
     }
 
-    let Some(channel) = CHANNEL.get() else {
-        warn!("AI channel not initialized");
-        return;
-    };
-
     channel.send(AiChannelMessage::CycleStarted).await;
 
-    let config = read_config().clone();
-    let service = SERVICES.iter()
-        .find(|s| s.service() == config.ai.service)
-        .unwrap_or(&SERVICES[0]);
-    
     let mut failed = false;
     loop {
         let items = session.items.read().unwrap()
             .clone()
             .iter_mut()
             .map(|item| {
-                if config.ai.user_message_timestamps {
+                if user_message_timestamps {
                     item.inject_timestamp_into_content();
                 }
                 item.clone()
@@ -216,7 +240,7 @@ pub async fn start_request_cycle() {
         match service.make_stream_request(items, channel, stop_cycle_flag).await {
             Ok(result) => {
                 for (index, item) in result.items.iter().enumerate() {
-                    let id = write_item_payload(item.clone()).await;
+                    let id = write_item(item.clone()).await;
                     if index == 0 {
                         channel.send(AiChannelMessage::StreamComplete(id)).await;
                     }
@@ -229,7 +253,7 @@ pub async fn start_request_cycle() {
                         let name = name.clone();
                         let args = arguments.clone();
                         Some(tokio::spawn(async move {
-                            let result = tools::call_tool(&name, &args);
+                            let result = tools::call_tool(&name, &args).await;
                             (id, name, result)
                         }))
                     } else {
@@ -252,7 +276,7 @@ pub async fn start_request_cycle() {
                     .collect::<Vec<_>>();
 
                 for payload in function_call_outputs {
-                    write_item_payload(payload).await;
+                    write_item(payload).await;
                 }
 
                 // If more data should not be requested, break the cycle
@@ -302,4 +326,17 @@ pub async fn send_user_image(uuid: &str) -> i64 {
     write_item_payload(AiConversationItemPayload::Image {
         uuid: uuid.to_owned(),
     }).await
+}
+
+pub async fn send_user_file(uuid: &str, name: &str, mime_type: &str) -> i64 {
+    if SESSION.get().is_none() {
+        warn!("AI session not initialized");
+        return 0;
+    }
+
+    write_item_payload(AiConversationItemPayload::File {
+        uuid: uuid.to_owned(),
+        name: name.to_owned(),
+        mime_type: mime_type.to_owned(),
+    }).await
 }
\ No newline at end of file
@@ -12,10 +12,14 @@ use gemini_rust::{
 use crate::config::{AiService as AiConfigService, GeminiThinkingLevel, read_config};
 use crate::utils::broadcast::BroadcastChannel;
 use crate::services::ai::images::load_image_data;
+use crate::services::ai::files::{inline_file_text, load_native_file_data};
 use crate::services::ai::tools::gemini::add_gemini_tools;
 use super::super::variables::transform_variables;
 use super::super::{AiChannelMessage, AiConversationItem, AiConversationItemPayload, AiConversationDelta};
 
+// File types that Gemini accepts as inline data, anything else gets inlined as text
+const NATIVE_FILE_MIME_TYPES: &[&str] = &["application/pdf"];
+
 #[derive(Default, Debug, Clone)]
 pub struct GeminiContext {
     pub reasoning: Option<AiConversationItemPayload>,
@@ -100,6 +104,26 @@ impl GeminiService {
                     });
                 },
 
+                AiConversationItemPayload::File { uuid, name, mime_type } => {
+                    flush_assistant_parts(&mut assistant_parts, &mut builder);
+
+                    if let Some(data) = load_native_file_data(uuid, mime_type, NATIVE_FILE_MIME_TYPES) {
+                        user_parts.push(Part::InlineData {
+                            inline_data: Blob {
+                                mime_type: mime_type.clone(),
+                                data,
+                            },
+                            media_resolution: None,
+                        });
+                    } else {
+                        user_parts.push(Part::Text {
+                            text: inline_file_text(uuid, name, mime_type),
+                            thought: Some(false),
+                            thought_signature: None,
+                        });
+                    }
+                },
+
                 AiConversationItemPayload::Reasoning { summary, encrypted_content, .. } => {
                     flush_user_parts(&mut user_parts, &mut builder);
                     let thought_signature = if encrypted_content.is_empty() {
@@ -161,11 +185,12 @@ impl super::AiService for GeminiService {
         AiConfigService::Gemini
     }
 
-    fn make_stream_request(
+    fn make_request(
         &self,
         items: Vec<AiConversationItem>,
         channel: &BroadcastChannel<AiChannelMessage>,
         stop_cycle_flag: Arc<RwLock<bool>>,
+        kind: super::AiRequestKind,
     ) -> Pin<Box<dyn Future<Output = anyhow::Result<super::AiServiceResult>> + 'static + Send>> {
         let channel = channel.clone();
         let config = read_config();
@@ -181,7 +206,6 @@ impl super::AiService for GeminiService {
                 .expect("Failed to create Gemini client");
 
             let mut builder = Self::transform_items_into_builder(items, &client)
-                .with_system_prompt(transform_variables(system_prompt.as_str()))
                 .with_thinking_config(ThinkingConfig {
                     thinking_budget: (!matches!(thinking_level, GeminiThinkingLevel::Low | GeminiThinkingLevel::High))
                         .then_some(thinking_budget),
@@ -193,7 +217,10 @@ impl super::AiService for GeminiService {
                     },
                 });
             
-            builder = add_gemini_tools(builder);
+            // Plain requests get neither the chat's system prompt nor its tools
+            if kind == super::AiRequestKind::Chat {
+                builder = add_gemini_tools(builder.with_system_prompt(transform_variables(system_prompt.as_str())));
+            }
 
             let mut should_request_more = true;
             let mut context = GeminiContext::default();
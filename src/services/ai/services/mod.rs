@@ -1,5 +1,6 @@
 pub mod openai;
 pub mod gemini;
+pub mod replay;
 
 use std::pin::Pin;
 use std::sync::{Arc, RwLock};
@@ -13,13 +14,65 @@ pub struct AiServiceResult {
     pub should_request_more: bool,
 }
 
+/// What a request is for, which decides what gets sent along with the items.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AiRequestKind {
+    /// A turn in the conversation, with the chat's system prompt and tools.
+    Chat,
+    /// A one-off request with only the given items.
+    Plain,
+}
+
 pub trait AiService: Send + Sync {
     fn service(&self) -> AiConfigService;
 
-    fn make_stream_request(
+    fn make_request(
         &self,
         items: Vec<AiConversationItem>,
         channel: &BroadcastChannel<AiChannelMessage>,
         stop_cycle_flag: Arc<RwLock<bool>>,
+        kind: AiRequestKind,
     ) -> Pin<Box<dyn Future<Output = anyhow::Result<AiServiceResult>> + 'static + Send>>;
-}
\ No newline at end of file
+
+    fn make_stream_request(
+        &self,
+        items: Vec<AiConversationItem>,
+        channel: &BroadcastChannel<AiChannelMessage>,
+        stop_cycle_flag: Arc<RwLock<bool>>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<AiServiceResult>> + 'static + Send>> {
+        self.make_request(items, channel, stop_cycle_flag, AiRequestKind::Chat)
+    }
+
+    /// Sends a single user message, without the chat's system prompt or tools, and returns the
+    /// reply. Streamed chunks still go to `channel`.
+    fn make_plain_request(
+        &self,
+        content: String,
+        channel: &BroadcastChannel<AiChannelMessage>,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<String>> + 'static + Send>> {
+        let items = vec![AiConversationItem {
+            id: 0,
+            conversation_id: 0,
+            payload: AiConversationItemPayload::Message {
+                id: String::new(),
+                role: "user".to_owned(),
+                content,
+                thought_signature: None,
+            },
+            timestamp: None,
+        }];
+
+        let request = self.make_request(items, channel, Arc::new(RwLock::new(false)), AiRequestKind::Plain);
+        Box::pin(async move {
+            let reply = request.await?.items.into_iter()
+                .filter_map(|item| match item {
+                    AiConversationItemPayload::Message { role, content, .. } if role == "assistant" => Some(content),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+
+            Ok(reply.trim().to_owned())
+        })
+    }
+}
@@ -9,7 +9,7 @@ use async_openai::types::responses::{
     AssistantRole,
     CreateResponseArgs,
     FunctionCallOutput, FunctionCallOutputItemParam, FunctionTool, FunctionToolCall,
-    InputContent, InputImageContent, InputMessage, InputRole, InputTextContent,
+    InputContent, InputFileArgs, InputImageContent, InputMessage, InputRole, InputTextContent,
     ImageDetail,
     Item, MessageItem,
     OutputContent, OutputItem, OutputMessage, OutputMessageContent, OutputStatus, OutputTextContent,
@@ -25,9 +25,13 @@ use crate::utils::broadcast::BroadcastChannel;
 use super::super::variables::transform_variables;
 use super::super::{AiChannelMessage, AiConversationItem, AiConversationItemPayload, AiConversationDelta};
 use super::super::images::load_image_data;
+use super::super::files::{inline_file_text, load_native_file_data};
 use super::super::types::AiFunction;
 use super::super::tools;
 
+// File types that the Responses API accepts as native file inputs, anything else gets inlined as text
+const NATIVE_FILE_MIME_TYPES: &[&str] = &["application/pdf"];
+
 #[derive(Debug, Default, Clone)]
 pub struct OpenAiService {
     pub client: Arc<RwLock<Option<Client<OpenAIConfig>>>>,
@@ -42,7 +46,7 @@ impl OpenAiService {
         self.client.write().unwrap().replace(Client::with_config(config));
     }
 
-    async fn create_stream(&self, items: Vec<AiConversationItem>) -> Result<ResponseStream, OpenAIError> {
+    async fn create_stream(&self, items: Vec<AiConversationItem>, kind: super::AiRequestKind) -> Result<ResponseStream, OpenAIError> {
         if self.client.read().unwrap().is_none() {
             self.make_client();
         }
@@ -55,58 +59,47 @@ impl OpenAiService {
         let app_config = read_config().clone();
         let mut native_items = Self::transform_items_into_native(items);
 
-        native_items.insert(0, Item::Message(MessageItem::Input(InputMessage {
-            role: InputRole::Developer,
-            content: vec![InputContent::InputText(InputTextContent {
-                text: transform_variables(&app_config.ai.prompt),
-            })],
-            status: None,
-        })));
-
-        let tools = tools::get_tools()
-            .into_iter()
-            .map(Self::transform_function_into_tool)
-            .collect::<Vec<Tool>>();
-
-        let request = if !matches!(app_config.ai.openai.reasoning_effort, OpenAiReasoningEffort::None) {
-            CreateResponseArgs::default()
-                .max_output_tokens(2048_u32)
-                .stream(true)
-                .model(app_config.ai.openai.model.as_str())
-                .service_tier(match app_config.ai.openai.service_tier {
-                    OpenAiServiceTier::Flex => ServiceTier::Flex,
-                    OpenAiServiceTier::Priority => ServiceTier::Priority,
-                    _ => ServiceTier::Default,
-                })
-                .reasoning(Reasoning {
-                    effort: Some(match app_config.ai.openai.reasoning_effort {
-                        OpenAiReasoningEffort::Minimal => ReasoningEffort::Minimal,
-                        OpenAiReasoningEffort::Low => ReasoningEffort::Low,
-                        OpenAiReasoningEffort::Medium => ReasoningEffort::Medium,
-                        OpenAiReasoningEffort::High => ReasoningEffort::High,
-                        OpenAiReasoningEffort::Xhigh => ReasoningEffort::Xhigh,
-                        _ => ReasoningEffort::None,
-                    }),
-                    summary: Some(ReasoningSummary::Auto),
-                })
-                .tools(tools)
-                .input(native_items)
-                .build()?
-        } else {
-            CreateResponseArgs::default()
-                .max_output_tokens(2048_u32)
-                .stream(true)
-                .model(app_config.ai.openai.model.as_str())
-                .service_tier(match app_config.ai.openai.service_tier {
-                    OpenAiServiceTier::Flex => ServiceTier::Flex,
-                    OpenAiServiceTier::Priority => ServiceTier::Priority,
-                    _ => ServiceTier::Default,
-                })
-                .tools(tools)
-                .input(native_items)
-                .build()?
-        };
+        let mut args = CreateResponseArgs::default();
+        args.max_output_tokens(2048_u32)
+            .stream(true)
+            .model(app_config.ai.openai.model.as_str())
+            .service_tier(match app_config.ai.openai.service_tier {
+                OpenAiServiceTier::Flex => ServiceTier::Flex,
+                OpenAiServiceTier::Priority => ServiceTier::Priority,
+                _ => ServiceTier::Default,
+            });
+
+        if !matches!(app_config.ai.openai.reasoning_effort, OpenAiReasoningEffort::None) {
+            args.reasoning(Reasoning {
+                effort: Some(match app_config.ai.openai.reasoning_effort {
+                    OpenAiReasoningEffort::Minimal => ReasoningEffort::Minimal,
+                    OpenAiReasoningEffort::Low => ReasoningEffort::Low,
+                    OpenAiReasoningEffort::Medium => ReasoningEffort::Medium,
+                    OpenAiReasoningEffort::High => ReasoningEffort::High,
+                    OpenAiReasoningEffort::Xhigh => ReasoningEffort::Xhigh,
+                    _ => ReasoningEffort::None,
+                }),
+                summary: Some(ReasoningSummary::Auto),
+            });
+        }
+
+        // Plain requests get neither the chat's system prompt nor its tools
+        if kind == super::AiRequestKind::Chat {
+            native_items.insert(0, Item::Message(MessageItem::Input(InputMessage {
+                role: InputRole::Developer,
+                content: vec![InputContent::InputText(InputTextContent {
+                    text: transform_variables(&app_config.ai.prompt),
+                })],
+                status: None,
+            })));
+
+            args.tools(tools::get_tools()
+                .into_iter()
+                .map(Self::transform_function_into_tool)
+                .collect::<Vec<Tool>>());
+        }
 
+        let request = args.input(native_items).build()?;
         client.responses().create_stream(request).await
     }
 
@@ -185,6 +178,24 @@ impl OpenAiService {
                         image_url: Some(format!("data:image/png;base64,{}", base64_data)),
                     }));
                 },
+
+                AiConversationItemPayload::File { uuid, name, mime_type } => {
+                    let native_file = load_native_file_data(&uuid, &mime_type, NATIVE_FILE_MIME_TYPES)
+                        .and_then(|base64_data| InputFileArgs::default()
+                            .filename(name.clone())
+                            .file_data(format!("data:{};base64,{}", mime_type, base64_data))
+                            .build()
+                            .ok()
+                        );
+
+                    if let Some(native_file) = native_file {
+                        user_parts.push(InputContent::InputFile(native_file));
+                    } else {
+                        user_parts.push(InputContent::InputText(InputTextContent {
+                            text: inline_file_text(&uuid, &name, &mime_type),
+                        }));
+                    }
+                },
             }
         }
 
@@ -267,11 +278,12 @@ impl super::AiService for OpenAiService {
         AiConfigService::OpenAi
     }
 
-    fn make_stream_request(
+    fn make_request(
         &self,
         items: Vec<AiConversationItem>,
         channel: &BroadcastChannel<AiChannelMessage>,
         stop_cycle_flag: Arc<RwLock<bool>>,
+        kind: super::AiRequestKind,
     ) -> Pin<Box<dyn Future<Output = anyhow::Result<super::AiServiceResult>> + 'static + Send>> {
         let channel = channel.clone();
         let client = self.client.clone();
@@ -283,7 +295,7 @@ impl super::AiService for OpenAiService {
 
             let mut should_request_more = true;
             let mut new_items: HashMap<String, Item> = HashMap::new();
-            let mut stream = service.create_stream(items).await?;
+            let mut stream = service.create_stream(items, kind).await?;
 
             channel.send(AiChannelMessage::StreamStart).await;
 
@@ -0,0 +1,454 @@
+// Replays scripted streams from a local fixture file, so the AI sidebar can be developed
+// without an API key or network access. The fixture is re-read on every request, so it
+// can be edited while the shell is running. Which response is played back follows from the
+// conversation, so every conversation starts from the first one.
+
+use std::path::Path;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+use serde::Deserialize;
+
+use crate::config::{AiService as AiConfigService, read_config};
+use crate::utils::broadcast::BroadcastChannel;
+use crate::utils::filesystem;
+use super::super::{AiChannelMessage, AiConversationItem, AiConversationItemPayload, AiConversationDelta};
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplayFixture {
+    pub responses: Vec<ReplayResponse>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReplayResponse {
+    pub events: Vec<ReplayEvent>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ReplayEvent {
+    Text {
+        delta: String,
+    },
+
+    Reasoning {
+        delta: String,
+    },
+
+    ToolCall {
+        name: String,
+        #[serde(default)]
+        arguments: serde_json::Value,
+        // If present, the output of this tool call is compared against this on the next request
+        expected_output: Option<serde_json::Value>,
+    },
+
+    Delay {
+        ms: u64,
+    },
+
+    Error {
+        message: String,
+    },
+}
+
+#[derive(Debug, Default)]
+pub struct ReplayService {
+    /// A fixture to play back, and whether to loop it, instead of the ones in the config.
+    fixture: Option<(ReplayFixture, bool)>,
+}
+
+impl ReplayService {
+    fn fixture_path() -> String {
+        let path = filesystem::expand_tilde(&read_config().ai.replay.fixture_path);
+        if Path::new(&path).is_absolute() {
+            path
+        } else {
+            format!("{}/{}", filesystem::get_config_directory(), path)
+        }
+    }
+
+    pub fn load_fixture(path: &str) -> anyhow::Result<ReplayFixture> {
+        Self::parse_fixture(&std::fs::read_to_string(path)?, path)
+    }
+
+    /// Parses a fixture in the format given by the extension of `path`, JSON by default.
+    fn parse_fixture(contents: &str, path: &str) -> anyhow::Result<ReplayFixture> {
+        let fixture = match Path::new(path).extension().and_then(|extension| extension.to_str()) {
+            Some("toml") => toml::from_str(contents)?,
+            Some("yaml" | "yml") => serde_yaml_ng::from_str(contents)?,
+            _ => serde_json::from_str(contents)?,
+        };
+
+        Ok(fixture)
+    }
+
+    /// The index of the next response, from the responses already in the conversation. Every
+    /// run of assistant items that isn't interrupted by the user or tool outputs is one response.
+    fn response_index(items: &[AiConversationItem]) -> usize {
+        let mut responses = 0;
+        let mut in_response = false;
+
+        for item in items {
+            let from_assistant = match &item.payload {
+                AiConversationItemPayload::Message { role, .. } => role == "assistant",
+                AiConversationItemPayload::Reasoning { .. } | AiConversationItemPayload::FunctionCall { .. } => true,
+                _ => false,
+            };
+
+            if from_assistant && !in_response {
+                responses += 1;
+            }
+
+            in_response = from_assistant;
+        }
+
+        responses
+    }
+
+    fn call_id(response_index: usize, event_index: usize) -> String {
+        format!("replay_{}_{}", response_index, event_index)
+    }
+
+    fn parse_call_id(call_id: &str) -> Option<(usize, usize)> {
+        let (response_index, event_index) = call_id.strip_prefix("replay_")?.split_once('_')?;
+        Some((response_index.parse().ok()?, event_index.parse().ok()?))
+    }
+
+    /// Finds tool call outputs in the conversation that do not match what the fixture expected,
+    /// as (call ID, expected, actual).
+    fn mismatched_tool_outputs(items: &[AiConversationItem], fixture: &ReplayFixture) -> Vec<(String, serde_json::Value, serde_json::Value)> {
+        items.iter()
+            .filter_map(|item| {
+                let AiConversationItemPayload::FunctionCallOutput { call_id, output, .. } = &item.payload else {
+                    return None;
+                };
+
+                let (response_index, event_index) = Self::parse_call_id(call_id)?;
+                let ReplayEvent::ToolCall { expected_output: Some(expected), .. } = fixture.responses
+                    .get(response_index % fixture.responses.len().max(1))?
+                    .events.get(event_index)?
+                else {
+                    return None;
+                };
+
+                let actual = serde_json::from_str::<serde_json::Value>(output).unwrap_or(serde_json::Value::Null);
+                (actual != *expected).then(|| (call_id.clone(), expected.clone(), actual))
+            })
+            .collect()
+    }
+
+    /// Plays back the response at `response_index` of the fixture.
+    async fn play(
+        fixture: ReplayFixture,
+        response_index: usize,
+        loop_responses: bool,
+        channel: BroadcastChannel<AiChannelMessage>,
+        stop_cycle_flag: Arc<RwLock<bool>>,
+    ) -> anyhow::Result<super::AiServiceResult> {
+        if fixture.responses.is_empty() {
+            anyhow::bail!("Replay fixture has no responses");
+        }
+
+        let response = if loop_responses {
+            &fixture.responses[response_index % fixture.responses.len()]
+        } else {
+            fixture.responses.get(response_index)
+                .ok_or_else(|| anyhow::anyhow!("Replay fixture ran out of responses"))?
+        };
+
+        let mut should_request_more = true;
+        let mut reasoning: Option<String> = None;
+        let mut message: Option<String> = None;
+        let mut tool_calls: Vec<AiConversationItemPayload> = vec![];
+
+        channel.send(AiChannelMessage::StreamStart).await;
+
+        for (index, event) in response.events.iter().enumerate() {
+            if *stop_cycle_flag.read().unwrap() {
+                break;
+            }
+
+            match event {
+                ReplayEvent::Text { delta } => {
+                    message.get_or_insert_default().push_str(delta);
+                    channel.send(AiChannelMessage::StreamChunk(AiConversationDelta::Message(delta.clone()))).await;
+                },
+
+                ReplayEvent::Reasoning { delta } => {
+                    reasoning.get_or_insert_default().push_str(delta);
+                    channel.send(AiChannelMessage::StreamChunk(AiConversationDelta::Reasoning(delta.clone()))).await;
+                },
+
+                ReplayEvent::ToolCall { name, arguments, .. } => {
+                    // Same as the other services, power actions end the cycle
+                    if name == "perform_power_action" {
+                        should_request_more = false;
+                    }
+
+                    tool_calls.push(AiConversationItemPayload::FunctionCall {
+                        id: String::new(),
+                        name: name.clone(),
+                        arguments: arguments.to_string(),
+                        call_id: Self::call_id(response_index, index),
+                        thought_signature: None,
+                    });
+
+                    channel.send(AiChannelMessage::ToolCall(name.clone(), arguments.to_string())).await;
+                },
+
+                ReplayEvent::Delay { ms } => {
+                    tokio::time::sleep(Duration::from_millis(*ms)).await;
+                },
+
+                ReplayEvent::Error { message } => {
+                    if let Ok(mut stop_flag) = stop_cycle_flag.write() {
+                        *stop_flag = false;
+                    }
+
+                    anyhow::bail!("{}", message);
+                },
+            }
+        }
+
+        if let Ok(mut stop_flag) = stop_cycle_flag.write() {
+            *stop_flag = false;
+        }
+
+        let mut result_items = vec![];
+        if let Some(summary) = reasoning {
+            result_items.push(AiConversationItemPayload::Reasoning {
+                id: String::new(),
+                summary,
+                encrypted_content: String::new(),
+            });
+        }
+
+        if let Some(content) = message {
+            result_items.push(AiConversationItemPayload::Message {
+                id: String::new(),
+                role: "assistant".to_owned(),
+                content,
+                thought_signature: None,
+            });
+        }
+
+        let has_tool_calls = !tool_calls.is_empty();
+        result_items.extend(tool_calls);
+
+        Ok(super::AiServiceResult {
+            items: result_items,
+            should_request_more: has_tool_calls && should_request_more,
+        })
+    }
+}
+
+impl super::AiService for ReplayService {
+    fn service(&self) -> AiConfigService {
+        AiConfigService::Replay
+    }
+
+    fn make_request(
+        &self,
+        items: Vec<AiConversationItem>,
+        channel: &BroadcastChannel<AiChannelMessage>,
+        stop_cycle_flag: Arc<RwLock<bool>>,
+        _kind: super::AiRequestKind,
+    ) -> Pin<Box<dyn Future<Output = anyhow::Result<super::AiServiceResult>> + 'static + Send>> {
+        let channel = channel.clone();
+        let (fixture, loop_responses) = match &self.fixture {
+            Some((fixture, loop_responses)) => (Ok(fixture.clone()), *loop_responses),
+            None => (Self::load_fixture(&Self::fixture_path()), read_config().ai.replay.loop_responses),
+        };
+
+        Box::pin(async move {
+            let fixture = fixture?;
+
+            for (call_id, expected, actual) in Self::mismatched_tool_outputs(&items, &fixture) {
+                warn!(call_id, %expected, %actual, "Replayed tool call output does not match the fixture");
+            }
+
+            let response_index = Self::response_index(&items);
+            Self::play(fixture, response_index, loop_responses, channel, stop_cycle_flag).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use super::super::super::{run_request_cycle, AiSession};
+
+    const TOOL_LOOP_FIXTURE: &str = r#"
+responses:
+  - events:
+      - type: reasoning
+        delta: Checking the weather
+      - type: tool_call
+        name: get_weather
+        arguments: {}
+        expected_output: { success: false, error: "Unknown function: get_weather" }
+  - events:
+      - type: text
+        delta: "Let me check "
+      - type: tool_call
+        name: get_weather
+        arguments: {}
+        expected_output: { temperature: 25 }
+  - events:
+      - type: text
+        delta: It's warm.
+"#;
+
+    const STOP_FIXTURE: &str = r#"{
+        "responses": [
+            { "events": [
+                { "type": "text", "delta": "Hello" },
+                { "type": "delay", "ms": 200 },
+                { "type": "tool_call", "name": "get_weather", "arguments": {} }
+            ] }
+        ]
+    }"#;
+
+    fn user_message(content: &str) -> AiConversationItemPayload {
+        AiConversationItemPayload::Message {
+            id: String::new(),
+            role: "user".to_owned(),
+            content: content.to_owned(),
+            thought_signature: None,
+        }
+    }
+
+    fn session() -> AiSession {
+        AiSession {
+            conversation: Arc::new(RwLock::new(None)),
+            items: Arc::new(RwLock::new(Vec::new())),
+            currently_in_cycle: Arc::new(RwLock::new(false)),
+            stop_cycle_flag: Arc::new(RwLock::new(false)),
+        }
+    }
+
+    /// Stores items in the session only, instead of the database.
+    fn write_item(session: &AiSession) -> impl Fn(AiConversationItemPayload) -> std::future::Ready<i64> + '_ {
+        move |payload| {
+            let mut items = session.items.write().unwrap();
+            let id = items.len() as i64 + 1;
+            items.push(AiConversationItem { id, conversation_id: 0, payload, timestamp: None });
+            std::future::ready(id)
+        }
+    }
+
+    /// Sends a user message and runs a cycle, returning the messages sent on the channel.
+    async fn run_cycle(session: &AiSession, service: &ReplayService, message: &str) -> Vec<AiChannelMessage> {
+        let channel = BroadcastChannel::new(100);
+        let mut receiver = channel.subscribe();
+
+        write_item(session)(user_message(message)).await;
+        run_request_cycle(session, &channel, service, false, write_item(session)).await;
+
+        std::iter::from_fn(|| receiver.try_recv().ok()).collect()
+    }
+
+    /// A short description of each item, to compare whole conversations.
+    fn describe(session: &AiSession) -> Vec<String> {
+        session.items.read().unwrap().iter()
+            .map(|item| match &item.payload {
+                AiConversationItemPayload::Message { role, content, .. } => format!("{}: {}", role, content),
+                AiConversationItemPayload::Reasoning { summary, .. } => format!("reasoning: {}", summary),
+                AiConversationItemPayload::FunctionCall { call_id, .. } => format!("call: {}", call_id),
+                AiConversationItemPayload::FunctionCallOutput { call_id, .. } => format!("output: {}", call_id),
+                _ => "other".to_owned(),
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn tool_loop_requests_more_until_a_response_without_tool_calls() {
+        let fixture = ReplayService::parse_fixture(TOOL_LOOP_FIXTURE, "fixture.yaml").unwrap();
+        let service = ReplayService { fixture: Some((fixture.clone(), false)) };
+        let session = session();
+
+        let messages = run_cycle(&session, &service, "How's the weather?").await;
+        assert_eq!(describe(&session), [
+            "user: How's the weather?",
+            "reasoning: Checking the weather",
+            "call: replay_0_1",
+            "output: replay_0_1",
+            "assistant: Let me check ",
+            "call: replay_1_1",
+            "output: replay_1_1",
+            "assistant: It's warm.",
+        ]);
+
+        assert!(matches!(messages.first(), Some(AiChannelMessage::CycleStarted)));
+        assert!(matches!(messages.last(), Some(AiChannelMessage::CycleFinished)));
+        assert_eq!(messages.iter().filter(|message| matches!(message, AiChannelMessage::StreamStart)).count(), 3);
+        assert_eq!(messages.iter().filter(|message| matches!(message, AiChannelMessage::ToolCall(..))).count(), 2);
+        assert!(!*session.currently_in_cycle.read().unwrap());
+
+        // Both calls are at the same event index, so each has to be checked against its own response
+        let items = session.items.read().unwrap().clone();
+        let mismatched = ReplayService::mismatched_tool_outputs(&items, &fixture);
+        assert_eq!(mismatched.len(), 1);
+        assert_eq!(mismatched[0].0, "replay_1_1");
+
+        // Every response was played back
+        let messages = run_cycle(&session, &service, "And tomorrow?").await;
+        assert!(matches!(messages.last(), Some(AiChannelMessage::CycleFailed)));
+        assert!(!*session.currently_in_cycle.read().unwrap());
+    }
+
+    #[tokio::test]
+    async fn every_conversation_starts_from_the_first_response() {
+        let fixture = ReplayService::parse_fixture(TOOL_LOOP_FIXTURE, "fixture.yaml").unwrap();
+        let service = ReplayService { fixture: Some((fixture, false)) };
+        let (first, second) = (session(), session());
+
+        run_cycle(&first, &service, "How's the weather?").await;
+        run_cycle(&second, &service, "How's the weather?").await;
+
+        assert_eq!(describe(&first), describe(&second));
+    }
+
+    #[tokio::test]
+    async fn stop_flag_ends_the_response_and_is_reset() {
+        let fixture = ReplayService::parse_fixture(STOP_FIXTURE, "fixture.json").unwrap();
+        let service = ReplayService { fixture: Some((fixture, false)) };
+        let session = session();
+
+        let stop = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            *session.stop_cycle_flag.write().unwrap() = true;
+        };
+
+        let (messages, ()) = tokio::join!(run_cycle(&session, &service, "Hi"), stop);
+
+        assert_eq!(describe(&session), ["user: Hi", "assistant: Hello"]);
+        assert!(!messages.iter().any(|message| matches!(message, AiChannelMessage::ToolCall(..))));
+        assert!(matches!(messages.last(), Some(AiChannelMessage::CycleFinished)));
+        assert!(!*session.stop_cycle_flag.read().unwrap());
+    }
+
+    #[test]
+    fn tool_outputs_are_compared_to_their_own_response() {
+        let fixture = ReplayService::parse_fixture(TOOL_LOOP_FIXTURE, "fixture.yaml").unwrap();
+        let output = |call_id: &str, output: serde_json::Value| AiConversationItem {
+            id: 0,
+            conversation_id: 0,
+            payload: AiConversationItemPayload::FunctionCallOutput {
+                call_id: call_id.to_owned(),
+                output: output.to_string(),
+                name: Some("get_weather".to_owned()),
+            },
+            timestamp: None,
+        };
+
+        let items = [output("replay_1_1", json!({ "temperature": 25 }))];
+        assert!(ReplayService::mismatched_tool_outputs(&items, &fixture).is_empty());
+
+        let items = [output("replay_1_1", json!({ "temperature": 20 }))];
+        assert_eq!(ReplayService::mismatched_tool_outputs(&items, &fixture).len(), 1);
+    }
+}
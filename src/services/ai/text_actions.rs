@@ -0,0 +1,99 @@
+// One-shot AI requests on a piece of text, independent of the open conversation.
+// These are triggered through IPC, e.g. `gray-meadows-shell ai_text_action summarize primary`.
+
+use std::str::FromStr;
+
+use crate::config::read_config;
+use crate::services::clipboard;
+use crate::utils::broadcast::BroadcastChannel;
+use super::{AiChannelMessage, SERVICES};
+
+/// Prompt templates for the built-in actions, `{TEXT}` is replaced with the selected text.
+/// These can be overridden, and more actions can be added, through `[ai.text_actions]`.
+const BUILTIN_ACTIONS: &[(&str, &str)] = &[
+    (
+        "rewrite",
+        "Rewrite the following text so it reads clearly and naturally, keeping its meaning, tone and language. \
+        Reply with only the rewritten text.\n\n{TEXT}",
+    ),
+    (
+        "summarize",
+        "Summarize the following text in a few sentences, in the same language as the text. \
+        Reply with only the summary.\n\n{TEXT}",
+    ),
+    (
+        "translate",
+        "Translate the following text into English. If it is already in English, translate it into Spanish. \
+        Reply with only the translation.\n\n{TEXT}",
+    ),
+    (
+        "explain",
+        "Explain the following text briefly and simply. If it is code or a command, explain what it does.\n\n{TEXT}",
+    ),
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextSource {
+    PrimarySelection,
+    Clipboard,
+}
+
+impl FromStr for TextSource {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "primary" | "selection" => Ok(TextSource::PrimarySelection),
+            "clipboard" => Ok(TextSource::Clipboard),
+            _ => Err(anyhow::anyhow!("Unknown text source: {}", s)),
+        }
+    }
+}
+
+pub fn get_prompt_template(action: &str) -> Option<String> {
+    read_config().ai.text_actions.get(action).cloned().or_else(|| {
+        BUILTIN_ACTIONS.iter()
+            .find(|(name, _)| *name == action)
+            .map(|(_, template)| (*template).to_owned())
+    })
+}
+
+pub async fn fetch_text(source: TextSource) -> Option<String> {
+    match source {
+        TextSource::PrimarySelection => clipboard::fetch_text_primary_selection().await,
+        TextSource::Clipboard => clipboard::fetch_text_clipboard().await,
+    }
+}
+
+/// Runs an action on some text and returns the reply. Streamed chunks are sent to the
+/// given channel, not the conversation's.
+pub async fn run(
+    action: &str,
+    text: &str,
+    channel: &BroadcastChannel<AiChannelMessage>,
+) -> anyhow::Result<String> {
+    let config = read_config().clone();
+    if !config.ai.enabled {
+        anyhow::bail!("The AI service is disabled");
+    }
+
+    let template = get_prompt_template(action)
+        .ok_or_else(|| anyhow::anyhow!("Unknown text action: {}", action))?;
+
+    let content = if template.contains("{TEXT}") {
+        template.replace("{TEXT}", text)
+    } else {
+        format!("{}\n\n{}", template, text)
+    };
+
+    let service = SERVICES.iter()
+        .find(|s| s.service() == config.ai.service)
+        .unwrap_or(&SERVICES[0]);
+
+    let reply = service.make_plain_request(content, channel).await?;
+    if reply.is_empty() {
+        anyhow::bail!("The AI service returned an empty reply");
+    }
+
+    Ok(reply)
+}
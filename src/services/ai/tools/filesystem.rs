@@ -0,0 +1,395 @@
+// Read-only filesystem tools, restricted to the directories allowed in [ai.features].
+// Paths are canonicalized before they are checked, so symlinks cannot be used to escape
+// the allowed roots, and directory walks never follow symlinks. The shell's own config
+// (which holds the API keys) and well-known credential stores are denied even inside them.
+
+use std::io::{BufRead as _, BufReader, Read as _};
+use std::path::{Path, PathBuf};
+use serde_json::json;
+
+use crate::config::read_config;
+use crate::utils::filesystem::{expand_tilde, get_config_directory};
+use super::super::confirmation;
+
+const MAX_LIST_ENTRIES: usize = 200;
+const MAX_READ_BYTES: usize = 32 * 1024;
+const DEFAULT_READ_LINES: usize = 400;
+const MAX_READ_LINES: usize = 2000;
+const MAX_LINE_CHARS: usize = 500;
+const MAX_SEARCH_MATCHES: usize = 100;
+const MAX_SEARCH_FILES: usize = 5000;
+const MAX_SEARCH_DEPTH: usize = 8;
+const MAX_SEARCH_FILE_SIZE: u64 = 1024 * 1024;
+const BINARY_SNIFF_BYTES: usize = 8192;
+
+// Credentials and keys of other tools, relative to the home directory
+const DENIED_PATHS: &[&str] = &[
+    "~/.ssh",
+    "~/.gnupg",
+    "~/.password-store",
+    "~/.local/share/keyrings",
+    "~/.aws",
+    "~/.azure",
+    "~/.kube",
+    "~/.docker",
+    "~/.netrc",
+    "~/.git-credentials",
+    "~/.npmrc",
+    "~/.pypirc",
+    "~/.cargo/credentials",
+    "~/.cargo/credentials.toml",
+    "~/.config/gh",
+    "~/.config/hub",
+    "~/.config/rclone",
+    "~/.config/gcloud",
+    "~/.config/git/credentials",
+    "~/.mozilla",
+    "~/.config/google-chrome",
+    "~/.config/chromium",
+    "~/.config/BraveSoftware",
+];
+
+enum Access {
+    Allowed,
+    NeedsConfirmation,
+}
+
+fn error(message: impl std::fmt::Display) -> serde_json::Value {
+    json!({
+        "success": false,
+        "error": message.to_string()
+    })
+}
+
+fn canonical_roots(roots: &[String]) -> Vec<PathBuf> {
+    roots.iter()
+        .filter_map(|root| std::fs::canonicalize(expand_tilde(root)).ok())
+        .collect()
+}
+
+fn denied_paths() -> Vec<PathBuf> {
+    DENIED_PATHS.iter()
+        .map(|path| expand_tilde(path))
+        .chain(std::iter::once(get_config_directory()))
+        .filter_map(|path| std::fs::canonicalize(path).ok())
+        .collect()
+}
+
+fn is_denied(path: &Path, denied: &[PathBuf]) -> bool {
+    denied.iter().any(|denied| path.starts_with(denied))
+}
+
+/// Resolves a path requested by the model and checks it against the allowed roots.
+fn resolve_path(path: &str) -> Result<(PathBuf, Access), String> {
+    let expanded = PathBuf::from(expand_tilde(path));
+    if !expanded.is_absolute() {
+        return Err("Paths must be absolute or start with ~".to_owned());
+    }
+
+    let canonical = std::fs::canonicalize(&expanded)
+        .map_err(|err| format!("Could not access {}: {}", path, err))?;
+
+    if is_denied(&canonical, &denied_paths()) {
+        return Err(format!("{} may contain credentials and can't be accessed by the assistant", path));
+    }
+
+    let features = &read_config().ai.features;
+    if canonical_roots(&features.filesystem_roots).iter().any(|root| canonical.starts_with(root)) {
+        Ok((canonical, Access::Allowed))
+    } else if canonical_roots(&features.filesystem_confirm_roots).iter().any(|root| canonical.starts_with(root)) {
+        Ok((canonical, Access::NeedsConfirmation))
+    } else {
+        Err(format!("{} is outside of the directories the assistant may access", path))
+    }
+}
+
+async fn authorize(path: &str, verb: &str) -> Result<PathBuf, serde_json::Value> {
+    match resolve_path(path) {
+        Ok((canonical, Access::Allowed)) => Ok(canonical),
+
+        Ok((canonical, Access::NeedsConfirmation)) => {
+            if confirmation::request(&format!("Allow the assistant to {} {}?", verb, canonical.display())).await {
+                Ok(canonical)
+            } else {
+                Err(error("The user denied access to this path"))
+            }
+        },
+
+        Err(err) => Err(error(err)),
+    }
+}
+
+fn truncate_line(line: &str) -> String {
+    if line.chars().count() > MAX_LINE_CHARS {
+        format!("{}…", line.chars().take(MAX_LINE_CHARS).collect::<String>())
+    } else {
+        line.to_owned()
+    }
+}
+
+fn is_binary(path: &Path) -> bool {
+    let Ok(file) = std::fs::File::open(path) else {
+        return true;
+    };
+
+    let mut buffer = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    file.take(BINARY_SNIFF_BYTES as u64).read_to_end(&mut buffer).is_err() || buffer.contains(&0)
+}
+
+fn list_directory(path: &Path) -> serde_json::Value {
+    let read_dir = match std::fs::read_dir(path) {
+        Ok(read_dir) => read_dir,
+        Err(err) => return error(format!("Failed to read directory: {}", err)),
+    };
+
+    let mut entries = read_dir.filter_map(Result::ok).collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.file_name());
+
+    let total = entries.len();
+    let listed = entries.into_iter()
+        .take(MAX_LIST_ENTRIES)
+        .map(|entry| {
+            let file_type = entry.file_type().ok();
+            let kind = match file_type {
+                Some(file_type) if file_type.is_symlink() => "symlink",
+                Some(file_type) if file_type.is_dir() => "directory",
+                Some(file_type) if file_type.is_file() => "file",
+                _ => "other",
+            };
+
+            let mut value = json!({
+                "name": entry.file_name().to_string_lossy(),
+                "type": kind,
+            });
+
+            if kind == "file" && let Ok(metadata) = entry.metadata() {
+                value["size"] = json!(metadata.len());
+            }
+
+            value
+        })
+        .collect::<Vec<_>>();
+
+    json!({
+        "success": true,
+        "path": path.display().to_string(),
+        "entries": listed,
+        "truncated": total > MAX_LIST_ENTRIES,
+        "total_entries": total
+    })
+}
+
+fn read_file(path: &Path, start_line: usize, max_lines: usize) -> serde_json::Value {
+    if !path.is_file() {
+        return error("Not a regular file");
+    }
+
+    if is_binary(path) {
+        return error("This looks like a binary file, only text files can be read");
+    }
+
+    let file = match std::fs::File::open(path) {
+        Ok(file) => file,
+        Err(err) => return error(format!("Failed to open file: {}", err)),
+    };
+
+    let mut reader = BufReader::new(file);
+    let mut buffer = Vec::new();
+    let mut content = String::new();
+    let mut line_number = 0;
+    let mut lines_read = 0;
+    let mut truncated = false;
+
+    loop {
+        buffer.clear();
+        match reader.read_until(b'\n', &mut buffer) {
+            Ok(0) => break,
+            Ok(_) => {},
+            Err(err) => return error(format!("Failed to read file: {}", err)),
+        }
+
+        line_number += 1;
+        if line_number < start_line {
+            continue;
+        }
+
+        let line = truncate_line(String::from_utf8_lossy(&buffer).trim_end_matches(['\n', '\r']));
+        if lines_read >= max_lines || content.len() + line.len() + 1 > MAX_READ_BYTES {
+            truncated = true;
+            break;
+        }
+
+        content.push_str(&line);
+        content.push('\n');
+        lines_read += 1;
+    }
+
+    let mut result = json!({
+        "success": true,
+        "path": path.display().to_string(),
+        "start_line": start_line,
+        "lines_read": lines_read,
+        "content": content,
+        "truncated": truncated
+    });
+
+    if truncated {
+        result["next_start_line"] = json!(start_line + lines_read);
+    }
+
+    result
+}
+
+enum Matcher {
+    Text(String),
+    Regex(regex::Regex),
+}
+
+impl Matcher {
+    fn is_match(&self, line: &str) -> bool {
+        match self {
+            Matcher::Text(query) => line.to_lowercase().contains(query),
+            Matcher::Regex(regex) => regex.is_match(line),
+        }
+    }
+}
+
+fn search_files(root: &Path, matcher: &Matcher) -> serde_json::Value {
+    let mut matches = vec![];
+    let mut files_scanned = 0;
+    let mut truncated = false;
+    let mut stack = vec![(root.to_path_buf(), 0)];
+    let denied = denied_paths();
+
+    'walk: while let Some((directory, depth)) = stack.pop() {
+        let Ok(read_dir) = std::fs::read_dir(&directory) else {
+            continue;
+        };
+
+        let mut entries = read_dir.filter_map(Result::ok).collect::<Vec<_>>();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.file_name()));
+
+        for entry in entries {
+            // `DirEntry::file_type` does not follow symlinks, so they are skipped entirely
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+
+            let path = entry.path();
+            if is_denied(&path, &denied) {
+                continue;
+            }
+
+            if file_type.is_dir() {
+                if depth < MAX_SEARCH_DEPTH && entry.file_name() != ".git" {
+                    stack.push((path, depth + 1));
+                }
+                continue;
+            }
+
+            if !file_type.is_file()
+                || entry.metadata().is_ok_and(|metadata| metadata.len() > MAX_SEARCH_FILE_SIZE)
+                || is_binary(&path)
+            {
+                continue;
+            }
+
+            files_scanned += 1;
+            if files_scanned > MAX_SEARCH_FILES {
+                truncated = true;
+                break 'walk;
+            }
+
+            let Ok(file) = std::fs::File::open(&path) else {
+                continue;
+            };
+
+            let mut reader = BufReader::new(file);
+            let mut buffer = Vec::new();
+            let mut line_number = 0;
+            while reader.read_until(b'\n', &mut buffer).is_ok_and(|read| read > 0) {
+                line_number += 1;
+                let line = String::from_utf8_lossy(&buffer);
+                let line = line.trim_end_matches(['\n', '\r']);
+                if matcher.is_match(line) {
+                    if matches.len() >= MAX_SEARCH_MATCHES {
+                        truncated = true;
+                        break 'walk;
+                    }
+
+                    matches.push(json!({
+                        "path": path.display().to_string(),
+                        "line": line_number,
+                        "text": truncate_line(line.trim())
+                    }));
+                }
+                buffer.clear();
+            }
+        }
+    }
+
+    json!({
+        "success": true,
+        "matches": matches,
+        "truncated": truncated
+    })
+}
+
+fn string_arg(args: &serde_json::Value, name: &str) -> Option<String> {
+    args.get(name).and_then(|v| v.as_str()).map(str::to_owned)
+}
+
+pub async fn call_filesystem_tool(name: &str, args: &str) -> serde_json::Value {
+    let args = match serde_json::from_str::<serde_json::Value>(args) {
+        Ok(args) => args,
+        Err(e) => return error(format!("Failed to parse arguments: {}", e)),
+    };
+
+    let Some(path) = string_arg(&args, "path") else {
+        return error("Missing 'path' parameter");
+    };
+
+    let verb = match name {
+        "list_directory" => "list",
+        "read_file" => "read",
+        _ => "search",
+    };
+
+    let path = match authorize(&path, verb).await {
+        Ok(path) => path,
+        Err(err) => return err,
+    };
+
+    let result = match name {
+        "list_directory" => tokio::task::spawn_blocking(move || list_directory(&path)).await,
+
+        "read_file" => {
+            let start_line = args.get("start_line").and_then(|v| v.as_u64()).map_or(1, |line| line.max(1) as usize);
+            let max_lines = args.get("max_lines").and_then(|v| v.as_u64())
+                .map_or(DEFAULT_READ_LINES, |lines| (lines as usize).clamp(1, MAX_READ_LINES));
+
+            tokio::task::spawn_blocking(move || read_file(&path, start_line, max_lines)).await
+        },
+
+        "search_files" => {
+            let Some(query) = string_arg(&args, "query").filter(|query| !query.is_empty()) else {
+                return error("Missing 'query' parameter");
+            };
+
+            let matcher = if args.get("regex").and_then(|v| v.as_bool()).unwrap_or(false) {
+                match regex::RegexBuilder::new(&query).case_insensitive(true).size_limit(1 << 20).build() {
+                    Ok(regex) => Matcher::Regex(regex),
+                    Err(e) => return error(format!("Invalid regex: {}", e)),
+                }
+            } else {
+                Matcher::Text(query.to_lowercase())
+            };
+
+            tokio::task::spawn_blocking(move || search_files(&path, &matcher)).await
+        },
+
+        _ => return error(format!("Unknown function: {}", name)),
+    };
+
+    result.unwrap_or_else(|err| error(format!("Tool task failed: {}", err)))
+}
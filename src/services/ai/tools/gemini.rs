@@ -63,6 +63,50 @@ pub struct PerformPowerAction {
     pub action: PowerAction,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Remember {
+    #[schemars(description = "The fact to remember")]
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Recall {
+    #[schemars(description = "Keywords to search for. An empty query returns the most recent memories.")]
+    pub query: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Forget {
+    #[schemars(description = "The ID of the memory to delete")]
+    pub id: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ListDirectory {
+    #[schemars(description = "Absolute path of the directory, may start with ~")]
+    pub path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ReadFile {
+    #[schemars(description = "Absolute path of the file, may start with ~")]
+    pub path: String,
+    #[schemars(skip_serializing_if = "Option::is_none", description = "The 1-based line to start reading from. Defaults to 1.")]
+    pub start_line: Option<u64>,
+    #[schemars(skip_serializing_if = "Option::is_none", description = "The maximum amount of lines to read. Defaults to 400, at most 2000.")]
+    pub max_lines: Option<u64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct SearchFiles {
+    #[schemars(description = "Absolute path of the directory to search in, may start with ~")]
+    pub path: String,
+    #[schemars(description = "The text to search for")]
+    pub query: String,
+    #[schemars(skip_serializing_if = "Option::is_none", description = "Whether the query is a regular expression. Defaults to false.")]
+    pub regex: Option<bool>,
+}
+
 pub fn add_gemini_tools(mut builder: ContentBuilder) -> ContentBuilder {
     let app_config = read_config();
 
@@ -114,5 +158,59 @@ pub fn add_gemini_tools(mut builder: ContentBuilder) -> ContentBuilder {
         builder = builder.with_tool(Tool::new(weather_tool_declaration));
     }
 
+    if app_config.ai.features.memory {
+        let remember_declaration = FunctionDeclaration::new(
+            "remember",
+            "Stores a fact about the user that should be remembered across conversations, such as a preference. Keep it short and self-contained.",
+            None
+        )
+            .with_parameters::<Remember>();
+
+        let recall_declaration = FunctionDeclaration::new(
+            "recall",
+            "Searches the memories stored in previous conversations for ones related to a query.",
+            None
+        )
+            .with_parameters::<Recall>();
+
+        let forget_declaration = FunctionDeclaration::new(
+            "forget",
+            "Deletes a stored memory that is wrong or no longer relevant.",
+            None
+        )
+            .with_parameters::<Forget>();
+
+        builder = builder.with_tool(Tool::new(remember_declaration));
+        builder = builder.with_tool(Tool::new(recall_declaration));
+        builder = builder.with_tool(Tool::new(forget_declaration));
+    }
+
+    if app_config.ai.features.filesystem_access {
+        let list_directory_declaration = FunctionDeclaration::new(
+            "list_directory",
+            "Lists the entries of a directory on the user's computer. Only some directories are accessible.",
+            None
+        )
+            .with_parameters::<ListDirectory>();
+
+        let read_file_declaration = FunctionDeclaration::new(
+            "read_file",
+            "Reads lines from a text file on the user's computer, such as a config file or a log. Long files are returned in chunks.",
+            None
+        )
+            .with_parameters::<ReadFile>();
+
+        let search_files_declaration = FunctionDeclaration::new(
+            "search_files",
+            "Recursively searches the text files in a directory for lines containing a query, case-insensitively.",
+            None
+        )
+            .with_parameters::<SearchFiles>();
+
+        builder = builder.with_tool(Tool::new(list_directory_declaration));
+        builder = builder.with_tool(Tool::new(read_file_declaration));
+        builder = builder.with_tool(Tool::new(search_files_declaration));
+    }
+
     builder
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 pub mod gemini;
+mod filesystem;
 
 use serde_json::json;
 
@@ -6,6 +7,7 @@ use crate::config::read_config;
 use crate::session::SessionAction;
 use crate::services::mpris::{self, mpris_player::LoopStatus};
 use crate::services::weather::{WEATHER, get_wmo_code, get_daily_at};
+use super::memory;
 use super::types::AiFunction;
 
 pub fn get_tools() -> Vec<AiFunction> {
@@ -117,10 +119,132 @@ pub fn get_tools() -> Vec<AiFunction> {
         });
     }
 
+    if app_config.ai.features.memory {
+        tools.push(AiFunction {
+            name: "remember".to_owned(),
+            description: "Stores a fact about the user that should be remembered across conversations, such as a preference. Keep it short and self-contained.".to_owned(),
+            strict: true,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "content": {
+                        "type": "string",
+                        "description": "The fact to remember"
+                    }
+                },
+                "required": ["content"],
+                "additionalProperties": false
+            }),
+        });
+
+        tools.push(AiFunction {
+            name: "recall".to_owned(),
+            description: "Searches the memories stored in previous conversations for ones related to a query.".to_owned(),
+            strict: true,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "query": {
+                        "type": "string",
+                        "description": "Keywords to search for. An empty query returns the most recent memories."
+                    }
+                },
+                "required": ["query"],
+                "additionalProperties": false
+            }),
+        });
+
+        tools.push(AiFunction {
+            name: "forget".to_owned(),
+            description: "Deletes a stored memory that is wrong or no longer relevant.".to_owned(),
+            strict: true,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "id": {
+                        "type": "integer",
+                        "description": "The ID of the memory to delete"
+                    }
+                },
+                "required": ["id"],
+                "additionalProperties": false
+            }),
+        });
+    }
+
+    if app_config.ai.features.filesystem_access {
+        tools.push(AiFunction {
+            name: "list_directory".to_owned(),
+            description: "Lists the entries of a directory on the user's computer. Only some directories are accessible.".to_owned(),
+            strict: true,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path of the directory, may start with ~"
+                    }
+                },
+                "required": ["path"],
+                "additionalProperties": false
+            }),
+        });
+
+        tools.push(AiFunction {
+            name: "read_file".to_owned(),
+            description: "Reads lines from a text file on the user's computer, such as a config file or a log. Long files are returned in chunks.".to_owned(),
+            strict: false,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path of the file, may start with ~"
+                    },
+                    "start_line": {
+                        "type": "integer",
+                        "description": "The 1-based line to start reading from. Defaults to 1."
+                    },
+                    "max_lines": {
+                        "type": "integer",
+                        "description": "The maximum amount of lines to read. Defaults to 400, at most 2000."
+                    }
+                },
+                "required": ["path"],
+                "additionalProperties": false
+            }),
+        });
+
+        tools.push(AiFunction {
+            name: "search_files".to_owned(),
+            description: "Recursively searches the text files in a directory for lines containing a query, case-insensitively.".to_owned(),
+            strict: false,
+            schema: json!({
+                "type": "object",
+                "properties": {
+                    "path": {
+                        "type": "string",
+                        "description": "Absolute path of the directory to search in, may start with ~"
+                    },
+                    "query": {
+                        "type": "string",
+                        "description": "The text to search for"
+                    },
+                    "regex": {
+                        "type": "boolean",
+                        "description": "Whether the query is a regular expression. Defaults to false."
+                    }
+                },
+                "required": ["path", "query"],
+                "additionalProperties": false
+            }),
+        });
+    }
+
     tools
 }
 
-pub fn call_tool(name: &str, args: &str) -> serde_json::Value {
+pub async fn call_tool(name: &str, args: &str) -> serde_json::Value {
     let success = json!({
         "success": true
     });
@@ -330,6 +454,85 @@ pub fn call_tool(name: &str, args: &str) -> serde_json::Value {
             })
         },
 
+        "remember" => {
+            let content = serde_json::from_str::<serde_json::Value>(args).ok()
+                .and_then(|args| args.get("content").and_then(|v| v.as_str()).map(str::to_owned));
+
+            match content {
+                Some(content) if !content.trim().is_empty() => match memory::remember(&content).await {
+                    Ok(id) => json!({
+                        "success": true,
+                        "id": id
+                    }),
+
+                    Err(e) => json!({
+                        "success": false,
+                        "error": format!("Failed to store memory: {}", e)
+                    }),
+                },
+
+                _ => json!({
+                    "success": false,
+                    "error": "Missing 'content' parameter"
+                }),
+            }
+        },
+
+        "recall" => {
+            let query = serde_json::from_str::<serde_json::Value>(args).ok()
+                .and_then(|args| args.get("query").and_then(|v| v.as_str()).map(str::to_owned))
+                .unwrap_or_default();
+
+            let memories = memory::recall(&query).into_iter()
+                .map(|memory| json!({
+                    "id": memory.id,
+                    "content": memory.content,
+                    "updated_at": memory.updated_at.format("%Y-%m-%d").to_string()
+                }))
+                .collect::<Vec<_>>();
+
+            json!({
+                "success": true,
+                "memories": memories
+            })
+        },
+
+        "forget" => {
+            let id = serde_json::from_str::<serde_json::Value>(args).ok()
+                .and_then(|args| args.get("id").and_then(|v| v.as_i64()));
+
+            match id {
+                Some(id) => match memory::forget(id).await {
+                    Ok(true) => success,
+                    Ok(false) => json!({
+                        "success": false,
+                        "error": format!("No memory with ID {}", id)
+                    }),
+
+                    Err(e) => json!({
+                        "success": false,
+                        "error": format!("Failed to delete memory: {}", e)
+                    }),
+                },
+
+                None => json!({
+                    "success": false,
+                    "error": "Missing 'id' parameter"
+                }),
+            }
+        },
+
+        "list_directory" | "read_file" | "search_files" => {
+            if read_config().ai.features.filesystem_access {
+                filesystem::call_filesystem_tool(name, args).await
+            } else {
+                json!({
+                    "success": false,
+                    "error": "Filesystem access is disabled"
+                })
+            }
+        },
+
         _ => json!({
             "success": false,
             "error": format!("Unknown function: {}", name)
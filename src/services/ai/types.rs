@@ -22,6 +22,19 @@ pub struct AiFunction {
 pub struct AiConversation {
     pub id: i64,
     pub title: String,
+    pub pinned: bool,
+    pub archived: bool,
+    pub folder: Option<String>,
+    pub tags: Vec<String>,
+    /// When the latest item was added, or when the conversation was created if it's empty.
+    pub last_activity: chrono::DateTime<chrono::Local>,
+}
+
+#[derive(Debug, Clone)]
+pub struct AiMemory {
+    pub id: i64,
+    pub content: String,
+    pub updated_at: chrono::DateTime<chrono::Local>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -40,6 +53,14 @@ pub enum AiConversationItemPayload {
         uuid: String,
     },
 
+    // Only users can provide files. The file itself and its extracted text are stored
+    // in the AI image cache directory.
+    File {
+        uuid: String,
+        name: String,
+        mime_type: String,
+    },
+
     Reasoning {
         id: String,
         summary: String,
@@ -1,6 +1,7 @@
 use crate::USERNAME;
 use crate::services::hyprland::HYPRLAND;
 use crate::services::mpris;
+use super::memory;
 
 fn us_to_readable_duration(us: u64) -> String {
     let total_seconds = us / 1_000_000;
@@ -73,5 +74,20 @@ pub fn transform_variables(input: &str) -> String {
                     .replace("{HYPRLAND_ACTIVE_CLIENT_MONITOR}", "0");
     }
 
+    if memory::is_enabled() {
+        let memories = memory::prompt_section();
+        if owned.contains("{MEMORIES}") {
+            owned = owned.replace("{MEMORIES}", &memories);
+        } else {
+            owned = format!(
+                "{}\n\nThings you remember about the user that relate to their latest message, \
+                use the remember, recall and forget tools to manage them and look up others:\n{}",
+                owned, memories
+            );
+        }
+    } else {
+        owned = owned.replace("{MEMORIES}", "");
+    }
+
     owned
 }
\ No newline at end of file
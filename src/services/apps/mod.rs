@@ -10,9 +10,23 @@ use crate::sql::wrappers::commands;
 
 pub struct WeightedDesktopEntry {
     pub entry: DesktopEntry,
+    /// The desktop action this result is for, or `None` for the entry itself.
+    pub action: Option<DesktopAction>,
     pub weight: f32,
 }
 
+/// A `[Desktop Action ...]` group of a desktop entry, such as "New Private Window".
+#[derive(Debug, Clone, PartialEq)]
+pub struct DesktopAction {
+    pub id: String,
+    pub name: String,
+    pub exec: String,
+    /// What launches of this action are tracked under, see `action_key`.
+    pub key: String,
+}
+
+static ACTION_KEY_PREFIX: &str = "desktop-action:";
+
 pub static DESKTOPS: LazyLock<RwLock<Vec<DesktopEntry>>> = LazyLock::new(|| RwLock::new(Vec::new()));
 
 pub async fn activate() {
@@ -24,53 +38,137 @@ pub async fn activate() {
     }
 }
 
-pub fn calculate_weight(entry: &DesktopEntry, query: &str) -> f32 {
-    // Fixed weights:
-    // 1. Exact match (10000)
-    // 2. Lazy match, contains all characters, length match (500)
-    // 3. Fuzzy match (30)
-    // 4. Lazy match (10)
-    //
-    // Dynamic weights:
-    // 5. Beginning or end match (query.length * 5)
-    // 6. String inclusion bonus (query.length * 4)
-    // 7. Launch count bonus (runs / 4, minimum 1.25x multiplier)
+/// The text fields of a desktop entry that are searched, with how much a match in each
+/// counts relative to a match in the name.
+fn searchable_fields(entry: &DesktopEntry) -> Vec<(String, f32)> {
     let locales = get_languages_from_env();
-    let name = entry.name(&locales).map(|c| c.to_string()).unwrap_or_default().to_lowercase();
-    let query = &query.trim().to_lowercase();
-
-    let lazy_match = matching::lazy_match(&name, query);
-    let contains_all = name.chars().all(|c| query.contains(c));
-
-    let mut weight = if name == *query {
-        10000.0
-    } else if lazy_match && contains_all && query.len() == name.len() {
-        500.0
-    } else if matching::fuzzy_match(&name, query) {
-        30.0
-    } else if lazy_match {
-        10.0
-    } else {
-        0.0
-    };
+    let mut fields = Vec::new();
+
+    if let Some(name) = entry.name(&locales) {
+        fields.push((name.to_string(), 1.0));
+    }
+
+    if let Some(generic_name) = entry.generic_name(&locales) {
+        fields.push((generic_name.to_string(), 0.7));
+    }
+
+    for keyword in entry.keywords(&locales).unwrap_or_default() {
+        fields.push((keyword.to_string(), 0.6));
+    }
+
+    if let Some(program) = entry.exec()
+        .and_then(|exec| exec.split_whitespace().next())
+        .and_then(|program| program.rsplit('/').next())
+    {
+        fields.push((program.to_owned(), 0.5));
+    }
 
-    if name.starts_with(query) || name.ends_with(query) {
-        weight += query.len() as f32 * 5.0;
+    if let Some(comment) = entry.comment(&locales) {
+        fields.push((comment.to_string(), 0.3));
     }
 
-    if name.contains(query) {
-        weight += query.len() as f32 * 4.0;
+    fields
+}
+
+/// How much to boost a match by how much it's been used lately, see `runs::frecency`. It
+/// grows slowly, so an app used a lot doesn't bury better matches.
+fn frecency_multiplier(command: &str) -> f32 {
+    1.0 + runs::frecency(command).ln_1p()
+}
+
+pub fn calculate_weight(entry: &DesktopEntry, query: &str) -> f32 {
+    // The best fuzzy match score over all searchable fields, scaled by the field's weight,
+    // with bonuses for an exact name match and for how much the entry has been used lately
+    let query = query.trim();
+    let fields = searchable_fields(entry);
+
+    let mut weight = fields.iter()
+        .filter_map(|(text, field_weight)| {
+            matching::fuzzy_score(text, query).map(|m| m.score as f32 * field_weight)
+        })
+        .fold(0.0, f32::max);
+
+    if weight <= 0.0 {
+        return 0.0;
     }
 
-    // How many times has this entry been run?
-    if weight > 0.0
-        && let Some(entry) = runs::get_entry(entry.exec().unwrap_or_default())
-        && entry.runs > 0
+    if let Some((name, _)) = fields.first()
+        && name.to_lowercase() == query.to_lowercase()
     {
-        weight *= f32::max(1.25, entry.runs as f32 / 4.0);
+        weight *= 2.0;
     }
 
-    weight
+    weight * frecency_multiplier(entry.exec().unwrap_or_default())
+}
+
+/// The actions declared by a desktop entry that have both a name and a command.
+pub fn desktop_actions(entry: &DesktopEntry) -> Vec<DesktopAction> {
+    let locales = get_languages_from_env();
+
+    entry.actions()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|id| !id.is_empty())
+        .filter_map(|id| Some(DesktopAction {
+            id: id.to_owned(),
+            name: entry.action_name(id, &locales)?.to_string(),
+            exec: entry.action_exec(id)?.to_owned(),
+            key: action_key(entry, id)
+        }))
+        .collect()
+}
+
+/// The key that launches of a desktop action are tracked under in the runs table. Actions
+/// often share their command with other entries (or have none that's unique), so they get
+/// their own key instead of being tracked by command like entries are.
+pub fn action_key(entry: &DesktopEntry, action: &str) -> String {
+    format!("{}{}:{}", ACTION_KEY_PREFIX, entry.id(), action)
+}
+
+/// Finds the desktop entry and action that a tracked key made with `action_key` refers to.
+pub fn get_from_action_key(key: &str) -> Option<(DesktopEntry, DesktopAction)> {
+    let (entry_id, action_id) = key.strip_prefix(ACTION_KEY_PREFIX)?.rsplit_once(':')?;
+    let entry = DESKTOPS.read().unwrap()
+        .iter()
+        .find(|entry| entry.id() == entry_id)?
+        .clone();
+
+    let action = desktop_actions(&entry).into_iter().find(|action| action.id == action_id)?;
+    Some((entry, action))
+}
+
+/// Weighs a desktop action against the query. The action is searched as "App: Action" so
+/// queries like "firefox private" work, but only counts if the query reaches into the
+/// action's name; otherwise every action would show up alongside its app.
+pub fn calculate_action_weight(entry: &DesktopEntry, action: &DesktopAction, query: &str) -> f32 {
+    let locales = get_languages_from_env();
+    let name = entry.name(&locales).map(|name| name.to_string()).unwrap_or_default();
+    let title = action_title(&name, &action.name);
+    let query = query.trim();
+
+    let weight = match matching::fuzzy_score(&title, query) {
+        Some(m) if m.positions.last().is_some_and(|last| *last > name.chars().count()) => {
+            m.score as f32 * 0.9
+        },
+
+        _ => return 0.0
+    };
+
+    weight * frecency_multiplier(&action.key)
+}
+
+/// How an action is shown on its own, e.g. "Firefox: New Private Window".
+pub fn action_title(entry_name: &str, action_name: &str) -> String {
+    format!("{}: {}", entry_name, action_name)
+}
+
+/// Finds the desktop entry of a window class, by `StartupWMClass` or else by desktop ID.
+pub fn get_from_class(class: &str) -> Option<DesktopEntry> {
+    let desktops = DESKTOPS.read().unwrap();
+    desktops.iter()
+        .find(|entry| entry.startup_wm_class().is_some_and(|wm_class| wm_class.eq_ignore_ascii_case(class)))
+        .or_else(|| desktops.iter().find(|entry| entry.id().eq_ignore_ascii_case(class)))
+        .cloned()
 }
 
 pub fn get_from_command(command: &str) -> Option<DesktopEntry> {
@@ -93,9 +191,21 @@ pub fn query_desktops(query: &str) -> Vec<WeightedDesktopEntry> {
         if weight > 0.0 {
             weighted.push(WeightedDesktopEntry {
                 entry: desktop.clone(),
+                action: None,
                 weight,
             });
         }
+
+        for action in desktop_actions(desktop) {
+            let weight = calculate_action_weight(desktop, &action, query);
+            if weight > 0.0 {
+                weighted.push(WeightedDesktopEntry {
+                    entry: desktop.clone(),
+                    action: Some(action),
+                    weight,
+                });
+            }
+        }
     }
 
     weighted.sort_by(|a, b| b.weight.partial_cmp(&a.weight).unwrap_or(std::cmp::Ordering::Equal));
@@ -120,13 +230,28 @@ pub fn refresh_desktops() {
 /// This is a function that will invoke process::launch and tell SQLite to
 /// increment the runs count for a desktop entry. Use this for launch tracking.
 pub fn launch_and_track(command: &str) {
+    launch_and_track_as(command, command);
+}
+
+/// Like `launch_and_track`, but counts the launch under `key` instead of the command.
+/// Desktop actions use this with their `action_key`.
+pub fn launch_and_track_as(command: &str, key: &str) {
     process::launch(command);
+    track_launch(key);
+}
+
+/// Like `launch_and_track`, but opens the app on the given Hyprland workspace.
+pub fn launch_on_workspace_and_track(command: &str, workspace: i32) {
+    process::launch_on_workspace(command, workspace);
+    track_launch(command);
+}
 
+fn track_launch(key: &str) {
     glib::spawn_future_local({
-        let command = command.to_owned();
+        let key = key.to_owned();
         async move {
-            let _ = commands::increment_runs(&command).await;
-            runs::increment_entry_runs(&command);
+            let _ = commands::increment_runs(&key, runs::LAUNCH_LIMIT).await;
+            runs::increment_entry_runs(&key);
         }
     });
 }
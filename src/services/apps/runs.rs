@@ -1,56 +1,165 @@
 use std::sync::{RwLock, LazyLock};
 use std::collections::HashMap;
+use chrono::{Datelike as _, Timelike as _};
 
 use crate::sql::wrappers::commands::{self, DesktopRunsEntry};
 
-static RUNS: LazyLock<RwLock<HashMap<String, DesktopRunsEntry>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+/// How many launches are kept per command; older ones barely count by the time they're dropped.
+pub const LAUNCH_LIMIT: usize = 50;
+
+// A launch counts half as much after this many days
+const HALF_LIFE_DAYS: f32 = 14.0;
+
+// Launches around the same time of day, or on the same day of the week, count for more
+const HOUR_AFFINITY_WINDOW: f32 = 3.0;
+const HOUR_AFFINITY: f32 = 0.5;
+const WEEKDAY_AFFINITY: f32 = 0.5;
+const WEEKPART_AFFINITY: f32 = 0.2;
+
+#[derive(Debug, Clone)]
+struct CommandRuns {
+    entry: DesktopRunsEntry,
+    /// The most recent launches, oldest first.
+    launches: Vec<chrono::DateTime<chrono::Local>>,
+}
+
+static RUNS: LazyLock<RwLock<HashMap<String, CommandRuns>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
 
 pub async fn init() {
-    match commands::get_all_runs().await {
-        Ok(all_runs) => {
-            let mut runs_writer = RUNS.write().unwrap();
-            for run in all_runs {
-                runs_writer.insert(run.command.clone(), run);
-            }
-        },
-        
+    let all_runs = match commands::get_all_runs().await {
+        Ok(all_runs) => all_runs,
         Err(err) => {
             error!(%err, "Could not get all desktop runs");
+            return;
+        },
+    };
+
+    let mut launches = HashMap::<String, Vec<_>>::new();
+    match commands::get_all_launches().await {
+        Ok(all_launches) => for (command, launched_at) in all_launches {
+            launches.entry(command).or_default().push(launched_at);
         },
+
+        Err(err) => error!(%err, "Could not get desktop launches"),
     }
+
+    {
+        let mut runs_writer = RUNS.write().unwrap();
+        for run in all_runs {
+            let launches = launches.remove(&run.command).unwrap_or_default();
+            runs_writer.insert(run.command.clone(), CommandRuns { entry: run, launches });
+        }
+    }
+
+    prune().await;
 }
 
-pub fn get_entry(command: &str) -> Option<DesktopRunsEntry> {
-    RUNS.read().unwrap().get(command).cloned()
+/// Forgets commands whose desktop entry (or action) no longer exists. This only happens at
+/// startup, as package upgrades briefly remove desktop files while the shell is running.
+async fn prune() {
+    if super::DESKTOPS.read().unwrap().is_empty() {
+        return;
+    }
+
+    let stale = RUNS.read().unwrap()
+        .keys()
+        .filter(|command| super::get_from_action_key(command).is_none() && super::get_from_command(command).is_none())
+        .cloned()
+        .collect::<Vec<_>>();
+
+    if stale.is_empty() {
+        return;
+    }
+
+    {
+        let mut runs_writer = RUNS.write().unwrap();
+        for command in &stale {
+            runs_writer.remove(command);
+        }
+    }
+
+    info!(count = stale.len(), "Pruning runs of removed desktop entries");
+    if let Err(err) = commands::delete_runs(stale).await {
+        error!(%err, "Could not prune desktop runs");
+    }
+}
+
+/// How alike two times are by time of day and day of the week, from 0 up.
+fn time_affinity(a: chrono::DateTime<chrono::Local>, b: chrono::DateTime<chrono::Local>) -> f32 {
+    let hour = |time: chrono::DateTime<chrono::Local>| time.hour() as f32 + time.minute() as f32 / 60.0;
+    let hours_apart = (hour(a) - hour(b)).abs();
+    let hours_apart = hours_apart.min(24.0 - hours_apart);
+    let hour_affinity = (1.0 - hours_apart / HOUR_AFFINITY_WINDOW).max(0.0) * HOUR_AFFINITY;
+
+    let is_weekend = |time: chrono::DateTime<chrono::Local>| time.weekday().number_from_monday() > 5;
+    let weekday_affinity = if a.weekday() == b.weekday() {
+        WEEKDAY_AFFINITY
+    } else if is_weekend(a) == is_weekend(b) {
+        WEEKPART_AFFINITY
+    } else {
+        0.0
+    };
+
+    hour_affinity + weekday_affinity
+}
+
+fn launch_score(launched_at: chrono::DateTime<chrono::Local>, now: chrono::DateTime<chrono::Local>) -> f32 {
+    let age_days = (now - launched_at).num_seconds().max(0) as f32 / (24.0 * 60.0 * 60.0);
+    0.5_f32.powf(age_days / HALF_LIFE_DAYS) * (1.0 + time_affinity(launched_at, now))
+}
+
+fn score(runs: &CommandRuns, now: chrono::DateTime<chrono::Local>) -> f32 {
+    runs.launches.iter().map(|launched_at| launch_score(*launched_at, now)).sum()
+}
+
+/// How much a command is used around now: every launch counts for less as it gets older,
+/// and for more if it happened around this time of day or day of the week.
+pub fn frecency(command: &str) -> f32 {
+    RUNS.read().unwrap()
+        .get(command)
+        .map_or(0.0, |runs| score(runs, chrono::Local::now()))
 }
 
 pub fn increment_entry_runs(command: &str) {
     let now = chrono::Local::now();
     let mut writer = RUNS.write().unwrap();
-    if let Some(entry) = writer.get_mut(command) {
-        entry.last_run = now;
-        entry.runs += 1;
-    } else {
-        writer.insert(command.to_owned(), DesktopRunsEntry {
+    let runs = writer.entry(command.to_owned()).or_insert_with(|| CommandRuns {
+        entry: DesktopRunsEntry {
             command: command.to_owned(),
-            runs: 1,
+            runs: 0,
             last_run: now,
-        });
+        },
+        launches: Vec::new(),
+    });
+
+    runs.entry.last_run = now;
+    runs.entry.runs += 1;
+    runs.launches.push(now);
+    if runs.launches.len() > LAUNCH_LIMIT {
+        runs.launches.remove(0);
     }
 }
 
+/// Commands by frecency, most used around now first.
 pub fn get_top_commands() -> Vec<DesktopRunsEntry> {
+    let now = chrono::Local::now();
     let runs_guard = RUNS.read().unwrap();
-    let mut runs_vec: Vec<DesktopRunsEntry> = runs_guard.values().cloned().collect();
+    let mut runs_vec = runs_guard.values()
+        .map(|runs| (score(runs, now), &runs.entry))
+        .filter(|(score, _)| *score > 0.0)
+        .collect::<Vec<_>>();
 
-    runs_vec.sort_by(|a, b| b.runs.cmp(&a.runs));
-    runs_vec
+    runs_vec.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    runs_vec.into_iter().map(|(_, entry)| entry.clone()).collect()
 }
 
+/// Commands by when they were last launched, most recent first.
 pub fn get_most_recent_commands() -> Vec<DesktopRunsEntry> {
     let runs_guard = RUNS.read().unwrap();
-    let mut runs_vec: Vec<DesktopRunsEntry> = runs_guard.values().cloned().collect();
+    let mut runs_vec = runs_guard.values()
+        .map(|runs| runs.entry.clone())
+        .collect::<Vec<_>>();
 
     runs_vec.sort_by_key(|b| std::cmp::Reverse(b.last_run));
     runs_vec
-}
\ No newline at end of file
+}
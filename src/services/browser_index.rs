@@ -0,0 +1,292 @@
+// Bookmarks and history from Firefox and Chromium-based browsers, for the overview's browser
+// search. Browsers keep their databases locked while they run, so the files are copied to the
+// cache directory and read from there. The index is rebuilt every `refresh_interval` seconds.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+use rusqlite::{Connection, OpenFlags};
+
+use crate::config::read_config;
+use crate::config::structs::OverviewBrowserConfig;
+use crate::utils::{filesystem::{expand_tilde, get_cache_directory, get_home_directory}, matching};
+
+/// Directories holding Firefox-style profiles, relative to the home directory.
+const FIREFOX_ROOTS: &[&str] = &[
+    ".mozilla/firefox",
+    ".librewolf",
+    ".floorp",
+    ".zen",
+    ".var/app/org.mozilla.firefox/.mozilla/firefox",
+];
+
+/// Directories holding Chromium-style profiles, relative to the home directory.
+const CHROMIUM_ROOTS: &[&str] = &[
+    ".config/chromium",
+    ".config/google-chrome",
+    ".config/BraveSoftware/Brave-Browser",
+    ".config/vivaldi",
+    ".config/microsoft-edge",
+];
+
+// Bookmarks rank as if they were visited this often
+const BOOKMARK_FRECENCY: f32 = 200.0;
+
+// Chromium stores times as microseconds since 1601-01-01
+const CHROMIUM_EPOCH_OFFSET_SECS: i64 = 11_644_473_600;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BrowserKind {
+    Firefox,
+    Chromium,
+}
+
+struct BrowserProfile {
+    kind: BrowserKind,
+    path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct BrowserEntry {
+    pub title: String,
+    pub url: String,
+    pub frecency: f32,
+    pub bookmarked: bool,
+}
+
+static ENTRIES: RwLock<Vec<BrowserEntry>> = RwLock::new(Vec::new());
+
+fn find_profiles(config: &OverviewBrowserConfig) -> Vec<BrowserProfile> {
+    let home = PathBuf::from(get_home_directory());
+    let mut profiles = Vec::new();
+
+    let roots = FIREFOX_ROOTS.iter().map(|root| (BrowserKind::Firefox, root))
+        .chain(CHROMIUM_ROOTS.iter().map(|root| (BrowserKind::Chromium, root)));
+
+    for (kind, root) in roots {
+        let Ok(entries) = std::fs::read_dir(home.join(root)) else {
+            continue;
+        };
+
+        for path in entries.filter_map(Result::ok).map(|entry| entry.path()) {
+            let is_profile = match kind {
+                BrowserKind::Firefox => path.join("places.sqlite").is_file(),
+                BrowserKind::Chromium => path.join("Bookmarks").is_file() || path.join("History").is_file(),
+            };
+
+            if is_profile {
+                profiles.push(BrowserProfile { kind, path });
+            }
+        }
+    }
+
+    if !config.profiles.is_empty() {
+        let wanted = config.profiles.iter()
+            .map(|profile| PathBuf::from(expand_tilde(profile.trim_end_matches('/'))))
+            .collect::<Vec<_>>();
+
+        profiles.retain(|profile| wanted.contains(&profile.path));
+    }
+
+    profiles
+}
+
+/// Copies a database, along with its write-ahead log, and opens the copy.
+fn open_copy(database: &Path, name: &str) -> anyhow::Result<Connection> {
+    let directory = PathBuf::from(get_cache_directory()).join("browser");
+    std::fs::create_dir_all(&directory)?;
+
+    let copy = directory.join(name);
+    std::fs::copy(database, &copy)?;
+
+    let wal = PathBuf::from(format!("{}-wal", database.display()));
+    let wal_copy = PathBuf::from(format!("{}-wal", copy.display()));
+    if wal.is_file() {
+        std::fs::copy(&wal, &wal_copy)?;
+    } else {
+        let _ = std::fs::remove_file(&wal_copy);
+    }
+
+    // The WAL can only be read back with write access, but nothing is written
+    Ok(Connection::open_with_flags(&copy, OpenFlags::SQLITE_OPEN_READ_WRITE | OpenFlags::SQLITE_OPEN_NO_MUTEX)?)
+}
+
+/// A copy's file name that's unique to the profile.
+fn copy_name(profile: &Path, file: &str) -> String {
+    let profile = profile.to_string_lossy().replace(['/', ' '], "_");
+    format!("{}_{}", profile.trim_start_matches('_'), file)
+}
+
+fn read_firefox(profile: &Path, config: &OverviewBrowserConfig) -> anyhow::Result<Vec<BrowserEntry>> {
+    let connection = open_copy(&profile.join("places.sqlite"), &copy_name(profile, "places.sqlite"))?;
+
+    // Firefox keeps a frecency score for every place
+    let mut statement = connection.prepare("
+        SELECT p.url, COALESCE(MAX(b.title), p.title, ''), MAX(p.frecency, 0), COUNT(b.id) > 0 AS bookmarked
+        FROM moz_places p
+        LEFT JOIN moz_bookmarks b ON b.fk = p.id AND b.type = 1
+        WHERE p.hidden = 0
+        GROUP BY p.id
+        HAVING bookmarked OR ?1
+        ORDER BY bookmarked DESC, p.frecency DESC
+        LIMIT ?2
+    ")?;
+
+    let entries = statement.query_map(
+        rusqlite::params![config.include_history, config.max_history_entries as i64],
+        |row| Ok(BrowserEntry {
+            url: row.get(0)?,
+            title: row.get(1)?,
+            frecency: row.get::<_, i64>(2)? as f32,
+            bookmarked: row.get(3)?,
+        })
+    )?
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(entries)
+}
+
+/// Weighs visits by how recent the last one was, roughly like Firefox's frecency.
+fn chromium_frecency(visits: i64, typed: i64, last_visit: i64) -> f32 {
+    let last_visit_secs = last_visit / 1_000_000 - CHROMIUM_EPOCH_OFFSET_SECS;
+    let age_days = (chrono::Utc::now().timestamp() - last_visit_secs) / (24 * 60 * 60);
+    let recency = match age_days {
+        ..4 => 100.0,
+        4..14 => 70.0,
+        14..31 => 50.0,
+        31..90 => 30.0,
+        _ => 10.0,
+    };
+
+    (visits + typed * 2) as f32 * recency
+}
+
+fn read_chromium_bookmarks(node: &serde_json::Value, entries: &mut Vec<BrowserEntry>) {
+    if let Some(url) = node.get("url").and_then(|url| url.as_str()) {
+        entries.push(BrowserEntry {
+            title: node.get("name").and_then(|name| name.as_str()).unwrap_or_default().to_owned(),
+            url: url.to_owned(),
+            frecency: 0.0,
+            bookmarked: true,
+        });
+    }
+
+    for child in node.get("children").and_then(|children| children.as_array()).into_iter().flatten() {
+        read_chromium_bookmarks(child, entries);
+    }
+}
+
+fn read_chromium(profile: &Path, config: &OverviewBrowserConfig) -> anyhow::Result<Vec<BrowserEntry>> {
+    let mut entries = Vec::new();
+
+    // Bookmarks are plain JSON, written atomically, so they don't need a copy
+    if let Ok(contents) = std::fs::read_to_string(profile.join("Bookmarks")) {
+        let bookmarks = serde_json::from_str::<serde_json::Value>(&contents)?;
+        for root in bookmarks.get("roots").and_then(|roots| roots.as_object()).into_iter().flat_map(|roots| roots.values()) {
+            read_chromium_bookmarks(root, &mut entries);
+        }
+    }
+
+    if config.include_history && profile.join("History").is_file() {
+        let connection = open_copy(&profile.join("History"), &copy_name(profile, "History"))?;
+        let mut statement = connection.prepare("
+            SELECT url, title, visit_count, typed_count, last_visit_time
+            FROM urls
+            WHERE hidden = 0
+            ORDER BY last_visit_time DESC
+            LIMIT ?1
+        ")?;
+
+        let history = statement.query_map([config.max_history_entries as i64], |row| Ok(BrowserEntry {
+            url: row.get(0)?,
+            title: row.get(1)?,
+            frecency: chromium_frecency(row.get(2)?, row.get(3)?, row.get(4)?),
+            bookmarked: false,
+        }))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        entries.extend(history);
+    }
+
+    Ok(entries)
+}
+
+fn build(config: &OverviewBrowserConfig) {
+    // The same URL can come from several profiles, or be both bookmarked and visited
+    let mut entries = HashMap::<String, BrowserEntry>::new();
+
+    for profile in find_profiles(config) {
+        let result = match profile.kind {
+            BrowserKind::Firefox => read_firefox(&profile.path, config),
+            BrowserKind::Chromium => read_chromium(&profile.path, config),
+        };
+
+        let profile_entries = match result {
+            Ok(profile_entries) => profile_entries,
+            Err(err) => {
+                warn!(%err, profile = %profile.path.display(), "Failed to read browser profile");
+                continue;
+            },
+        };
+
+        for entry in profile_entries {
+            match entries.get_mut(&entry.url) {
+                Some(existing) => {
+                    existing.frecency = existing.frecency.max(entry.frecency);
+                    existing.bookmarked |= entry.bookmarked;
+                    if existing.title.is_empty() || entry.bookmarked {
+                        existing.title = entry.title;
+                    }
+                },
+
+                None => {
+                    entries.insert(entry.url.clone(), entry);
+                },
+            }
+        }
+    }
+
+    let entries = entries.into_values().collect::<Vec<_>>();
+    info!(entries = entries.len(), "Built browser index");
+    *ENTRIES.write().unwrap() = entries;
+}
+
+pub fn activate() {
+    std::thread::spawn(|| loop {
+        let config = read_config().overview.browser.clone();
+        if config.enabled {
+            build(&config);
+        } else {
+            ENTRIES.write().unwrap().clear();
+        }
+
+        std::thread::sleep(Duration::from_secs(config.refresh_interval.max(10)));
+    });
+}
+
+/// Searches titles and URLs, ranking matches by how well they match and their frecency.
+pub fn search(query: &str, limit: usize) -> Vec<BrowserEntry> {
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let entries = ENTRIES.read().unwrap();
+    let mut matches = entries.iter()
+        .filter_map(|entry| {
+            let title_score = matching::fuzzy_score(&entry.title, query).map(|m| m.score as f32);
+            let url_score = matching::fuzzy_score(&entry.url, query).map(|m| m.score as f32 * 0.8);
+            let score = title_score.into_iter().chain(url_score).reduce(f32::max)?;
+
+            let frecency = entry.frecency + if entry.bookmarked { BOOKMARK_FRECENCY } else { 0.0 };
+            Some((score * (1.0 + frecency.ln_1p() / 10.0), entry))
+        })
+        .collect::<Vec<_>>();
+
+    matches.sort_by(|(a, _), (b, _)| b.total_cmp(a));
+    matches.into_iter()
+        .take(limit)
+        .map(|(_, entry)| entry.clone())
+        .collect()
+}
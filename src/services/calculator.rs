@@ -1,8 +1,178 @@
+use std::sync::{Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::Duration;
+
 use crate::ffi::libqalculate::ffi;
+use crate::sql::wrappers::calculator::{self, CalculatorHistoryEntry};
+
+const CALCULATION_TIMEOUT_MSECS: u32 = 1000;
+const HISTORY_LIMIT: usize = 100;
+const EXCHANGE_RATES_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+const EXCHANGE_RATES_CHECK_INTERVAL: Duration = Duration::from_secs(60 * 60);
+const EXCHANGE_RATES_FETCH_TIMEOUT_SECS: i32 = 15;
+
+// libqalculate's calculator is global, so only evaluate one expression at a time
+static CALCULATOR_LOCK: Mutex<()> = Mutex::new(());
+static HISTORY: RwLock<Vec<CalculatorHistoryEntry>> = RwLock::new(Vec::new());
+
+fn lock() -> MutexGuard<'static, ()> {
+    CALCULATOR_LOCK.lock().unwrap_or_else(PoisonError::into_inner)
+}
 
 pub fn activate() {
     ffi::init_calc();
     ffi::loadExchangeRates();
     ffi::loadGlobalDefinitions();
     ffi::loadLocalDefinitions();
-}
\ No newline at end of file
+
+    tokio::spawn(async {
+        restore_state().await;
+        refresh_exchange_rates().await;
+    });
+}
+
+/// Restores the user's variables and `ans` from the last session.
+async fn restore_state() {
+    match calculator::get_variables().await {
+        Ok(variables) => {
+            let _guard = lock();
+            for (name, expression) in variables {
+                ffi::setVariable(name, expression);
+            }
+        },
+
+        Err(err) => error!(%err, "Could not get calculator variables"),
+    }
+
+    match calculator::get_history(HISTORY_LIMIT).await {
+        Ok(history) => {
+            if let Some(last) = history.first() {
+                let _guard = lock();
+                ffi::setVariable("ans".to_owned(), last.result.clone());
+            }
+
+            *HISTORY.write().unwrap() = history;
+        },
+
+        Err(err) => error!(%err, "Could not get calculator history"),
+    }
+}
+
+/// Fetches new exchange rates whenever the loaded ones get older than a day.
+async fn refresh_exchange_rates() {
+    let mut interval = tokio::time::interval(EXCHANGE_RATES_CHECK_INTERVAL);
+
+    loop {
+        interval.tick().await;
+
+        let fetched_at = {
+            let _guard = lock();
+            ffi::exchangeRatesTime()
+        };
+
+        let age = chrono::Utc::now().timestamp().saturating_sub(fetched_at);
+        if age < EXCHANGE_RATES_MAX_AGE.as_secs() as i64 {
+            continue;
+        }
+
+        let result = tokio::task::spawn_blocking(|| {
+            // Fetching only downloads the rates to libqalculate's cache, so it doesn't need
+            // to hold up calculations; loading them does
+            if !ffi::fetchExchangeRates(EXCHANGE_RATES_FETCH_TIMEOUT_SECS) {
+                return false;
+            }
+
+            let _guard = lock();
+            ffi::loadExchangeRates()
+        }).await;
+
+        match result {
+            Ok(true) => info!("Updated exchange rates"),
+            Ok(false) => warn!("Failed to update exchange rates"),
+            Err(err) => error!(%err, "Exchange rate update panicked"),
+        }
+    }
+}
+
+pub fn calculate(expression: &str) -> ffi::CalculationResult {
+    let _guard = lock();
+    ffi::calculate(expression.to_owned(), CALCULATION_TIMEOUT_MSECS)
+}
+
+/// Splits a `name := expression` assignment.
+pub fn parse_assignment(query: &str) -> Option<(&str, &str)> {
+    let (name, expression) = query.split_once(":=")?;
+    let name = name.trim();
+    let expression = expression.trim();
+
+    let valid_name = name.chars().next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+    (valid_name && !expression.is_empty()).then_some((name, expression))
+}
+
+pub fn history() -> Vec<CalculatorHistoryEntry> {
+    HISTORY.read().unwrap().clone()
+}
+
+/// Joins what `remember` and `store_variable` need into the single key item actions carry.
+pub fn action_key(first: &str, second: &str) -> String {
+    format!("{}\n{}", first, second)
+}
+
+/// Adds a calculation to the history and makes its result `ans`. Takes an `action_key`
+/// of the expression and its result.
+pub fn remember(key: &str) {
+    let Some((expression, result)) = key.split_once('\n') else {
+        return;
+    };
+
+    let entry = CalculatorHistoryEntry {
+        expression: expression.to_owned(),
+        result: result.to_owned(),
+    };
+
+    {
+        let mut history = HISTORY.write().unwrap();
+        history.insert(0, entry.clone());
+        history.truncate(HISTORY_LIMIT);
+    }
+
+    {
+        let _guard = lock();
+        ffi::setVariable("ans".to_owned(), entry.result.clone());
+    }
+
+    glib::spawn_future_local(async move {
+        if let Err(err) = calculator::add_history_entry(entry, HISTORY_LIMIT).await {
+            error!(%err, "Could not save calculator history");
+        }
+    });
+}
+
+/// Defines a variable for later calculations, and saves it for later sessions. Takes an
+/// `action_key` of the name and its value.
+pub fn store_variable(key: &str) {
+    let Some((name, value)) = key.split_once('\n') else {
+        return;
+    };
+
+    let stored = {
+        let _guard = lock();
+        ffi::setVariable(name.to_owned(), value.to_owned())
+    };
+
+    if !stored {
+        warn!(name, "Could not define calculator variable");
+        return;
+    }
+
+    glib::spawn_future_local({
+        let name = name.to_owned();
+        let value = value.to_owned();
+        async move {
+            if let Err(err) = calculator::set_variable(&name, &value).await {
+                error!(%err, "Could not save calculator variable");
+            }
+        }
+    });
+}
@@ -0,0 +1,179 @@
+// Emoji and Unicode character lookup for the overview's character picker. Emoji come from the
+// `emojis` crate, other characters from `unicode_names.txt`, which is embedded at build time.
+// The preferred skin tone and recently picked characters are kept in SQLite.
+
+use std::sync::{LazyLock, RwLock};
+use emojis::SkinTone;
+
+use crate::sql::wrappers::{characters, state};
+use crate::utils::matching;
+
+const UNICODE_NAMES: &str = include_str!("unicode_names.txt");
+const RECENT_LIMIT: usize = 24;
+
+/// The skin tones that can be picked, by the key they're stored under.
+pub const SKIN_TONES: [(&str, &str, SkinTone); 6] = [
+    ("default", "Default", SkinTone::Default),
+    ("light", "Light", SkinTone::Light),
+    ("medium_light", "Medium-light", SkinTone::MediumLight),
+    ("medium", "Medium", SkinTone::Medium),
+    ("medium_dark", "Medium-dark", SkinTone::MediumDark),
+    ("dark", "Dark", SkinTone::Dark),
+];
+
+static SKIN_TONE: RwLock<SkinTone> = RwLock::new(SkinTone::Default);
+static RECENTS: RwLock<Vec<String>> = RwLock::new(Vec::new());
+
+/// (character, lowercase name) for every character in `unicode_names.txt` that isn't an emoji.
+static UNICODE_CHARACTERS: LazyLock<Vec<(String, String)>> = LazyLock::new(|| {
+    UNICODE_NAMES.lines()
+        .filter(|line| !line.starts_with('#'))
+        .filter_map(|line| {
+            let (code_point, name) = line.split_once(';')?;
+            let character = char::from_u32(u32::from_str_radix(code_point, 16).ok()?)?.to_string();
+            (!is_emoji(&character)).then(|| (character, name.to_lowercase()))
+        })
+        .collect()
+});
+
+#[derive(Clone, Debug)]
+pub struct Character {
+    pub character: String,
+    pub name: String,
+}
+
+fn is_emoji(character: &str) -> bool {
+    emojis::get(character).is_some() || emojis::get(&format!("{}\u{fe0f}", character)).is_some()
+}
+
+fn skin_tone_from_key(key: &str) -> SkinTone {
+    SKIN_TONES.iter()
+        .find(|(tone_key, ..)| *tone_key == key)
+        .map_or(SkinTone::Default, |(.., tone)| *tone)
+}
+
+pub async fn activate() {
+    match state::get_emoji_skin_tone().await {
+        Ok(key) => *SKIN_TONE.write().unwrap() = skin_tone_from_key(&key),
+        Err(err) => error!(%err, "Could not get emoji skin tone"),
+    }
+
+    match characters::get_recent_characters(RECENT_LIMIT).await {
+        Ok(recents) => *RECENTS.write().unwrap() = recents,
+        Err(err) => error!(%err, "Could not get recent characters"),
+    }
+}
+
+fn name(character: &str) -> Option<String> {
+    if let Some(emoji) = emojis::get(character) {
+        return Some(emoji.name().to_owned());
+    }
+
+    UNICODE_CHARACTERS.iter()
+        .find(|(c, _)| c == character)
+        .map(|(_, name)| name.clone())
+}
+
+/// Scores every word of the query on its own, so "arrow right" finds "rightwards arrow".
+fn score(text: &str, words: &[&str]) -> Option<f32> {
+    words.iter()
+        .map(|word| matching::fuzzy_score(text, word).map(|m| m.score as f32))
+        .sum()
+}
+
+/// Searches emoji by name and shortcode and other characters by name. An empty query
+/// returns the recently picked characters instead.
+pub fn search(query: &str, limit: usize) -> Vec<Character> {
+    let words = query.split_whitespace().collect::<Vec<_>>();
+    if words.is_empty() {
+        return recents().into_iter()
+            .filter_map(|character| Some(Character { name: name(&character)?, character }))
+            .take(limit)
+            .collect();
+    }
+
+    let skin_tone = *SKIN_TONE.read().unwrap();
+    let recents = recents();
+
+    let emoji_matches = emojis::iter().filter_map(|emoji| {
+        let name_score = score(emoji.name(), &words);
+        let shortcode_score = emoji.shortcodes()
+            .filter_map(|shortcode| score(&shortcode.replace('_', " "), &words))
+            .map(|score| score * 0.9)
+            .reduce(f32::max);
+
+        let score = name_score.into_iter().chain(shortcode_score).reduce(f32::max)?;
+        let emoji = emoji.with_skin_tone(skin_tone).unwrap_or(emoji);
+        Some((score, Character { character: emoji.as_str().to_owned(), name: emoji.name().to_owned() }))
+    });
+
+    let unicode_matches = UNICODE_CHARACTERS.iter().filter_map(|(character, name)| {
+        Some((score(name, &words)?, Character { character: character.clone(), name: name.clone() }))
+    });
+
+    let mut matches = emoji_matches.chain(unicode_matches)
+        .map(|(score, character)| {
+            // Prefer characters that were picked before
+            let boost = if recents.contains(&character.character) { 1.1 } else { 1.0 };
+            (score * boost, character)
+        })
+        .collect::<Vec<_>>();
+
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        b_score.total_cmp(a_score).then(a.name.len().cmp(&b.name.len()))
+    });
+
+    matches.into_iter()
+        .take(limit)
+        .map(|(_, character)| character)
+        .collect()
+}
+
+/// The other skin tones an emoji comes in, as (key, label, emoji).
+pub fn skin_tone_variants(character: &str) -> Vec<(&'static str, &'static str, String)> {
+    let Some(emoji) = emojis::get(character) else {
+        return Vec::new();
+    };
+
+    SKIN_TONES.iter()
+        .filter(|(.., tone)| emoji.skin_tone() != Some(*tone))
+        .filter_map(|(key, label, tone)| Some((*key, *label, emoji.with_skin_tone(*tone)?.as_str().to_owned())))
+        .collect()
+}
+
+pub fn recents() -> Vec<String> {
+    RECENTS.read().unwrap().clone()
+}
+
+/// Moves a character to the front of the recents.
+pub fn record_use(character: &str) {
+    {
+        let mut recents = RECENTS.write().unwrap();
+        recents.retain(|c| c != character);
+        recents.insert(0, character.to_owned());
+        recents.truncate(RECENT_LIMIT);
+    }
+
+    glib::spawn_future_local({
+        let character = character.to_owned();
+        async move {
+            if let Err(err) = characters::record_character_use(&character).await {
+                error!(%err, "Could not record character use");
+            }
+        }
+    });
+}
+
+/// Sets the skin tone emoji are shown in, by its key in `SKIN_TONES`.
+pub fn set_skin_tone(key: &str) {
+    *SKIN_TONE.write().unwrap() = skin_tone_from_key(key);
+
+    glib::spawn_future_local({
+        let key = key.to_owned();
+        async move {
+            if let Err(err) = state::set_emoji_skin_tone(&key).await {
+                error!(%err, "Could not save emoji skin tone");
+            }
+        }
+    });
+}
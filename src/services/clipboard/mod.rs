@@ -113,8 +113,22 @@ pub fn copy_text(text: &str) {
 }
 
 pub async fn fetch_text_clipboard() -> Option<String> {
+    fetch_text(false).await
+}
+
+/// Fetches the Wayland primary selection, which is the currently highlighted text.
+pub async fn fetch_text_primary_selection() -> Option<String> {
+    fetch_text(true).await
+}
+
+async fn fetch_text(primary: bool) -> Option<String> {
     let result = timeout(std::time::Duration::from_secs(1), async {
-        tokio::process::Command::new("wl-paste")
+        let mut command = tokio::process::Command::new("wl-paste");
+        if primary {
+            command.arg("--primary");
+        }
+
+        command
             .arg("--no-newline")
             .arg("--type")
             .arg("text")
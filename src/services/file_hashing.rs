@@ -0,0 +1,193 @@
+// Hashes files for the overview's hashing module. Files can be large (ISOs...), so they're
+// streamed on their own thread to a worker per algorithm, and the overview's search is
+// re-run as the progress changes. Only one file is hashed at a time, starting another cancels
+// it. Digests of the last few files are kept until they change.
+
+use std::collections::HashMap;
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc, LazyLock, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, Instant, SystemTime};
+
+use crate::ipc;
+use crate::utils::hashing::{Algorithm, Hasher};
+
+const CHUNK_SIZE: usize = 4 * 1024 * 1024;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(250);
+
+// Chunks queued up for a worker that's behind, so reading doesn't run far ahead of the slowest one
+const QUEUED_CHUNKS: usize = 2;
+
+// Finished jobs kept, the oldest are forgotten first
+const MAX_FINISHED_JOBS: usize = 16;
+
+#[derive(Debug, Clone)]
+pub enum FileHashState {
+    Hashing {
+        bytes_read: u64,
+        size: u64
+    },
+    Done(Vec<(Algorithm, String)>),
+    Failed(String)
+}
+
+struct FileHashJob {
+    id: u64,
+    modified: Option<SystemTime>,
+    cancelled: Arc<AtomicBool>,
+    started: Instant,
+    state: FileHashState,
+}
+
+static JOBS: LazyLock<Mutex<HashMap<PathBuf, FileHashJob>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+static NEXT_JOB_ID: AtomicU64 = AtomicU64::new(0);
+
+/// Updates the job, unless it was cancelled and the path has a newer job since.
+fn set_state(path: &Path, id: u64, state: FileHashState) {
+    if let Some(job) = JOBS.lock().unwrap().get_mut(path)
+        && job.id == id
+    {
+        job.state = state;
+    }
+}
+
+/// Asks the overview to search again, so it picks up the new state.
+fn refresh_overview() {
+    let _ = ipc::client::send_message("refresh_overview_search");
+}
+
+fn hash(path: &Path, id: u64, size: u64, cancelled: &AtomicBool) -> std::io::Result<Vec<(Algorithm, String)>> {
+    let mut file = std::fs::File::open(path)?;
+
+    std::thread::scope(|scope| {
+        // Some of the algorithms are much slower than reading, so each gets its own thread
+        let workers = Algorithm::all().into_iter()
+            .map(|algorithm| {
+                let (sender, receiver) = mpsc::sync_channel::<Arc<[u8]>>(QUEUED_CHUNKS);
+                let worker = scope.spawn(move || {
+                    let mut hasher = Hasher::new(algorithm);
+                    for chunk in receiver {
+                        hasher.update(&chunk);
+                    }
+
+                    hasher.finalize()
+                });
+
+                (algorithm, sender, worker)
+            })
+            .collect::<Vec<_>>();
+
+        let mut buffer = vec![0; CHUNK_SIZE];
+        let mut bytes_read = 0;
+        let mut last_progress = Instant::now();
+
+        loop {
+            if cancelled.load(Ordering::Relaxed) {
+                return Err(std::io::Error::new(std::io::ErrorKind::Interrupted, "Cancelled"));
+            }
+
+            let length = file.read(&mut buffer)?;
+            if length == 0 {
+                break;
+            }
+
+            let chunk = Arc::<[u8]>::from(&buffer[..length]);
+            for (_, sender, _) in &workers {
+                let _ = sender.send(chunk.clone());
+            }
+
+            bytes_read += length as u64;
+            if last_progress.elapsed() >= PROGRESS_INTERVAL {
+                last_progress = Instant::now();
+                set_state(path, id, FileHashState::Hashing { bytes_read, size });
+                refresh_overview();
+            }
+        }
+
+        // Closing the channels lets the workers finish
+        workers.into_iter()
+            .map(|(algorithm, sender, worker)| {
+                drop(sender);
+                worker.join()
+                    .map(|digest| (algorithm, digest))
+                    .map_err(|_| std::io::Error::other(format!("The {} hasher crashed", algorithm.name())))
+            })
+            .collect()
+    })
+}
+
+/// Forgets the oldest finished jobs past `MAX_FINISHED_JOBS`.
+fn prune_jobs(jobs: &mut HashMap<PathBuf, FileHashJob>) {
+    let mut finished = jobs.iter()
+        .filter(|(_, job)| !matches!(job.state, FileHashState::Hashing { .. }))
+        .map(|(path, job)| (job.started, path.clone()))
+        .collect::<Vec<_>>();
+
+    if finished.len() <= MAX_FINISHED_JOBS {
+        return;
+    }
+
+    finished.sort_by_key(|(started, _)| *started);
+    for (_, path) in finished.into_iter().rev().skip(MAX_FINISHED_JOBS) {
+        jobs.remove(&path);
+    }
+}
+
+/// The state of hashing the file, which is started if it isn't hashed yet or changed since.
+pub fn hash_file(path: &Path) -> FileHashState {
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(err) => return FileHashState::Failed(err.to_string()),
+    };
+
+    let modified = metadata.modified().ok();
+    let mut jobs = JOBS.lock().unwrap();
+    if let Some(job) = jobs.get(path)
+        && job.modified == modified
+    {
+        return job.state.clone();
+    }
+
+    // The query moved on to another file, so the one being hashed isn't needed anymore
+    jobs.retain(|_, job| {
+        let running = matches!(job.state, FileHashState::Hashing { .. });
+        if running {
+            job.cancelled.store(true, Ordering::Relaxed);
+        }
+
+        !running
+    });
+
+    let id = NEXT_JOB_ID.fetch_add(1, Ordering::Relaxed);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let size = metadata.len();
+    let state = FileHashState::Hashing { bytes_read: 0, size };
+    jobs.insert(path.to_path_buf(), FileHashJob {
+        id,
+        modified,
+        cancelled: cancelled.clone(),
+        started: Instant::now(),
+        state: state.clone()
+    });
+    prune_jobs(&mut jobs);
+
+    std::thread::spawn({
+        let path = path.to_path_buf();
+        move || {
+            let state = match hash(&path, id, size, &cancelled) {
+                Ok(digests) => FileHashState::Done(digests),
+                Err(_) if cancelled.load(Ordering::Relaxed) => return,
+                Err(err) => {
+                    warn!(%err, path = %path.display(), "Failed to hash file");
+                    FileHashState::Failed(err.to_string())
+                },
+            };
+
+            set_state(&path, id, state);
+            refresh_overview();
+        }
+    });
+
+    state
+}
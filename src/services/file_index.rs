@@ -0,0 +1,343 @@
+// An in-memory index of the files under the roots in `[overview.files]`, for the overview's
+// file search. It's built on its own thread, then kept up to date by `notify` watchers. Every
+// indexed directory is watched on its own, so ignored directories (node_modules, target...)
+// don't use up inotify watches. The index is rebuilt when its config changes.
+
+use std::cmp::{Ordering as CmpOrdering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock, mpsc};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use ignore::gitignore::Gitignore;
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher as _};
+use notify::event::{AccessKind, AccessMode, ModifyKind};
+
+use crate::config::read_config;
+use crate::config::structs::OverviewFilesConfig;
+use crate::utils::{filesystem::expand_tilde, matching};
+
+fn read_ignore_file(directory: &Path) -> Option<Gitignore> {
+    let path = directory.join(".gitignore");
+    if !path.is_file() {
+        return None;
+    }
+
+    let (gitignore, err) = Gitignore::new(&path);
+    if let Some(err) = err {
+        warn!(%err, path = %path.display(), "Failed to read some of a .gitignore");
+    }
+
+    (!gitignore.is_empty()).then_some(gitignore)
+}
+
+pub struct IndexedFile {
+    pub path: PathBuf,
+    pub is_dir: bool,
+}
+
+struct FileIndex {
+    generation: u64,
+    config: OverviewFilesConfig,
+    roots: Vec<PathBuf>,
+    // Whether each path is a directory
+    files: BTreeMap<PathBuf, bool>,
+    ignores: HashMap<PathBuf, Gitignore>,
+    watcher: Option<RecommendedWatcher>,
+    watch_limit_warned: bool,
+}
+
+static INDEX: LazyLock<RwLock<Option<FileIndex>>> = LazyLock::new(|| RwLock::new(None));
+static GENERATION: AtomicU64 = AtomicU64::new(0);
+static REBUILDING: AtomicBool = AtomicBool::new(false);
+
+impl FileIndex {
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+            return false;
+        };
+
+        if name == ".git" || (!self.config.show_hidden && name.starts_with('.')) {
+            return true;
+        }
+
+        if !self.config.respect_gitignore {
+            return false;
+        }
+
+        // The innermost .gitignore with a matching rule wins. Parents are matched too, for
+        // changes in directories that were ignored after they were watched.
+        path.ancestors().skip(1)
+            .filter_map(|directory| self.ignores.get(directory))
+            .map(|gitignore| gitignore.matched_path_or_any_parents(path, is_dir))
+            .find(|matched| !matched.is_none())
+            .is_some_and(|matched| matched.is_ignore())
+    }
+
+    fn watch(&mut self, directory: &Path) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+
+        if let Err(err) = watcher.watch(directory, RecursiveMode::NonRecursive)
+            && !self.watch_limit_warned
+        {
+            self.watch_limit_warned = true;
+            warn!(%err, path = %directory.display(), "Failed to watch indexed directory, changes in it won't be picked up");
+        }
+    }
+
+    /// Adds a file, or a directory and everything in it, unless it's ignored.
+    fn add(&mut self, path: &Path) {
+        let mut pending = vec![path.to_path_buf()];
+
+        while let Some(path) = pending.pop() {
+            if self.files.len() >= self.config.max_files {
+                break;
+            }
+
+            let Ok(metadata) = std::fs::symlink_metadata(&path) else {
+                continue;
+            };
+
+            let is_dir = metadata.is_dir();
+            let is_root = self.roots.contains(&path);
+            if !is_root && self.is_ignored(&path, is_dir) {
+                continue;
+            }
+
+            if !is_root {
+                self.files.insert(path.clone(), is_dir);
+            }
+
+            if !is_dir {
+                continue;
+            }
+
+            if self.config.respect_gitignore
+                && let Some(gitignore) = read_ignore_file(&path)
+            {
+                self.ignores.insert(path.clone(), gitignore);
+            }
+
+            self.watch(&path);
+
+            if let Ok(entries) = std::fs::read_dir(&path) {
+                pending.extend(entries.filter_map(Result::ok).map(|entry| entry.path()));
+            }
+        }
+    }
+
+    fn remove(&mut self, path: &Path) {
+        let removed = self.files.range(path.to_path_buf()..)
+            .map(|(file, _)| file)
+            .take_while(|file| file.starts_with(path))
+            .cloned()
+            .collect::<Vec<_>>();
+
+        for file in removed {
+            if self.files.remove(&file) == Some(true)
+                && let Some(watcher) = self.watcher.as_mut()
+            {
+                let _ = watcher.unwatch(&file);
+            }
+
+            self.ignores.remove(&file);
+        }
+    }
+
+    /// Indexes a directory again from scratch, after its .gitignore changed.
+    fn reindex(&mut self, directory: &Path) {
+        let is_root = self.roots.iter().any(|root| root == directory);
+        if !is_root && !self.files.contains_key(directory) {
+            return;
+        }
+
+        // Roots aren't in the index, so their .gitignore is forgotten separately
+        self.remove(directory);
+        self.ignores.remove(directory);
+        self.add(directory);
+    }
+
+    fn handle_event(&mut self, event: notify::Event) {
+        let is_gitignore = event.paths.iter().any(|path| path.file_name() == Some(".gitignore".as_ref()));
+        let is_relevant = matches!(event.kind, EventKind::Create(_) | EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(_)))
+            // Edits only matter to .gitignore files, once they're saved
+            || (is_gitignore && matches!(event.kind, EventKind::Access(AccessKind::Close(AccessMode::Write))));
+
+        if !is_relevant {
+            return;
+        }
+
+        for path in event.paths {
+            if path.file_name() == Some(".gitignore".as_ref())
+                && self.config.respect_gitignore
+                && let Some(directory) = path.parent()
+            {
+                self.reindex(directory);
+            }
+
+            // Renames come as a removal of the old path and a creation of the new one, so
+            // whether the path still exists is all that matters
+            if path.exists() {
+                if !self.files.contains_key(&path) {
+                    self.add(&path);
+                }
+            } else {
+                self.remove(&path);
+            }
+        }
+    }
+}
+
+fn build(config: OverviewFilesConfig) {
+    let generation = GENERATION.fetch_add(1, Ordering::Relaxed) + 1;
+    let (tx, rx) = mpsc::channel();
+
+    let watcher = notify::recommended_watcher(tx)
+        .inspect_err(|err| error!(%err, "Failed to create file index watcher"))
+        .ok();
+
+    let roots = config.roots.iter()
+        .map(|root| PathBuf::from(expand_tilde(root)))
+        .filter(|root| root.is_dir())
+        .collect::<Vec<_>>();
+
+    let mut index = FileIndex {
+        generation,
+        config,
+        roots: roots.clone(),
+        files: BTreeMap::new(),
+        ignores: HashMap::new(),
+        watcher,
+        watch_limit_warned: false,
+    };
+
+    for root in &roots {
+        index.add(root);
+    }
+
+    info!(files = index.files.len(), "Built file index");
+    *INDEX.write().unwrap() = Some(index);
+    REBUILDING.store(false, Ordering::Relaxed);
+
+    // Changes made while building are queued up, so nothing is missed. The loop ends when
+    // a rebuild drops this index's watcher.
+    for res in rx {
+        match res {
+            Ok(event) => {
+                let mut index = INDEX.write().unwrap();
+                match index.as_mut() {
+                    Some(index) if index.generation == generation => index.handle_event(event),
+                    _ => break,
+                }
+            },
+
+            Err(err) => {
+                error!(%err, "Error watching indexed files");
+            },
+        }
+    }
+}
+
+/// Rebuilds the index in the background if the config changed since it was built.
+fn rebuild_if_outdated() {
+    let config = read_config().overview.files.clone();
+    let outdated = INDEX.read().unwrap()
+        .as_ref()
+        .is_some_and(|index| index.config != config);
+
+    if outdated && !REBUILDING.swap(true, Ordering::Relaxed) {
+        std::thread::spawn(move || build(config));
+    }
+}
+
+pub fn activate() {
+    REBUILDING.store(true, Ordering::Relaxed);
+    let config = read_config().overview.files.clone();
+    std::thread::spawn(move || build(config));
+}
+
+/// A search match, ordered from worse to better: a lower score, then a longer path.
+struct ScoredFile {
+    score: f32,
+    path: PathBuf,
+    is_dir: bool,
+}
+
+impl Ord for ScoredFile {
+    fn cmp(&self, other: &Self) -> CmpOrdering {
+        self.score.total_cmp(&other.score)
+            .then_with(|| other.path.as_os_str().len().cmp(&self.path.as_os_str().len()))
+            .then_with(|| other.path.cmp(&self.path))
+    }
+}
+
+impl PartialOrd for ScoredFile {
+    fn partial_cmp(&self, other: &Self) -> Option<CmpOrdering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for ScoredFile {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == CmpOrdering::Equal
+    }
+}
+
+impl Eq for ScoredFile {}
+
+/// Searches the index with the same fuzzy scorer as apps. The file name counts the most,
+/// but queries with a slash are also matched against the whole path.
+pub fn search(query: &str, limit: usize) -> Vec<IndexedFile> {
+    rebuild_if_outdated();
+
+    let query = query.trim();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    // Only the cheap subsequence check runs under the lock, so the watchers aren't kept
+    // waiting on the scoring
+    let match_paths = query.contains('/');
+    let candidates = {
+        let index = INDEX.read().unwrap();
+        let Some(index) = index.as_ref() else {
+            return Vec::new();
+        };
+
+        index.files.iter()
+            .filter(|(path, _)| {
+                path.file_name().is_some_and(|name| matching::is_subsequence(&name.to_string_lossy(), query))
+                    || (match_paths && matching::is_subsequence(&path.to_string_lossy(), query))
+            })
+            .map(|(path, is_dir)| (path.clone(), *is_dir))
+            .collect::<Vec<_>>()
+    };
+
+    // Only the best `limit` matches are kept while scoring, the worst of them on top
+    let mut best = BinaryHeap::with_capacity(limit + 1);
+    for (path, is_dir) in candidates {
+        let Some(name) = path.file_name().map(|name| name.to_string_lossy()) else {
+            continue;
+        };
+
+        let name_score = matching::fuzzy_score(&name, query).map(|m| m.score as f32);
+        let path_score = match_paths
+            .then(|| matching::fuzzy_score(&path.to_string_lossy(), query))
+            .flatten()
+            .map(|m| m.score as f32 * 0.8);
+
+        let Some(score) = name_score.into_iter().chain(path_score).reduce(f32::max) else {
+            continue;
+        };
+
+        best.push(Reverse(ScoredFile { score, path, is_dir }));
+        if best.len() > limit {
+            best.pop();
+        }
+    }
+
+    best.into_sorted_vec().into_iter()
+        .map(|Reverse(file)| IndexedFile { path: file.path, is_dir: file.is_dir })
+        .collect()
+}
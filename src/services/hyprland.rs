@@ -2,8 +2,9 @@ use std::sync::LazyLock;
 use futures_signals::signal::Mutable;
 use gdk::prelude::MonitorExt as _;
 use hyprland::data::{Client, Monitor, Workspace, Workspaces};
+use hyprland::dispatch::{Dispatch, DispatchType, WindowIdentifier, WorkspaceIdentifierWithSpecial};
 use hyprland::event_listener::AsyncEventListener;
-use hyprland::shared::{HyprData as _, HyprDataActive as _, HyprDataActiveOptional as _};
+use hyprland::shared::{Address, HyprData as _, HyprDataActive as _, HyprDataActiveOptional as _};
 
 use crate::utils::display;
 
@@ -45,6 +46,39 @@ pub fn call_hyprctl_batch(commands: &[&str]) -> Option<Vec<String>> {
     Some(results)
 }
 
+/// Something that can be done to a window from outside of it, by its address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WindowAction {
+    Focus,
+    Close,
+    MoveToActiveWorkspace,
+    ToggleFloating
+}
+
+pub fn dispatch_window_action(address: &str, action: WindowAction) {
+    let window = WindowIdentifier::Address(Address::new(address));
+    let result = match action {
+        WindowAction::Focus => Dispatch::call(DispatchType::FocusWindow(window)),
+        WindowAction::Close => Dispatch::call(DispatchType::CloseWindow(window)),
+        WindowAction::ToggleFloating => Dispatch::call(DispatchType::ToggleFloating(Some(window))),
+
+        WindowAction::MoveToActiveWorkspace => {
+            let Some(workspace) = HYPRLAND.active_workspace.get_cloned() else {
+                return;
+            };
+
+            Dispatch::call(DispatchType::MoveToWorkspace(
+                WorkspaceIdentifierWithSpecial::Id(workspace.id),
+                Some(window)
+            ))
+        }
+    };
+
+    if let Err(err) = result {
+        error!(address, ?action, %err, "Failed to dispatch window action");
+    }
+}
+
 fn refresh_active_client() {
     HYPRLAND.active_client.set(Client::get_active().ok().unwrap_or(None));
 }
@@ -13,6 +13,13 @@ pub mod clipboard;
 pub mod ai;
 pub mod weather;
 pub mod screen_recorder;
+pub mod search_plugins;
+pub mod file_index;
+pub mod characters;
+pub mod file_hashing;
+pub mod browser_index;
+pub mod ssh_hosts;
+pub mod recent_files;
 
 pub async fn activate_all() {
     date_time::activate();
@@ -22,9 +29,14 @@ pub async fn activate_all() {
     tray::activate();
     wireplumber::activate();
     apps::activate().await;
+    file_index::activate();
+    browser_index::activate();
+    recent_files::activate();
+    characters::activate().await;
+    ssh_hosts::activate().await;
     calculator::activate();
     g_translate::activate();
-    notifications::activate();
+    notifications::activate().await;
     //networkmanager::activate();
     clipboard::activate();
     ai::activate().await;
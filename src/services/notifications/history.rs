@@ -0,0 +1,161 @@
+// Every notification is written to SQLite, along with when it was closed and which of its
+// actions was invoked, so it can be found again after it's gone. Writes go through a single
+// task so they land in the order they happened.
+
+use std::sync::{LazyLock, OnceLock};
+use std::time::Duration;
+use async_broadcast::Receiver;
+use tokio::sync::mpsc::{self, UnboundedSender};
+
+use crate::config::{read_config, NotificationRuleAction};
+use crate::sql::wrappers::{notifications::{self, NotificationHistoryEntry}, state};
+use crate::utils::broadcast::BroadcastChannel;
+use super::wrapper::{Notification, NotificationCloseReason, NotificationHint};
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+enum HistoryEvent {
+    Received(Box<NotificationHistoryEntry>),
+    Closed(u32, NotificationCloseReason, chrono::DateTime<chrono::Local>),
+    ActionInvoked(u32, String, chrono::DateTime<chrono::Local>),
+}
+
+static SENDER: OnceLock<UnboundedSender<HistoryEvent>> = OnceLock::new();
+
+/// Sent after the history changes.
+static CHANGED: LazyLock<BroadcastChannel<()>> = LazyLock::new(|| BroadcastChannel::new(10));
+
+pub fn activate() {
+    let (sender, mut receiver) = mpsc::unbounded_channel();
+    let _ = SENDER.set(sender);
+
+    tokio::spawn(async move {
+        while let Some(event) = receiver.recv().await {
+            let result = match event {
+                HistoryEvent::Received(entry) => notifications::upsert_notification(*entry).await,
+                HistoryEvent::Closed(id, reason, closed_at) => notifications::set_closed(id, reason as u32, closed_at).await,
+                HistoryEvent::ActionInvoked(id, action, invoked_at) => notifications::set_action_invoked(id, &action, invoked_at).await,
+            };
+
+            match result {
+                Ok(()) => CHANGED.send(()).await,
+                Err(err) => error!(%err, "Could not write notification history"),
+            }
+        }
+    });
+
+    tokio::spawn(async {
+        let mut interval = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            interval.tick().await;
+
+            let config = read_config().notifications.history.clone();
+            if let Err(err) = notifications::prune(config.max_entries, config.max_age_days).await {
+                error!(%err, "Could not prune notification history");
+            }
+        }
+    });
+}
+
+/// The last ID handed out, so IDs keep counting up across restarts.
+pub async fn last_id() -> u32 {
+    let last_id = state::get_last_notification_id().await
+        .inspect_err(|err| error!(%err, "Could not get the last notification ID"))
+        .unwrap_or(0);
+
+    let max_history_id = notifications::get_max_id().await
+        .inspect_err(|err| error!(%err, "Could not get the last notification in the history"))
+        .unwrap_or(0);
+
+    last_id.max(max_history_id)
+}
+
+fn send(event: HistoryEvent) {
+    if let Some(sender) = SENDER.get() {
+        let _ = sender.send(event);
+    }
+}
+
+fn is_enabled() -> bool {
+    read_config().notifications.history.enabled
+}
+
+pub fn record_received(notification: &Notification) {
+    // The ID is kept even when the history isn't, as IDs shouldn't be reused
+    let id = notification.id;
+    tokio::spawn(async move {
+        if let Err(err) = state::set_last_notification_id(id).await {
+            error!(%err, "Could not save the last notification ID");
+        }
+    });
+
+    if !is_enabled() {
+        return;
+    }
+
+    let mut entry = NotificationHistoryEntry {
+        id: notification.id,
+        app_name: notification.app_name.clone(),
+        app_icon: notification.app_icon.clone(),
+        summary: notification.summary.clone(),
+        body: notification.body.clone(),
+        actions: notification.actions.iter()
+            .map(|action| (action.id.clone(), action.localized_name.clone()))
+            .collect(),
+        urgency: None,
+        category: None,
+        received_at: chrono::Local::now(),
+        closed_at: None,
+        close_reason: None,
+        action_invoked_at: None,
+        invoked_action: None,
+        rule: notification.rule.as_ref().map(|rule| rule.name.clone()),
+        silent: notification.rule.as_ref().is_some_and(|rule| rule.action == NotificationRuleAction::Silent),
+        dropped: notification.rule.as_ref().is_some_and(|rule| rule.action == NotificationRuleAction::Drop),
+    };
+
+    for hint in &notification.hints {
+        match hint {
+            NotificationHint::Urgency(urgency) => entry.urgency = Some(*urgency),
+            NotificationHint::Category(category) => entry.category = Some(category.clone()),
+        }
+    }
+
+    send(HistoryEvent::Received(Box::new(entry)));
+}
+
+pub fn record_closed(id: u32, reason: NotificationCloseReason) {
+    if is_enabled() {
+        send(HistoryEvent::Closed(id, reason, chrono::Local::now()));
+    }
+}
+
+pub fn record_action_invoked(id: u32, action: &str) {
+    if is_enabled() {
+        send(HistoryEvent::ActionInvoked(id, action.to_owned(), chrono::Local::now()));
+    }
+}
+
+/// Notifies of changes to the history.
+pub fn subscribe() -> Receiver<()> {
+    CHANGED.subscribe()
+}
+
+pub async fn search(query: &str, app: Option<String>, include_dropped: bool, limit: usize) -> Vec<NotificationHistoryEntry> {
+    notifications::search(query, app, include_dropped, limit).await
+        .inspect_err(|err| error!(%err, "Could not search notification history"))
+        .unwrap_or_default()
+}
+
+pub async fn apps() -> Vec<String> {
+    notifications::get_apps().await
+        .inspect_err(|err| error!(%err, "Could not get notification history apps"))
+        .unwrap_or_default()
+}
+
+pub async fn clear() {
+    match notifications::clear().await {
+        Ok(()) => CHANGED.send(()).await,
+        Err(err) => error!(%err, "Could not clear notification history"),
+    }
+}
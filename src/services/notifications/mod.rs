@@ -2,6 +2,8 @@ pub mod proxy;
 pub mod wrapper;
 pub mod bus;
 pub mod client;
+pub mod history;
+pub mod rules;
 
 use std::collections::HashMap;
 use std::sync::{Arc, LazyLock, OnceLock, RwLock};
@@ -15,8 +17,10 @@ static CHANNEL: LazyLock<BroadcastChannel<BusEvent>> = LazyLock::new(|| Broadcas
 
 pub static NOTIFICATIONS: OnceLock<Arc<RwLock<HashMap<u32, Notification>>>> = OnceLock::new();
 
-pub fn activate() {
-    let manager = NotificationManager::default();
+pub async fn activate() {
+    history::activate();
+
+    let manager = NotificationManager::new(history::last_id().await);
     let mut receiver = manager.subscribe();
 
     NOTIFICATIONS.set(manager.notifications())
@@ -86,5 +90,6 @@ pub fn invoke_notification_action(
     id: u32,
     action_key: &str,
 ) {
+    history::record_action_invoked(id, action_key);
     wrapper::emit_notification_action_invoked(id, action_key);
 }
\ No newline at end of file
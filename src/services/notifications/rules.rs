@@ -0,0 +1,157 @@
+// User rules from the config that match notifications as they arrive, and drop them, keep
+// them out of sight, or change how they're shown.
+
+use std::sync::{LazyLock, RwLock};
+use std::time::Duration;
+use regex::Regex;
+
+use crate::config::{read_config, NotificationRuleAction, NotificationUrgency};
+use crate::config::structs::NotificationRuleConfig;
+use super::wrapper::{Notification, NotificationHint};
+
+/// A rule that matched a notification, and what it changed about how it's shown.
+#[derive(Debug, Clone)]
+pub struct AppliedRule {
+    pub name: String,
+    pub action: NotificationRuleAction,
+    /// How long the popup stays up. Zero keeps it up until it's dismissed.
+    pub popup_timeout: Option<Duration>,
+    pub ignore_dnd: bool,
+}
+
+struct CompiledRule {
+    config: NotificationRuleConfig,
+    app_name: Option<Regex>,
+    summary: Option<Regex>,
+    body: Option<Regex>,
+    /// A pattern didn't compile, so the rule never matches.
+    invalid: bool,
+}
+
+impl CompiledRule {
+    fn new(config: &NotificationRuleConfig) -> Self {
+        let mut invalid = false;
+        let mut compile = |field: &str, pattern: &Option<String>| {
+            pattern.as_ref().and_then(|pattern| match Regex::new(pattern) {
+                Ok(regex) => Some(regex),
+                Err(err) => {
+                    warn!(%err, rule = config.name, field, "Invalid notification rule pattern");
+                    invalid = true;
+                    None
+                },
+            })
+        };
+
+        let app_name = compile("app_name", &config.app_name);
+        let summary = compile("summary", &config.summary);
+        let body = compile("body", &config.body);
+
+        Self {
+            config: config.clone(),
+            app_name,
+            summary,
+            body,
+            invalid,
+        }
+    }
+
+    fn matches(&self, notification: &Notification) -> bool {
+        let is_match = |regex: &Option<Regex>, text: &str| regex.as_ref().is_none_or(|regex| regex.is_match(text));
+
+        !self.invalid
+            && is_match(&self.app_name, &notification.app_name)
+            && is_match(&self.summary, &notification.summary)
+            && is_match(&self.body, &notification.body)
+            && self.config.category.as_ref().is_none_or(|category| category_of(notification) == Some(category))
+            && self.config.urgency.is_none_or(|urgency| urgency_of(notification) == urgency as u8)
+    }
+}
+
+// Compiled again whenever the rules in the config change
+static RULES: LazyLock<RwLock<Vec<CompiledRule>>> = LazyLock::new(|| RwLock::new(Vec::new()));
+
+fn category_of(notification: &Notification) -> Option<&String> {
+    notification.hints.iter().find_map(|hint| match hint {
+        NotificationHint::Category(category) => Some(category),
+        _ => None,
+    })
+}
+
+/// Notifications without an urgency are normal ones.
+fn urgency_of(notification: &Notification) -> u8 {
+    notification.hints.iter()
+        .find_map(|hint| match hint {
+            NotificationHint::Urgency(urgency) => Some(*urgency),
+            _ => None,
+        })
+        .unwrap_or(NotificationUrgency::Normal as u8)
+}
+
+fn set_urgency(notification: &mut Notification, urgency: NotificationUrgency) {
+    notification.hints.retain(|hint| !matches!(hint, NotificationHint::Urgency(_)));
+    notification.hints.push(NotificationHint::Urgency(urgency as u8));
+}
+
+fn find_match(notification: &Notification) -> Option<NotificationRuleConfig> {
+    let config = read_config();
+    let up_to_date = {
+        let rules = RULES.read().unwrap();
+        rules.len() == config.notifications.rules.len()
+            && rules.iter().zip(&config.notifications.rules).all(|(rule, config)| rule.config == *config)
+    };
+
+    if !up_to_date {
+        *RULES.write().unwrap() = config.notifications.rules.iter().map(CompiledRule::new).collect();
+    }
+
+    RULES.read().unwrap().iter()
+        .find(|rule| rule.matches(notification))
+        .map(|rule| rule.config.clone())
+}
+
+fn run_command(command: String, notification: &Notification) {
+    let environment = [
+        ("NOTIFICATION_ID", notification.id.to_string()),
+        ("NOTIFICATION_APP_NAME", notification.app_name.clone()),
+        ("NOTIFICATION_SUMMARY", notification.summary.clone()),
+        ("NOTIFICATION_BODY", notification.body.clone()),
+        ("NOTIFICATION_CATEGORY", category_of(notification).cloned().unwrap_or_default()),
+        ("NOTIFICATION_URGENCY", urgency_of(notification).to_string()),
+    ];
+
+    std::thread::spawn(move || {
+        let result = std::process::Command::new("bash")
+            .arg("-c")
+            .arg(&command)
+            .envs(environment)
+            .output();
+
+        if let Err(err) = result {
+            error!(%err, command, "Failed to run notification rule command");
+        }
+    });
+}
+
+/// Applies the first rule that matches the notification, and records it on the notification.
+pub fn apply(notification: &mut Notification) -> NotificationRuleAction {
+    let Some(rule) = find_match(notification) else {
+        return NotificationRuleAction::Show;
+    };
+
+    if let Some(urgency) = rule.set_urgency {
+        set_urgency(notification, urgency);
+    }
+
+    if let Some(command) = rule.command {
+        run_command(command, notification);
+    }
+
+    notification.rule = Some(AppliedRule {
+        name: rule.name,
+        action: rule.action,
+        popup_timeout: rule.timeout_ms.map(Duration::from_millis),
+        ignore_dnd: rule.ignore_dnd,
+    });
+
+    rule.action
+}
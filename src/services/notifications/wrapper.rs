@@ -8,8 +8,11 @@ use dbus::channel::{MatchingReceiver as _, Sender as _};
 use dbus_crossroads::{Crossroads, IfaceToken};
 use dbus_tokio::connection;
 
+use crate::config::NotificationRuleAction;
 use crate::utils::broadcast::BroadcastChannel;
 use super::bus::{self, BusEvent};
+use super::history;
+use super::rules::{self, AppliedRule};
 use super::proxy::{self, server::OrgFreedesktopNotifications};
 
 static SHARED_CONNECTION: OnceLock<Arc<SyncConnection>> = OnceLock::new();
@@ -44,6 +47,8 @@ pub struct Notification {
     pub actions: Vec<NotificationAction>,
     pub hints: Vec<NotificationHint>,
     pub expire_timeout: i32,
+    /// The notification rule that matched, if any.
+    pub rule: Option<AppliedRule>,
 }
 
 #[derive(Debug, Clone)]
@@ -92,30 +97,52 @@ impl OrgFreedesktopNotifications for NotificationManager {
             actions,
             hints,
             expire_timeout,
+            rule: None,
         };
 
         let mut notifications = self.notifications.write()
             .map_err(|_| dbus::MethodErr::failed(&"Failed to acquire write lock on notifications"))?;
 
-        if replaces_id > 0 {
-            notification.id = replaces_id;
-            notifications.insert(replaces_id, notification.clone());
-            self.channel.send_blocking(BusEvent::NotificationUpdated(replaces_id, notification));
-            Ok(replaces_id)
+        notification.id = if replaces_id > 0 {
+            replaces_id
         } else {
-            let id = {
-                let mut id_counter = self.id_counter.write()
-                    .map_err(|_| dbus::MethodErr::failed(&"Failed to acquire write lock on id_counter"))?;
+            let mut id_counter = self.id_counter.write()
+                .map_err(|_| dbus::MethodErr::failed(&"Failed to acquire write lock on id_counter"))?;
 
-                *id_counter += 1;
-                *id_counter
-            };
+            *id_counter += 1;
+            *id_counter
+        };
+
+        let id = notification.id;
+        let action = rules::apply(&mut notification);
+
+        // Dropped notifications are recorded too, so rule hits can be looked up and their
+        // IDs aren't handed out again
+        history::record_received(&notification);
+
+        match action {
+            NotificationRuleAction::Show => {},
+
+            _ => {
+                // A notification that's kept out of sight is closed right away, along with
+                // the one it replaces
+                if notifications.remove(&id).is_some() {
+                    self.channel.send_blocking(BusEvent::NotificationClosed(id));
+                }
+
+                emit_notification_closed(id, NotificationCloseReason::Dismissed as u32);
+                return Ok(id);
+            },
+        }
 
-            notification.id = id;
-            notifications.insert(id, notification.clone());
+        let replaced = notifications.insert(id, notification.clone()).is_some();
+        if replaced {
+            self.channel.send_blocking(BusEvent::NotificationUpdated(id, notification));
+        } else {
             self.channel.send_blocking(BusEvent::NotificationAdded(notification));
-            Ok(id)
         }
+
+        Ok(id)
     }
 
     fn close_notification(&mut self, id: u32) -> Result<(), dbus::MethodErr> {
@@ -144,19 +171,18 @@ impl OrgFreedesktopNotifications for NotificationManager {
     }
 }
 
-impl Default for NotificationManager {
-    fn default() -> Self {
+impl NotificationManager {
+    /// Creates a manager whose IDs continue from `last_id`.
+    pub fn new(last_id: u32) -> Self {
         let channel = BroadcastChannel::new(10);
 
         NotificationManager {
-            id_counter: Arc::new(RwLock::new(0)),
+            id_counter: Arc::new(RwLock::new(last_id)),
             notifications: Arc::new(RwLock::new(HashMap::new())),
             channel,
         }
     }
-}
 
-impl NotificationManager {
     /// Subscribes to notification events.
     pub fn subscribe(&self) -> Receiver<BusEvent> {
         self.channel.subscribe()
@@ -279,6 +305,7 @@ pub(super) fn close_notification_by_id(
         .map_err(|_| dbus::MethodErr::failed(&"Failed to acquire write lock on notifications"))?;
 
     if notifications.remove(&id).is_some() {
+        history::record_closed(id, reason.clone());
         channel.send_blocking(BusEvent::NotificationClosed(id));
         emit_notification_closed(id, reason as u32);
         Ok(())
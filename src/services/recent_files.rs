@@ -0,0 +1,254 @@
+// Recently opened documents from the freedesktop `recently-used.xbel`, which GTK and most
+// other toolkits write to. The file is watched, and the overview's recent files and search
+// are refreshed whenever it changes.
+
+use std::path::{Path, PathBuf};
+use std::sync::RwLock;
+use std::time::Duration;
+use gio::prelude::FileExt as _;
+use notify::{EventKind, RecursiveMode, Watcher as _};
+use xml::reader::{EventReader, XmlEvent};
+
+use crate::ipc;
+use crate::utils::{filesystem::get_home_directory, matching};
+
+const MAX_FILES: usize = 500;
+
+// Applications replace the file through a temporary one, which fires a few events at once
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+#[derive(Debug, Clone)]
+pub struct RecentApplication {
+    pub name: String,
+    pub exec: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RecentFile {
+    pub uri: String,
+    pub path: PathBuf,
+    pub mime_type: String,
+    /// The application that opened the file last.
+    pub application: Option<RecentApplication>,
+    pub modified: chrono::DateTime<chrono::Utc>,
+}
+
+impl RecentFile {
+    pub fn name(&self) -> String {
+        self.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default()
+    }
+
+    pub fn icon(&self) -> String {
+        gio::content_type_get_generic_icon_name(&self.mime_type)
+            .map_or_else(|| "text-x-generic".to_owned(), |icon| icon.to_string())
+    }
+
+    /// The command that opens the file with the application that opened it last, or with the
+    /// default application if that isn't known.
+    pub fn reopen_command(&self) -> String {
+        let path = shlex::try_quote(&self.path.to_string_lossy()).map(|path| path.into_owned()).unwrap_or_default();
+        let Some(application) = &self.application else {
+            return format!("xdg-open {}", path);
+        };
+
+        // The command line is stored quoted as a whole, e.g. `'gedit %u'`
+        let exec = shlex::split(&application.exec)
+            .filter(|parts| parts.len() == 1)
+            .and_then(|parts| parts.into_iter().next())
+            .unwrap_or_else(|| application.exec.clone());
+
+        let uri = shlex::try_quote(&self.uri).map(|uri| uri.into_owned()).unwrap_or_default();
+        if exec.contains('%') {
+            exec.replace("%u", &uri)
+                .replace("%U", &uri)
+                .replace("%f", &path)
+                .replace("%F", &path)
+        } else {
+            format!("{} {}", exec, path)
+        }
+    }
+}
+
+static FILES: RwLock<Vec<RecentFile>> = RwLock::new(Vec::new());
+
+fn xbel_path() -> PathBuf {
+    PathBuf::from(get_home_directory()).join(".local/share/recently-used.xbel")
+}
+
+fn parse_time(time: &str) -> Option<chrono::DateTime<chrono::Utc>> {
+    chrono::DateTime::parse_from_rfc3339(time).ok().map(|time| time.to_utc())
+}
+
+fn attribute<'a>(attributes: &'a [xml::attribute::OwnedAttribute], name: &str) -> Option<&'a str> {
+    attributes.iter()
+        .find(|attribute| attribute.name.local_name == name)
+        .map(|attribute| attribute.value.as_str())
+}
+
+fn parse(path: &Path) -> anyhow::Result<Vec<RecentFile>> {
+    let file = std::io::BufReader::new(std::fs::File::open(path)?);
+    let mut files = Vec::new();
+
+    // The bookmark being read, and when its last application opened it
+    let mut current: Option<(RecentFile, Option<chrono::DateTime<chrono::Utc>>)> = None;
+
+    for event in EventReader::new(file) {
+        match event? {
+            XmlEvent::StartElement { name, attributes, .. } => match name.local_name.as_str() {
+                "bookmark" => {
+                    let uri = attribute(&attributes, "href").unwrap_or_default().to_owned();
+                    let modified = ["modified", "visited", "added"].iter()
+                        .filter_map(|name| attribute(&attributes, name).and_then(parse_time))
+                        .max()
+                        .unwrap_or_default();
+
+                    current = Some((RecentFile {
+                        path: gio::File::for_uri(&uri).path().unwrap_or_default(),
+                        uri,
+                        mime_type: String::new(),
+                        application: None,
+                        modified,
+                    }, None));
+                },
+
+                "mime-type" => {
+                    if let Some((file, _)) = current.as_mut() {
+                        file.mime_type = attribute(&attributes, "type").unwrap_or_default().to_owned();
+                    }
+                },
+
+                "application" => {
+                    let Some((file, last_opened)) = current.as_mut() else {
+                        continue;
+                    };
+
+                    let opened = attribute(&attributes, "modified").and_then(parse_time);
+                    if file.application.is_none() || opened > *last_opened {
+                        *last_opened = opened;
+                        file.application = Some(RecentApplication {
+                            name: attribute(&attributes, "name").unwrap_or_default().to_owned(),
+                            exec: attribute(&attributes, "exec").unwrap_or_default().to_owned(),
+                        });
+                    }
+                },
+
+                _ => {},
+            },
+
+            XmlEvent::EndElement { name } if name.local_name == "bookmark" => {
+                // Only local files that still exist can be reopened
+                if let Some((file, _)) = current.take()
+                    && file.path.exists()
+                {
+                    files.push(file);
+                }
+            },
+
+            _ => {},
+        }
+    }
+
+    files.sort_by_key(|file| std::cmp::Reverse(file.modified));
+    files.truncate(MAX_FILES);
+    Ok(files)
+}
+
+fn load() {
+    let path = xbel_path();
+    let files = match parse(&path) {
+        Ok(files) => files,
+        Err(err) => {
+            if path.exists() {
+                warn!(%err, path = %path.display(), "Failed to read recently used files");
+            }
+
+            Vec::new()
+        },
+    };
+
+    *FILES.write().unwrap() = files;
+}
+
+fn watch() {
+    let path = xbel_path();
+    let Some(directory) = path.parent() else {
+        return;
+    };
+
+    let (tx, rx) = std::sync::mpsc::channel();
+    let mut watcher = match notify::recommended_watcher(tx) {
+        Ok(watcher) => watcher,
+        Err(err) => {
+            error!(%err, "Failed to create recently used files watcher");
+            return;
+        },
+    };
+
+    // The file is replaced rather than written to, so its directory is watched instead
+    if let Err(err) = watcher.watch(directory, RecursiveMode::NonRecursive) {
+        error!(%err, path = %directory.display(), "Failed to watch recently used files");
+        return;
+    }
+
+    while let Ok(result) = rx.recv() {
+        let event = match result {
+            Ok(event) => event,
+            Err(err) => {
+                error!(%err, "Error watching recently used files");
+                continue;
+            },
+        };
+
+        if matches!(event.kind, EventKind::Access(_)) || !event.paths.contains(&path) {
+            continue;
+        }
+
+        while rx.recv_timeout(DEBOUNCE).is_ok() {}
+
+        load();
+        let _ = ipc::client::send_message("update_overview_windows");
+        let _ = ipc::client::send_message("refresh_overview_search");
+    }
+}
+
+pub fn activate() {
+    std::thread::spawn(|| {
+        load();
+        watch();
+    });
+}
+
+/// The most recently opened files, most recent first.
+pub fn recent(limit: usize) -> Vec<RecentFile> {
+    FILES.read().unwrap().iter().take(limit).cloned().collect()
+}
+
+/// Searches file names, then their paths. An empty query returns the most recent files.
+pub fn search(query: &str, limit: usize) -> Vec<RecentFile> {
+    let query = query.trim();
+    if query.is_empty() {
+        return recent(limit);
+    }
+
+    let files = FILES.read().unwrap();
+    let mut matches = files.iter()
+        .enumerate()
+        .filter_map(|(index, file)| {
+            let name_score = matching::fuzzy_score(&file.name(), query).map(|m| m.score as f32);
+            let path_score = matching::fuzzy_score(&file.path.to_string_lossy(), query).map(|m| m.score as f32 * 0.6);
+            let score = name_score.into_iter().chain(path_score).reduce(f32::max)?;
+
+            // Files are sorted most recent first, so ties go to the more recent one
+            Some((score, index, file))
+        })
+        .collect::<Vec<_>>();
+
+    matches.sort_by(|(a_score, a_index, _), (b_score, b_index, _)| {
+        b_score.total_cmp(a_score).then(a_index.cmp(b_index))
+    });
+
+    matches.into_iter()
+        .take(limit)
+        .map(|(.., file)| file.clone())
+        .collect()
+}
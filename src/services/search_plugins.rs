@@ -0,0 +1,260 @@
+// External search plugins for the overview, declared in `[[overview.plugins]]`.
+// A plugin is an executable that receives a query as one line of JSON on stdin and
+// answers with JSON on stdout:
+//
+//   -> {"id": 1, "query": "foo"}
+//   <- {"id": 1, "results": [{"title": "Foo", "subtitle": "Bar", "icon": "text-x-generic",
+//       "action": {"type": "copy", "value": "foo"}}]}
+//
+// Per-query plugins are started for every query, and everything they print before exiting
+// is read as the answer. Long-running plugins are started once and answer each query with
+// a single line, echoing its ID so late answers to older queries can be told apart. Each
+// long-running plugin answers one query at a time, and queries that were typed past while
+// waiting for their turn are skipped.
+
+use std::collections::HashMap;
+use std::io::{BufRead as _, BufReader, Write as _};
+use std::process::{Child, ChildStdin, Command, Stdio};
+use std::sync::{Arc, LazyLock, Mutex, mpsc};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+
+use crate::config::OverviewPluginMode;
+use crate::config::structs::OverviewPluginConfig;
+use crate::utils::filesystem::expand_tilde;
+
+#[derive(Debug, Serialize)]
+struct PluginRequest<'a> {
+    id: u64,
+    query: &'a str,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "type", content = "value", rename_all = "snake_case")]
+pub enum PluginAction {
+    Launch(String),
+    RunCommand(String),
+    Copy(String),
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PluginResult {
+    pub title: String,
+    #[serde(default)]
+    pub subtitle: Option<String>,
+    #[serde(default)]
+    pub icon: Option<String>,
+    #[serde(default)]
+    pub action_text: Option<String>,
+    pub action: PluginAction,
+}
+
+#[derive(Debug, Deserialize)]
+struct PluginResponse {
+    #[serde(default)]
+    id: Option<u64>,
+    #[serde(default)]
+    results: Vec<PluginResult>,
+}
+
+struct PluginProcess {
+    child: Child,
+    /// Closed once a per-query plugin has been sent its query.
+    stdin: Option<ChildStdin>,
+    lines: mpsc::Receiver<String>,
+}
+
+impl PluginProcess {
+    fn spawn(config: &OverviewPluginConfig) -> anyhow::Result<Self> {
+        let argv = shlex::split(&config.command)
+            .filter(|argv| !argv.is_empty())
+            .ok_or_else(|| anyhow::anyhow!("Invalid plugin command: {}", config.command))?;
+
+        let mut child = Command::new(expand_tilde(&argv[0]))
+            .args(&argv[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()?;
+
+        let stdin = child.stdin.take().ok_or_else(|| anyhow::anyhow!("Failed to open plugin stdin"))?;
+        let stdout = child.stdout.take().ok_or_else(|| anyhow::anyhow!("Failed to open plugin stdout"))?;
+
+        // Read stdout on its own thread so reads can time out
+        let (sender, lines) = mpsc::channel();
+        std::thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line).is_err() {
+                    break;
+                }
+            }
+        });
+
+        Ok(Self { child, stdin: Some(stdin), lines })
+    }
+
+    fn send(&mut self, request: &str) -> anyhow::Result<()> {
+        let stdin = self.stdin.as_mut().ok_or_else(|| anyhow::anyhow!("Plugin stdin is closed"))?;
+        writeln!(stdin, "{}", request)?;
+        stdin.flush()?;
+        Ok(())
+    }
+
+    fn is_running(&mut self) -> bool {
+        matches!(self.child.try_wait(), Ok(None))
+    }
+}
+
+impl Drop for PluginProcess {
+    fn drop(&mut self) {
+        if self.is_running() {
+            let _ = self.child.kill();
+        }
+        let _ = self.child.wait();
+    }
+}
+
+struct LongRunningPlugin {
+    config: OverviewPluginConfig,
+    /// The ID of the newest query for this plugin.
+    latest_request: AtomicU64,
+    /// Started by the first query, and again if it exits.
+    process: Mutex<Option<PluginProcess>>,
+}
+
+impl LongRunningPlugin {
+    fn is_superseded(&self, id: u64) -> bool {
+        self.latest_request.load(Ordering::Relaxed) != id
+    }
+}
+
+// Only held to look plugins up, each plugin has its own lock
+static LONG_RUNNING_PLUGINS: LazyLock<Mutex<HashMap<String, Arc<LongRunningPlugin>>>> = LazyLock::new(|| {
+    Mutex::new(HashMap::new())
+});
+
+// How often a long-running plugin's answer stops being waited on to check for newer queries
+const SUPERSEDED_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
+static NEXT_REQUEST_ID: AtomicU64 = AtomicU64::new(1);
+
+fn query_per_query(config: &OverviewPluginConfig, request: &str) -> anyhow::Result<Vec<PluginResult>> {
+    let mut process = PluginProcess::spawn(config)?;
+    process.send(request)?;
+
+    // Plugins may read stdin until it ends before answering
+    drop(process.stdin.take());
+
+    let deadline = Instant::now() + Duration::from_millis(config.timeout_ms);
+    let mut output = String::new();
+    loop {
+        match process.lines.recv_timeout(deadline.saturating_duration_since(Instant::now())) {
+            Ok(line) => {
+                output.push_str(&line);
+                output.push('\n');
+            },
+
+            Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            Err(mpsc::RecvTimeoutError::Timeout) => anyhow::bail!("Plugin timed out"),
+        }
+    }
+
+    if output.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    Ok(serde_json::from_str::<PluginResponse>(&output)?.results)
+}
+
+fn long_running_plugin(config: &OverviewPluginConfig) -> Arc<LongRunningPlugin> {
+    let mut plugins = LONG_RUNNING_PLUGINS.lock().unwrap();
+
+    // A plugin whose config changed is replaced, and stopped once its last query is done
+    if let Some(plugin) = plugins.get(&config.name)
+        && plugin.config == *config
+    {
+        return plugin.clone();
+    }
+
+    let plugin = Arc::new(LongRunningPlugin {
+        config: config.clone(),
+        latest_request: AtomicU64::new(0),
+        process: Mutex::new(None),
+    });
+
+    plugins.insert(config.name.clone(), plugin.clone());
+    plugin
+}
+
+fn query_long_running(config: &OverviewPluginConfig, id: u64, request: &str) -> anyhow::Result<Vec<PluginResult>> {
+    let plugin = long_running_plugin(config);
+    plugin.latest_request.fetch_max(id, Ordering::Relaxed);
+
+    let mut slot = plugin.process.lock().unwrap();
+
+    // The query was typed past while an older one had the plugin
+    if plugin.is_superseded(id) {
+        return Ok(Vec::new());
+    }
+
+    // (Re)start the plugin if it isn't running
+    let running = slot.take().and_then(|mut process| process.is_running().then_some(process));
+    let process = slot.insert(match running {
+        Some(running) => running,
+        None => PluginProcess::spawn(config)?,
+    });
+
+    // Drop late answers to queries that timed out
+    while process.lines.try_recv().is_ok() {}
+
+    process.send(request)?;
+
+    let deadline = Instant::now() + Duration::from_millis(config.timeout_ms);
+    loop {
+        let remaining = deadline.saturating_duration_since(Instant::now());
+        match process.lines.recv_timeout(remaining.min(SUPERSEDED_CHECK_INTERVAL)) {
+            Ok(line) => match serde_json::from_str::<PluginResponse>(&line) {
+                Ok(response) if response.id.is_none_or(|response_id| response_id == id) => {
+                    return Ok(response.results);
+                },
+
+                Ok(_) => {},
+
+                Err(err) => {
+                    warn!(plugin = %config.name, %err, "Ignoring invalid plugin output");
+                },
+            },
+
+            Err(mpsc::RecvTimeoutError::Disconnected) => {
+                *slot = None;
+                anyhow::bail!("Plugin exited");
+            },
+
+            Err(mpsc::RecvTimeoutError::Timeout) if remaining.is_zero() => anyhow::bail!("Plugin timed out"),
+
+            // Its answer would be dropped anyway, so the newer query can have the plugin
+            Err(mpsc::RecvTimeoutError::Timeout) if plugin.is_superseded(id) => return Ok(Vec::new()),
+
+            Err(mpsc::RecvTimeoutError::Timeout) => {},
+        }
+    }
+}
+
+/// Sends a query to a plugin and waits for its results, up to the plugin's timeout.
+pub fn query(config: &OverviewPluginConfig, query: &str) -> anyhow::Result<Vec<PluginResult>> {
+    let id = NEXT_REQUEST_ID.fetch_add(1, Ordering::Relaxed);
+    let request = serde_json::to_string(&PluginRequest { id, query })?;
+
+    match config.mode {
+        OverviewPluginMode::PerQuery => query_per_query(config, &request),
+        OverviewPluginMode::LongRunning => query_long_running(config, id, &request),
+    }
+}
+
+/// Stops long-running plugins that are no longer declared in the config.
+pub fn prune(configs: &[OverviewPluginConfig]) {
+    LONG_RUNNING_PLUGINS.lock().unwrap().retain(|name, _| configs.iter().any(|config| {
+        config.name == *name && config.mode == OverviewPluginMode::LongRunning
+    }));
+}
@@ -0,0 +1,305 @@
+// SSH hosts for the overview's SSH module, from `~/.ssh/config` (following its `Include`
+// directives), the unhashed entries of `~/.ssh/known_hosts` and the hosts in the config.
+// The files are small, so they're read again on every search. How often each host was
+// connected to is kept in SQLite to rank them.
+
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{LazyLock, RwLock};
+
+use crate::config::read_config;
+use crate::sql::wrappers::ssh;
+use crate::utils::{filesystem::{expand_tilde, get_home_directory}, matching};
+
+// OpenSSH gives up on includes nested deeper than this too
+const MAX_INCLUDE_DEPTH: usize = 16;
+
+static CONNECTIONS: LazyLock<RwLock<HashMap<String, i64>>> = LazyLock::new(|| RwLock::new(HashMap::new()));
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SshHostSource {
+    Config,
+    Custom,
+    KnownHosts,
+}
+
+#[derive(Debug, Clone)]
+pub struct SshHost {
+    /// What's passed to ssh, an alias from the config or a host name.
+    pub name: String,
+    pub hostname: Option<String>,
+    pub user: Option<String>,
+    /// Only set for `known_hosts` entries; the config's aliases already know their port.
+    pub port: Option<u16>,
+    pub source: SshHostSource,
+    pub connections: i64,
+}
+
+impl SshHost {
+    fn new(name: String, source: SshHostSource) -> Self {
+        Self {
+            name,
+            hostname: None,
+            user: None,
+            port: None,
+            source,
+            connections: 0,
+        }
+    }
+
+    /// The key connections are counted under.
+    pub fn key(&self) -> String {
+        match self.port {
+            Some(port) => format!("[{}]:{}", self.name, port),
+            None => self.name.clone(),
+        }
+    }
+
+    pub fn command(&self) -> String {
+        let name = shlex::try_quote(&self.name).map(|name| name.into_owned()).unwrap_or_default();
+        match self.port {
+            Some(port) => format!("ssh -p {} {}", port, name),
+            None => format!("ssh {}", name),
+        }
+    }
+}
+
+pub async fn activate() {
+    match ssh::get_connection_counts().await {
+        Ok(counts) => *CONNECTIONS.write().unwrap() = counts.into_iter().collect(),
+        Err(err) => error!(%err, "Could not get SSH connection counts"),
+    }
+}
+
+fn ssh_directory() -> PathBuf {
+    PathBuf::from(get_home_directory()).join(".ssh")
+}
+
+/// Matches `*` and `?` wildcards, as used by `Include` and `Host`.
+fn wildcard_match(pattern: &[u8], text: &[u8]) -> bool {
+    match (pattern.first(), text.first()) {
+        (None, _) => text.is_empty(),
+        (Some(b'*'), _) => wildcard_match(&pattern[1..], text) || (!text.is_empty() && wildcard_match(pattern, &text[1..])),
+        (Some(b'?'), Some(_)) => wildcard_match(&pattern[1..], &text[1..]),
+        (Some(p), Some(t)) => p == t && wildcard_match(&pattern[1..], &text[1..]),
+        (Some(_), None) => false,
+    }
+}
+
+fn has_wildcards(text: &str) -> bool {
+    text.contains(['*', '?'])
+}
+
+/// Expands the wildcards of an `Include` path. Relative paths are relative to `~/.ssh`.
+fn expand_include(pattern: &str) -> Vec<PathBuf> {
+    let pattern = PathBuf::from(expand_tilde(pattern));
+    let pattern = if pattern.is_relative() { ssh_directory().join(pattern) } else { pattern };
+
+    let mut paths = vec![PathBuf::new()];
+    for component in pattern.components() {
+        let component = component.as_os_str().to_string_lossy();
+        if !has_wildcards(&component) {
+            paths.iter_mut().for_each(|path| path.push(component.as_ref()));
+            continue;
+        }
+
+        paths = paths.iter()
+            .filter_map(|path| std::fs::read_dir(path).ok())
+            .flat_map(|entries| entries.filter_map(Result::ok))
+            .filter(|entry| wildcard_match(component.as_bytes(), entry.file_name().as_encoded_bytes()))
+            .map(|entry| entry.path())
+            .collect();
+    }
+
+    // OpenSSH reads matches in lexical order
+    paths.sort();
+    paths.retain(|path| path.is_file());
+    paths
+}
+
+/// Splits a config line into its keyword and arguments, which can also be separated by `=`.
+fn split_config_line(line: &str) -> Option<(String, Vec<String>)> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return None;
+    }
+
+    let (keyword, rest) = line.split_once(|c: char| c.is_whitespace() || c == '=')?;
+    let rest = rest.trim_start().strip_prefix('=').unwrap_or(rest);
+    let arguments = shlex::split(rest).unwrap_or_else(|| rest.split_whitespace().map(str::to_owned).collect());
+
+    Some((keyword.to_lowercase(), arguments))
+}
+
+fn read_config_file(path: &Path, depth: usize, hosts: &mut Vec<SshHost>) {
+    if depth > MAX_INCLUDE_DEPTH {
+        warn!(path = %path.display(), "SSH config includes are nested too deeply");
+        return;
+    }
+
+    let Ok(contents) = std::fs::read_to_string(path) else {
+        return;
+    };
+
+    // The hosts of the `Host` block being read, as indices into `hosts`
+    let mut block = Vec::<usize>::new();
+
+    for (keyword, arguments) in contents.lines().filter_map(split_config_line) {
+        match keyword.as_str() {
+            "host" => {
+                block.clear();
+
+                // Patterns and negations can't be connected to, only plain aliases
+                for alias in arguments.into_iter().filter(|alias| !has_wildcards(alias) && !alias.starts_with('!')) {
+                    block.push(hosts.len());
+                    hosts.push(SshHost::new(alias, SshHostSource::Config));
+                }
+            },
+
+            "match" => block.clear(),
+
+            "include" => {
+                for included in arguments.iter().flat_map(|pattern| expand_include(pattern)) {
+                    read_config_file(&included, depth + 1, hosts);
+                }
+            },
+
+            // The first value given for a host wins, like in OpenSSH
+            "hostname" | "user" => {
+                let Some(value) = arguments.into_iter().next() else {
+                    continue;
+                };
+
+                for &index in &block {
+                    let host = &mut hosts[index];
+                    let field = if keyword == "hostname" { &mut host.hostname } else { &mut host.user };
+                    field.get_or_insert_with(|| value.clone());
+                }
+            },
+
+            _ => {},
+        }
+    }
+}
+
+/// Reads the host names of `known_hosts`. Hashed entries (`|1|...`) can't be read back.
+fn read_known_hosts(hosts: &mut Vec<SshHost>) {
+    let Ok(contents) = std::fs::read_to_string(ssh_directory().join("known_hosts")) else {
+        return;
+    };
+
+    for line in contents.lines() {
+        let mut fields = line.split_whitespace();
+        let Some(mut names) = fields.next() else {
+            continue;
+        };
+
+        // Markers like `@cert-authority` come before the host names, and revoked keys
+        // aren't worth offering
+        if names.starts_with('@') {
+            match fields.next() {
+                Some(next) if names != "@revoked" => names = next,
+                _ => continue,
+            }
+        }
+
+        if names.starts_with('#') || names.starts_with('|') {
+            continue;
+        }
+
+        for name in names.split(',') {
+            if has_wildcards(name) || name.starts_with('!') {
+                continue;
+            }
+
+            // Hosts on other ports are written as `[host]:port`
+            let host = match name.strip_prefix('[').and_then(|name| name.split_once("]:")) {
+                Some((name, port)) => SshHost {
+                    port: port.parse().ok(),
+                    ..SshHost::new(name.to_owned(), SshHostSource::KnownHosts)
+                },
+
+                None => SshHost::new(name.to_owned(), SshHostSource::KnownHosts),
+            };
+
+            hosts.push(host);
+        }
+    }
+}
+
+/// Every host that can be connected to, without duplicates.
+pub fn hosts() -> Vec<SshHost> {
+    let config = read_config().overview.ssh.clone();
+    let mut found = Vec::new();
+
+    read_config_file(&ssh_directory().join("config"), 0, &mut found);
+    found.extend(config.hosts.into_iter().map(|host| SshHost::new(host, SshHostSource::Custom)));
+    if config.include_known_hosts {
+        read_known_hosts(&mut found);
+    }
+
+    // known_hosts holds the real names of the aliases too, which don't need to be listed twice
+    let aliased = found.iter()
+        .filter_map(|host| host.hostname.clone())
+        .collect::<HashSet<_>>();
+
+    let connections = CONNECTIONS.read().unwrap();
+    let mut seen = HashSet::new();
+
+    found.into_iter()
+        .filter(|host| host.source != SshHostSource::KnownHosts || !aliased.contains(&host.name))
+        .filter(|host| seen.insert(host.key()))
+        .map(|mut host| {
+            host.connections = connections.get(&host.key()).copied().unwrap_or(0);
+            host
+        })
+        .collect()
+}
+
+/// Searches hosts by name, ranking them by how well they match and how often they were
+/// connected to. An empty query returns the most connected to hosts.
+pub fn search(query: &str, limit: usize) -> Vec<SshHost> {
+    let query = query.trim();
+    let hosts = hosts();
+
+    let mut matches = if query.is_empty() {
+        hosts.into_iter()
+            .map(|host| (host.connections as f32, host))
+            .collect::<Vec<_>>()
+    } else {
+        hosts.into_iter()
+            .filter_map(|host| {
+                let name_score = matching::fuzzy_score(&host.name, query).map(|m| m.score as f32);
+                let hostname_score = host.hostname.as_deref()
+                    .and_then(|hostname| matching::fuzzy_score(hostname, query))
+                    .map(|m| m.score as f32 * 0.8);
+
+                let score = name_score.into_iter().chain(hostname_score).reduce(f32::max)?;
+                Some((score * (1.0 + (host.connections as f32).ln_1p() / 5.0), host))
+            })
+            .collect::<Vec<_>>()
+    };
+
+    matches.sort_by(|(a_score, a), (b_score, b)| {
+        b_score.total_cmp(a_score).then_with(|| a.name.cmp(&b.name))
+    });
+
+    matches.into_iter()
+        .take(limit)
+        .map(|(_, host)| host)
+        .collect()
+}
+
+/// Counts a connection to the host with the given `SshHost::key`.
+pub fn record_connection(key: &str) {
+    *CONNECTIONS.write().unwrap().entry(key.to_owned()).or_insert(0) += 1;
+
+    glib::spawn_future_local({
+        let key = key.to_owned();
+        async move {
+            if let Err(err) = ssh::record_connection(&key).await {
+                error!(%err, "Could not save SSH connection");
+            }
+        }
+    });
+}
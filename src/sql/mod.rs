@@ -3,15 +3,39 @@ pub mod wrappers;
 
 use crate::SQL_ACTOR;
 
+/// `CREATE TABLE IF NOT EXISTS` leaves tables from older versions untouched, so new
+/// columns have to be added to them separately.
+fn add_column_if_missing(connection: &rusqlite::Connection, table: &str, column: &str, definition: &str) -> rusqlite::Result<()> {
+    let exists = connection
+        .prepare(&format!("SELECT 1 FROM pragma_table_info('{}') WHERE name = ?1", table))?
+        .exists([column])?;
+
+    if !exists {
+        connection.execute_batch(&format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, definition))?;
+    }
+
+    Ok(())
+}
+
+fn table_exists(connection: &rusqlite::Connection, table: &str) -> rusqlite::Result<bool> {
+    connection
+        .prepare("SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1")?
+        .exists([table])
+}
+
 pub async fn init_database() {
     let result = SQL_ACTOR.with(|connection| {
+        let had_desktop_launches = table_exists(connection, "desktop_launches")?;
+
         // Create tables if they do not exist
         connection.execute_batch("
             CREATE TABLE IF NOT EXISTS state (
                 id INTEGER PRIMARY KEY CHECK (id = 1),
                 source_lang_code TEXT NOT NULL DEFAULT 'en',
                 target_lang_code TEXT NOT NULL DEFAULT 'es',
-                do_not_disturb INTEGER NOT NULL DEFAULT 0
+                do_not_disturb INTEGER NOT NULL DEFAULT 0,
+                emoji_skin_tone TEXT NOT NULL DEFAULT 'default',
+                last_notification_id INTEGER NOT NULL DEFAULT 0
             );
             
             INSERT OR IGNORE INTO state (id) 
@@ -35,6 +59,14 @@ pub async fn init_database() {
                 last_run TIMESTAMP DEFAULT CURRENT_TIMESTAMP
             );
             
+            CREATE TABLE IF NOT EXISTS desktop_launches (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                command TEXT NOT NULL,
+                launched_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            
+            CREATE INDEX IF NOT EXISTS desktop_launches_command ON desktop_launches (command);
+            
             CREATE TABLE IF NOT EXISTS timers (
                 started_at TIMESTAMP NOT NULL,
                 duration INTEGER NOT NULL,
@@ -66,7 +98,11 @@ pub async fn init_database() {
             CREATE TABLE IF NOT EXISTS aichat_conversations (
                 id INTEGER PRIMARY KEY AUTOINCREMENT,
                 title TEXT NOT NULL,
-                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                pinned INTEGER NOT NULL DEFAULT 0,
+                archived INTEGER NOT NULL DEFAULT 0,
+                folder TEXT,
+                tags TEXT NOT NULL DEFAULT '[]'
             );
             
             CREATE TABLE IF NOT EXISTS aichat_items (
@@ -76,7 +112,85 @@ pub async fn init_database() {
                 payload TEXT NOT NULL,
                 FOREIGN KEY(conversation_id) REFERENCES aichat_conversations(id) ON DELETE CASCADE
             );
-        ")
+            
+            CREATE TABLE IF NOT EXISTS aichat_memories (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                content TEXT NOT NULL,
+                created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                updated_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            
+            CREATE TABLE IF NOT EXISTS recent_characters (
+                character TEXT PRIMARY KEY,
+                uses INTEGER NOT NULL DEFAULT 0,
+                last_used TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            
+            CREATE TABLE IF NOT EXISTS calculator_history (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                expression TEXT NOT NULL,
+                result TEXT NOT NULL,
+                timestamp TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+            
+            CREATE TABLE IF NOT EXISTS calculator_variables (
+                name TEXT PRIMARY KEY,
+                expression TEXT NOT NULL
+            );
+            
+            CREATE TABLE IF NOT EXISTS notification_history (
+                id INTEGER PRIMARY KEY,
+                app_name TEXT NOT NULL,
+                app_icon TEXT NOT NULL,
+                summary TEXT NOT NULL,
+                body TEXT NOT NULL,
+                actions TEXT NOT NULL DEFAULT '[]',
+                urgency INTEGER,
+                category TEXT,
+                received_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP,
+                closed_at TIMESTAMP,
+                close_reason INTEGER,
+                action_invoked_at TIMESTAMP,
+                invoked_action TEXT,
+                rule TEXT,
+                silent INTEGER NOT NULL DEFAULT 0,
+                dropped INTEGER NOT NULL DEFAULT 0
+            );
+            
+            CREATE INDEX IF NOT EXISTS notification_history_received_at ON notification_history (received_at);
+            
+            CREATE TABLE IF NOT EXISTS ssh_connections (
+                host TEXT PRIMARY KEY,
+                connections INTEGER NOT NULL DEFAULT 0,
+                last_connected TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP
+            );
+        ")?;
+
+        // Columns added after a table was first released
+        add_column_if_missing(connection, "aichat_conversations", "pinned", "INTEGER NOT NULL DEFAULT 0")?;
+        add_column_if_missing(connection, "aichat_conversations", "archived", "INTEGER NOT NULL DEFAULT 0")?;
+        add_column_if_missing(connection, "aichat_conversations", "folder", "TEXT")?;
+        add_column_if_missing(connection, "aichat_conversations", "tags", "TEXT NOT NULL DEFAULT '[]'")?;
+        add_column_if_missing(connection, "state", "emoji_skin_tone", "TEXT NOT NULL DEFAULT 'default'")?;
+        add_column_if_missing(connection, "state", "last_notification_id", "INTEGER NOT NULL DEFAULT 0")?;
+        add_column_if_missing(connection, "notification_history", "rule", "TEXT")?;
+        add_column_if_missing(connection, "notification_history", "silent", "INTEGER NOT NULL DEFAULT 0")?;
+        add_column_if_missing(connection, "notification_history", "dropped", "INTEGER NOT NULL DEFAULT 0")?;
+
+        // Older versions only kept a total per command, so their runs (up to the 50 launches
+        // kept per command) are carried over as launches at the last run, which decay like
+        // any other launch
+        if !had_desktop_launches {
+            connection.execute_batch("
+                WITH RECURSIVE launch(n) AS (SELECT 1 UNION ALL SELECT n + 1 FROM launch WHERE n < 50)
+                INSERT INTO desktop_launches (command, launched_at)
+                SELECT desktop_runs.command, desktop_runs.last_run
+                FROM desktop_runs JOIN launch ON launch.n <= desktop_runs.runs
+                WHERE desktop_runs.last_run IS NOT NULL;
+            ")?;
+        }
+
+        Ok::<_, rusqlite::Error>(())
     }).await
         .expect("Failed to initialize database");
     
@@ -89,15 +89,6 @@ pub async fn add_conversation(title: &str) -> anyhow::Result<i64> {
     }).await?
 }
 
-/// Deletes a conversation and all its associated items.
-pub async fn delete_conversation(conversation_id: i64) -> anyhow::Result<()> {
-    SQL_ACTOR.with(move |connection| {
-        connection.execute("DELETE FROM aichat_conversations WHERE id = ?1", [conversation_id])?;
-        connection.execute("DELETE FROM aichat_items WHERE conversation_id = ?1", [conversation_id])?;
-        Ok(())
-    }).await?
-}
-
 /// Renames an existing AI chat conversation.
 pub async fn rename_conversation(conversation_id: i64, new_title: &str) -> anyhow::Result<()> {
     SQL_ACTOR.with({
@@ -112,31 +103,114 @@ pub async fn rename_conversation(conversation_id: i64, new_title: &str) -> anyho
     }).await?
 }
 
+// The last activity falls back to the creation time for conversations without items
+const CONVERSATION_COLUMNS: &str = "id, title, pinned, archived, folder, tags, \
+    COALESCE((SELECT MAX(timestamp) FROM aichat_items WHERE conversation_id = aichat_conversations.id), created_at) AS last_activity";
+
+fn conversation_from_row(row: &rusqlite::Row) -> rusqlite::Result<AiConversation> {
+    Ok(AiConversation {
+        id: row.get(0)?,
+        title: row.get(1)?,
+        pinned: row.get(2)?,
+        archived: row.get(3)?,
+        folder: row.get(4)?,
+        tags: serde_json::from_str(&row.get::<_, String>(5)?)
+            .map_err(|e| rusqlite::Error::FromSqlConversionFailure(5, rusqlite::types::Type::Text, Box::new(e)))?,
+        last_activity: row.get(6)?,
+    })
+}
+
 /// Retrieves information about an AI chat conversation by its ID.
 pub async fn get_conversation(conversation_id: i64) -> anyhow::Result<AiConversation> {
     SQL_ACTOR.with(move |connection| {
         connection.query_row(
-            "SELECT id, title FROM aichat_conversations WHERE id = ?1", [conversation_id],
-            |row| Ok(AiConversation {
-                id: row.get(0)?,
-                title: row.get(1)?
-            })
+            &format!("SELECT {} FROM aichat_conversations WHERE id = ?1", CONVERSATION_COLUMNS),
+            [conversation_id],
+            conversation_from_row
         ).map_err(|e| e.into())
     }).await?
 }
 
-/// Retrieves all AI chat conversations.
+/// Retrieves all AI chat conversations, archived ones included. Pinned conversations
+/// come first, then the rest by last activity.
 pub async fn get_all_conversations() -> anyhow::Result<Vec<AiConversation>> {
     SQL_ACTOR.with(|connection| {
-        let mut statement = connection.prepare("SELECT id, title FROM aichat_conversations ORDER BY created_at ASC")?;
-        let conversations = statement.query_map([], |row| Ok(AiConversation {
-            id: row.get(0)?,
-            title: row.get(1)?
-        }))?.collect::<Result<Vec<_>, _>>()?;
+        let mut statement = connection.prepare(&format!(
+            "SELECT {} FROM aichat_conversations ORDER BY pinned DESC, last_activity DESC, id DESC",
+            CONVERSATION_COLUMNS
+        ))?;
+        let conversations = statement.query_map([], conversation_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(conversations)
     }).await?
 }
 
+/// Pins or unpins an AI chat conversation.
+pub async fn set_conversation_pinned(conversation_id: i64, pinned: bool) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        connection.execute(
+            "UPDATE aichat_conversations SET pinned = ?1 WHERE id = ?2",
+            (pinned, conversation_id)
+        )?;
+        Ok(())
+    }).await?
+}
+
+/// Archives or restores an AI chat conversation.
+pub async fn set_conversation_archived(conversation_id: i64, archived: bool) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        connection.execute(
+            "UPDATE aichat_conversations SET archived = ?1 WHERE id = ?2",
+            (archived, conversation_id)
+        )?;
+        Ok(())
+    }).await?
+}
+
+/// Moves an AI chat conversation into a folder, or out of any folder with `None`.
+pub async fn set_conversation_folder(conversation_id: i64, folder: Option<&str>) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let folder = folder.map(str::to_owned);
+        move |connection| {
+            connection.execute(
+                "UPDATE aichat_conversations SET folder = ?1 WHERE id = ?2",
+                (folder, conversation_id)
+            )?;
+            Ok(())
+        }
+    }).await?
+}
+
+/// Replaces the tags of an AI chat conversation.
+pub async fn set_conversation_tags(conversation_id: i64, tags: &[String]) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let tags = serde_json::to_string(tags)?;
+        move |connection| {
+            connection.execute(
+                "UPDATE aichat_conversations SET tags = ?1 WHERE id = ?2",
+                (tags, conversation_id)
+            )?;
+            Ok(())
+        }
+    }).await?
+}
+
+/// Deletes several conversations and all their associated items at once.
+pub async fn delete_conversations(conversation_ids: &[i64]) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let conversation_ids = conversation_ids.to_vec();
+        move |connection| {
+            let transaction = connection.transaction()?;
+            for conversation_id in conversation_ids {
+                transaction.execute("DELETE FROM aichat_conversations WHERE id = ?1", [conversation_id])?;
+                transaction.execute("DELETE FROM aichat_items WHERE conversation_id = ?1", [conversation_id])?;
+            }
+            transaction.commit()?;
+            Ok(())
+        }
+    }).await?
+}
+
 /// Retrieves items for the specified AI chat conversation.
 pub async fn get_items(conversation_id: i64) -> anyhow::Result<Vec<AiConversationItem>> {
     SQL_ACTOR.with(move |connection| {
@@ -172,6 +246,16 @@ pub async fn get_all_image_item_uuids() -> anyhow::Result<Vec<String>> {
     }).await?
 }
 
+/// Gets every single file item UUID that's stored in AI chat conversations.
+pub async fn get_all_file_item_uuids() -> anyhow::Result<Vec<String>> {
+    SQL_ACTOR.with(|connection| {
+        let mut statement = connection.prepare("SELECT json_extract(payload, '$.uuid') FROM aichat_items WHERE json_extract(payload, '$.type') = 'file'")?;
+        let uuids = statement.query_map([], |row| row.get::<_, String>(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(uuids)
+    }).await?
+}
+
 /// Gets the length of user & assistant messages in a conversation.
 pub async fn get_messages_length(conversation_id: i64) -> anyhow::Result<usize> {
     Ok(get_items(conversation_id).await?
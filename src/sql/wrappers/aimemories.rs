@@ -0,0 +1,51 @@
+use crate::SQL_ACTOR;
+use crate::services::ai::types::AiMemory;
+
+/// Fetches all stored memories, most recently updated first.
+pub async fn get_all_memories() -> anyhow::Result<Vec<AiMemory>> {
+    SQL_ACTOR.with(|connection| {
+        let mut statement = connection.prepare("SELECT id, content, updated_at FROM aichat_memories ORDER BY updated_at DESC, id DESC")?;
+        let memories = statement.query_map([], |row| Ok(AiMemory {
+            id: row.get(0)?,
+            content: row.get(1)?,
+            updated_at: row.get(2)?,
+        }))?.collect::<Result<Vec<_>, _>>()?;
+        Ok(memories)
+    }).await?
+}
+
+/// Stores a new memory and returns its ID.
+pub async fn add_memory(content: &str) -> anyhow::Result<i64> {
+    SQL_ACTOR.with({
+        let content = content.to_owned();
+        move |connection| {
+            connection.execute(
+                "INSERT INTO aichat_memories (content) VALUES (?1)",
+                [content],
+            )?;
+            Ok(connection.last_insert_rowid())
+        }
+    }).await?
+}
+
+/// Replaces the content of an existing memory.
+pub async fn update_memory(memory_id: i64, content: &str) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let content = content.to_owned();
+        move |connection| {
+            connection.execute(
+                "UPDATE aichat_memories SET content = ?1, updated_at = CURRENT_TIMESTAMP WHERE id = ?2",
+                (content, memory_id),
+            )?;
+            Ok(())
+        }
+    }).await?
+}
+
+/// Deletes a memory, returning whether it existed.
+pub async fn delete_memory(memory_id: i64) -> anyhow::Result<bool> {
+    SQL_ACTOR.with(move |connection| {
+        let deleted = connection.execute("DELETE FROM aichat_memories WHERE id = ?1", [memory_id])?;
+        Ok(deleted > 0)
+    }).await?
+}
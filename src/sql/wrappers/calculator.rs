@@ -0,0 +1,61 @@
+use crate::SQL_ACTOR;
+
+#[derive(Debug, Clone)]
+pub struct CalculatorHistoryEntry {
+    pub expression: String,
+    pub result: String,
+}
+
+/// Fetches the most recent calculations, most recent first.
+pub async fn get_history(limit: usize) -> anyhow::Result<Vec<CalculatorHistoryEntry>> {
+    SQL_ACTOR.with(move |connection| {
+        let mut statement = connection.prepare("SELECT expression, result FROM calculator_history ORDER BY id DESC LIMIT ?1")?;
+        let entries = statement.query_map([limit as i64], |row| Ok(CalculatorHistoryEntry {
+            expression: row.get(0)?,
+            result: row.get(1)?,
+        }))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }).await?
+}
+
+/// Adds a calculation to the history, keeping only the most recent `limit` of them.
+pub async fn add_history_entry(entry: CalculatorHistoryEntry, limit: usize) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        connection.execute(
+            "INSERT INTO calculator_history (expression, result) VALUES (?1, ?2)",
+            [&entry.expression, &entry.result]
+        )?;
+
+        connection.execute(
+            "DELETE FROM calculator_history WHERE id NOT IN (SELECT id FROM calculator_history ORDER BY id DESC LIMIT ?1)",
+            [limit as i64]
+        )?;
+
+        Ok(())
+    }).await?
+}
+
+pub async fn get_variables() -> anyhow::Result<Vec<(String, String)>> {
+    SQL_ACTOR.with(|connection| {
+        let mut statement = connection.prepare("SELECT name, expression FROM calculator_variables")?;
+        let variables = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(variables)
+    }).await?
+}
+
+pub async fn set_variable(name: &str, expression: &str) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let name = name.to_owned();
+        let expression = expression.to_owned();
+        move |connection| {
+            connection.execute(
+                "INSERT INTO calculator_variables (name, expression) VALUES (?1, ?2)
+                 ON CONFLICT(name) DO UPDATE SET expression = excluded.expression",
+                [name, expression]
+            )?;
+            Ok(())
+        }
+    }).await?
+}
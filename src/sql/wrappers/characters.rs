@@ -0,0 +1,28 @@
+use crate::SQL_ACTOR;
+
+/// Fetches the most recently picked characters, most recent first.
+pub async fn get_recent_characters(limit: usize) -> anyhow::Result<Vec<String>> {
+    SQL_ACTOR.with(move |connection| {
+        let mut statement = connection.prepare("SELECT character FROM recent_characters ORDER BY last_used DESC LIMIT ?1")?;
+        let characters = statement.query_map([limit as i64], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(characters)
+    }).await?
+}
+
+/// Records that a character was picked, inserting it if it wasn't picked before.
+pub async fn record_character_use(character: &str) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let character = character.to_owned();
+        move |connection| {
+            connection.execute(
+                "INSERT INTO recent_characters (character, uses) VALUES (?1, 1)
+                 ON CONFLICT(character) DO UPDATE SET
+                    uses = uses + 1,
+                    last_used = datetime('subsec')",
+                [character],
+            )?;
+            Ok(())
+        }
+    }).await?
+}
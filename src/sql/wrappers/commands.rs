@@ -22,8 +22,19 @@ pub async fn get_all_runs() -> anyhow::Result<Vec<DesktopRunsEntry>> {
     }).await?
 }
 
-/// Increments the run count for a given command, or inserts it if it doesn't exist.
-pub async fn increment_runs(command: &str) -> anyhow::Result<()> {
+/// Fetches the recorded launches of all commands, as (command, launch time).
+pub async fn get_all_launches() -> anyhow::Result<Vec<(String, chrono::DateTime<chrono::Local>)>> {
+    SQL_ACTOR.with(|connection| {
+        let mut statement = connection.prepare("SELECT command, launched_at FROM desktop_launches ORDER BY id")?;
+        let launches = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(launches)
+    }).await?
+}
+
+/// Increments the run count for a given command, or inserts it if it doesn't exist, and
+/// records the launch, keeping only the most recent `launch_limit` launches of the command.
+pub async fn increment_runs(command: &str, launch_limit: usize) -> anyhow::Result<()> {
     SQL_ACTOR.with({
         let command = command.to_owned();
         move |connection| {
@@ -33,9 +44,35 @@ pub async fn increment_runs(command: &str) -> anyhow::Result<()> {
                     command = excluded.command,
                     runs = runs + 1, 
                     last_run = datetime('subsec')",
-                [command],
+                [&command],
+            )?;
+
+            connection.execute(
+                "INSERT INTO desktop_launches (command, launched_at) VALUES (?1, datetime('subsec'))",
+                [&command],
+            )?;
+
+            connection.execute(
+                "DELETE FROM desktop_launches WHERE command = ?1 AND id NOT IN
+                    (SELECT id FROM desktop_launches WHERE command = ?1 ORDER BY id DESC LIMIT ?2)",
+                rusqlite::params![command, launch_limit as i64],
             )?;
+
             Ok(())
         }
     }).await?
+}
+
+/// Forgets the runs and launches of the given commands.
+pub async fn delete_runs(commands: Vec<String>) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        let transaction = connection.transaction()?;
+        for command in &commands {
+            transaction.execute("DELETE FROM desktop_runs WHERE command = ?1", [command])?;
+            transaction.execute("DELETE FROM desktop_launches WHERE command = ?1", [command])?;
+        }
+
+        transaction.commit()?;
+        Ok(())
+    }).await?
 }
\ No newline at end of file
@@ -1,5 +1,10 @@
 pub mod state;
 pub mod commands;
 pub mod aichats;
+pub mod aimemories;
 pub mod weather;
-pub mod translate;
\ No newline at end of file
+pub mod translate;
+pub mod characters;
+pub mod calculator;
+pub mod ssh;
+pub mod notifications;
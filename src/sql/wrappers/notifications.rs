@@ -0,0 +1,179 @@
+use crate::SQL_ACTOR;
+
+#[derive(Debug, Clone)]
+pub struct NotificationHistoryEntry {
+    pub id: u32,
+    pub app_name: String,
+    pub app_icon: String,
+    pub summary: String,
+    pub body: String,
+    /// (action key, localized name)
+    pub actions: Vec<(String, String)>,
+    pub urgency: Option<u8>,
+    pub category: Option<String>,
+    // Times are stored in UTC, so they sort and compare with SQLite's own times
+    pub received_at: chrono::DateTime<chrono::Local>,
+    pub closed_at: Option<chrono::DateTime<chrono::Local>>,
+    pub close_reason: Option<u32>,
+    pub action_invoked_at: Option<chrono::DateTime<chrono::Local>>,
+    pub invoked_action: Option<String>,
+    /// The notification rule that matched it, if any.
+    pub rule: Option<String>,
+    /// Whether a rule kept it out of sight, so it only ever went to the history.
+    pub silent: bool,
+    /// Whether a rule dropped it, so it's left out of searches unless they ask for it.
+    pub dropped: bool,
+}
+
+fn entry_from_row(row: &rusqlite::Row) -> rusqlite::Result<NotificationHistoryEntry> {
+    let actions = serde_json::from_str(&row.get::<_, String>(5)?).unwrap_or_default();
+
+    Ok(NotificationHistoryEntry {
+        id: row.get(0)?,
+        app_name: row.get(1)?,
+        app_icon: row.get(2)?,
+        summary: row.get(3)?,
+        body: row.get(4)?,
+        actions,
+        urgency: row.get(6)?,
+        category: row.get(7)?,
+        received_at: row.get(8)?,
+        closed_at: row.get(9)?,
+        close_reason: row.get(10)?,
+        action_invoked_at: row.get(11)?,
+        invoked_action: row.get(12)?,
+        rule: row.get(13)?,
+        silent: row.get(14)?,
+        dropped: row.get(15)?,
+    })
+}
+
+/// Adds a notification to the history. A notification that replaces one with the same ID
+/// takes over its content, but keeps when it was first received.
+pub async fn upsert_notification(entry: NotificationHistoryEntry) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        connection.execute(
+            "INSERT INTO notification_history (id, app_name, app_icon, summary, body, actions, urgency, category, received_at, rule, silent, dropped)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+             ON CONFLICT(id) DO UPDATE SET
+                app_name = excluded.app_name,
+                app_icon = excluded.app_icon,
+                summary = excluded.summary,
+                body = excluded.body,
+                actions = excluded.actions,
+                urgency = excluded.urgency,
+                category = excluded.category,
+                rule = excluded.rule,
+                silent = excluded.silent,
+                dropped = excluded.dropped,
+                closed_at = NULL,
+                close_reason = NULL",
+            rusqlite::params![
+                entry.id,
+                entry.app_name,
+                entry.app_icon,
+                entry.summary,
+                entry.body,
+                serde_json::to_string(&entry.actions)?,
+                entry.urgency,
+                entry.category,
+                entry.received_at.to_utc(),
+                entry.rule,
+                entry.silent,
+                entry.dropped,
+            ],
+        )?;
+        Ok(())
+    }).await?
+}
+
+pub async fn set_closed(id: u32, reason: u32, closed_at: chrono::DateTime<chrono::Local>) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        connection.execute(
+            "UPDATE notification_history SET closed_at = ?2, close_reason = ?3 WHERE id = ?1 AND closed_at IS NULL",
+            rusqlite::params![id, closed_at.to_utc(), reason],
+        )?;
+        Ok(())
+    }).await?
+}
+
+pub async fn set_action_invoked(id: u32, action: &str, invoked_at: chrono::DateTime<chrono::Local>) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let action = action.to_owned();
+        move |connection| {
+            connection.execute(
+                "UPDATE notification_history SET action_invoked_at = ?2, invoked_action = ?3 WHERE id = ?1",
+                rusqlite::params![id, invoked_at.to_utc(), action],
+            )?;
+            Ok(())
+        }
+    }).await?
+}
+
+/// Searches the summaries, bodies and app names of the history, most recent first. `app`
+/// limits the search to one app's notifications.
+pub async fn search(query: &str, app: Option<String>, include_dropped: bool, limit: usize) -> anyhow::Result<Vec<NotificationHistoryEntry>> {
+    let pattern = format!("%{}%", query.replace('\\', "\\\\").replace('%', "\\%").replace('_', "\\_"));
+
+    SQL_ACTOR.with(move |connection| {
+        let mut statement = connection.prepare(
+            "SELECT id, app_name, app_icon, summary, body, actions, urgency, category, received_at,
+                    closed_at, close_reason, action_invoked_at, invoked_action, rule, silent, dropped
+             FROM notification_history
+             WHERE (summary LIKE ?1 ESCAPE '\\' OR body LIKE ?1 ESCAPE '\\' OR app_name LIKE ?1 ESCAPE '\\')
+                AND (?2 IS NULL OR app_name = ?2)
+                AND (?3 OR dropped = 0)
+             ORDER BY received_at DESC, id DESC
+             LIMIT ?4"
+        )?;
+
+        let entries = statement.query_map(rusqlite::params![pattern, app, include_dropped, limit as i64], entry_from_row)?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(entries)
+    }).await?
+}
+
+/// The names of every app in the history.
+pub async fn get_apps() -> anyhow::Result<Vec<String>> {
+    SQL_ACTOR.with(|connection| {
+        let mut statement = connection.prepare("SELECT DISTINCT app_name FROM notification_history ORDER BY app_name COLLATE NOCASE")?;
+        let apps = statement.query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<String>, _>>()?;
+        Ok(apps)
+    }).await?
+}
+
+pub async fn get_max_id() -> anyhow::Result<u32> {
+    SQL_ACTOR.with(|connection| {
+        let id: Option<u32> = connection.query_row("SELECT MAX(id) FROM notification_history", [], |row| row.get(0))?;
+        Ok(id.unwrap_or(0))
+    }).await?
+}
+
+/// Removes notifications beyond the most recent `max_entries`, and those received more than
+/// `max_age_days` ago unless it's 0.
+pub async fn prune(max_entries: usize, max_age_days: u64) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        connection.execute(
+            "DELETE FROM notification_history WHERE id NOT IN
+                (SELECT id FROM notification_history ORDER BY received_at DESC, id DESC LIMIT ?1)",
+            [max_entries as i64],
+        )?;
+
+        if max_age_days > 0 {
+            connection.execute(
+                "DELETE FROM notification_history WHERE received_at < datetime('now', ?1)",
+                [format!("-{} days", max_age_days)],
+            )?;
+        }
+
+        Ok(())
+    }).await?
+}
+
+pub async fn clear() -> anyhow::Result<()> {
+    SQL_ACTOR.with(|connection| {
+        connection.execute("DELETE FROM notification_history", [])?;
+        Ok(())
+    }).await?
+}
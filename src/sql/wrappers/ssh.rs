@@ -0,0 +1,28 @@
+use crate::SQL_ACTOR;
+
+/// Fetches how many times each host was connected to.
+pub async fn get_connection_counts() -> anyhow::Result<Vec<(String, i64)>> {
+    SQL_ACTOR.with(|connection| {
+        let mut statement = connection.prepare("SELECT host, connections FROM ssh_connections")?;
+        let counts = statement.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(counts)
+    }).await?
+}
+
+/// Records a connection to a host, inserting it if it wasn't connected to before.
+pub async fn record_connection(host: &str) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let host = host.to_owned();
+        move |connection| {
+            connection.execute(
+                "INSERT INTO ssh_connections (host, connections) VALUES (?1, 1)
+                 ON CONFLICT(host) DO UPDATE SET
+                    connections = connections + 1,
+                    last_connected = datetime('subsec')",
+                [host],
+            )?;
+            Ok(())
+        }
+    }).await?
+}
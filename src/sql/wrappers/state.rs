@@ -14,6 +14,38 @@ pub async fn set_do_not_disturb(dnd: bool) -> anyhow::Result<()> {
     }).await?
 }
 
+pub async fn get_emoji_skin_tone() -> anyhow::Result<String> {
+    SQL_ACTOR.with(|connection| {
+        let row: String = connection.query_row("SELECT emoji_skin_tone FROM state WHERE id = 1", [], |row| row.get(0))?;
+        Ok(row)
+    }).await?
+}
+
+pub async fn set_emoji_skin_tone(skin_tone: &str) -> anyhow::Result<()> {
+    SQL_ACTOR.with({
+        let skin_tone = skin_tone.to_owned();
+        move |connection| {
+            connection.execute("UPDATE state SET emoji_skin_tone = ?1 WHERE id = 1", [skin_tone])?;
+            Ok(())
+        }
+    }).await?
+}
+
+pub async fn get_last_notification_id() -> anyhow::Result<u32> {
+    SQL_ACTOR.with(|connection| {
+        let row: u32 = connection.query_row("SELECT last_notification_id FROM state WHERE id = 1", [], |row| row.get(0))?;
+        Ok(row)
+    }).await?
+}
+
+/// Raises the last notification ID. It never goes down, so IDs aren't reused.
+pub async fn set_last_notification_id(id: u32) -> anyhow::Result<()> {
+    SQL_ACTOR.with(move |connection| {
+        connection.execute("UPDATE state SET last_notification_id = MAX(last_notification_id, ?1) WHERE id = 1", [id])?;
+        Ok(())
+    }).await?
+}
+
 pub async fn get_source_language() -> anyhow::Result<String> {
     SQL_ACTOR.with(|connection| {
         let row: String = connection.query_row("SELECT source_lang_code FROM state WHERE id = 1", [], |row| row.get(0))?;
@@ -3,6 +3,17 @@ pub fn get_home_directory() -> String {
         .expect("HOME environment variable not set")
 }
 
+/// Expands a leading `~` in a path to the home directory.
+pub fn expand_tilde(path: &str) -> String {
+    if path == "~" {
+        get_home_directory()
+    } else if let Some(rest) = path.strip_prefix("~/") {
+        format!("{}/{}", get_home_directory(), rest)
+    } else {
+        path.to_owned()
+    }
+}
+
 pub fn get_config_directory() -> String {
     let home = get_home_directory();
     format!("{}/.config/gray-meadows", home)
@@ -20,6 +31,10 @@ pub fn get_local_state_directory() -> String {
     format!("{}/.local/state/gray-meadows", get_home_directory())
 }
 
+pub fn get_cache_directory() -> String {
+    format!("{}/.cache/gray-meadows", get_home_directory())
+}
+
 pub fn get_xdg_runtime_directory() -> String {
     std::env::var("XDG_RUNTIME_DIR").unwrap_or_else(|_| {
         format!("{}/.local/run", get_home_directory())
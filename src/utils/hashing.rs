@@ -0,0 +1,159 @@
+use std::fmt::Write as _;
+use hmac::Hmac;
+use sha2::Digest as _;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Algorithm {
+    Md5,
+    Sha1,
+    Sha224,
+    Sha256,
+    Sha384,
+    Sha512,
+    Sha3_256,
+    Sha3_512,
+    Blake3,
+    Whirlpool,
+    Crc32,
+    Xxh64,
+    Xxh3
+}
+
+impl Algorithm {
+    pub fn all() -> Vec<Algorithm> {
+        use self::Algorithm::*;
+
+        vec![Md5, Sha1, Sha224, Sha256, Sha384, Sha512, Sha3_256, Sha3_512, Blake3, Whirlpool, Crc32, Xxh64, Xxh3]
+    }
+
+    /// The algorithms that can be used for HMAC.
+    pub fn hmac() -> Vec<Algorithm> {
+        use self::Algorithm::*;
+
+        vec![Sha1, Sha256, Sha384, Sha512, Sha3_256, Sha3_512]
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Algorithm::Md5 => "MD5",
+            Algorithm::Sha1 => "SHA-1",
+            Algorithm::Sha224 => "SHA-224",
+            Algorithm::Sha256 => "SHA-256",
+            Algorithm::Sha384 => "SHA-384",
+            Algorithm::Sha512 => "SHA-512",
+            Algorithm::Sha3_256 => "SHA3-256",
+            Algorithm::Sha3_512 => "SHA3-512",
+            Algorithm::Blake3 => "BLAKE3",
+            Algorithm::Whirlpool => "Whirlpool",
+            Algorithm::Crc32 => "CRC32",
+            Algorithm::Xxh64 => "xxHash64",
+            Algorithm::Xxh3 => "XXH3-64"
+        }
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut acc, b| {
+        write!(&mut acc, "{:02x}", b).unwrap();
+        acc
+    })
+}
+
+/// A digest that's fed its input in chunks, so files don't have to be read into memory.
+pub enum Hasher {
+    Md5(md5::Context),
+    Sha1(sha1::Sha1),
+    Sha224(sha2::Sha224),
+    Sha256(sha2::Sha256),
+    Sha384(sha2::Sha384),
+    Sha512(sha2::Sha512),
+    Sha3_256(sha3::Sha3_256),
+    Sha3_512(sha3::Sha3_512),
+    Blake3(Box<blake3::Hasher>),
+    Whirlpool(Box<whirlpool::Whirlpool>),
+    Crc32(crc32fast::Hasher),
+    Xxh64(xxhash_rust::xxh64::Xxh64),
+    Xxh3(Box<xxhash_rust::xxh3::Xxh3>)
+}
+
+impl Hasher {
+    pub fn new(algorithm: Algorithm) -> Self {
+        match algorithm {
+            Algorithm::Md5 => Hasher::Md5(md5::Context::new()),
+            Algorithm::Sha1 => Hasher::Sha1(sha1::Sha1::new()),
+            Algorithm::Sha224 => Hasher::Sha224(sha2::Sha224::new()),
+            Algorithm::Sha256 => Hasher::Sha256(sha2::Sha256::new()),
+            Algorithm::Sha384 => Hasher::Sha384(sha2::Sha384::new()),
+            Algorithm::Sha512 => Hasher::Sha512(sha2::Sha512::new()),
+            Algorithm::Sha3_256 => Hasher::Sha3_256(sha3::Sha3_256::new()),
+            Algorithm::Sha3_512 => Hasher::Sha3_512(sha3::Sha3_512::new()),
+            Algorithm::Blake3 => Hasher::Blake3(Box::new(blake3::Hasher::new())),
+            Algorithm::Whirlpool => Hasher::Whirlpool(Box::new(whirlpool::Whirlpool::new())),
+            Algorithm::Crc32 => Hasher::Crc32(crc32fast::Hasher::new()),
+            Algorithm::Xxh64 => Hasher::Xxh64(xxhash_rust::xxh64::Xxh64::new(0)),
+            Algorithm::Xxh3 => Hasher::Xxh3(Box::new(xxhash_rust::xxh3::Xxh3::new()))
+        }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        match self {
+            Hasher::Md5(context) => context.consume(data),
+            Hasher::Sha1(hasher) => hasher.update(data),
+            Hasher::Sha224(hasher) => hasher.update(data),
+            Hasher::Sha256(hasher) => hasher.update(data),
+            Hasher::Sha384(hasher) => hasher.update(data),
+            Hasher::Sha512(hasher) => hasher.update(data),
+            Hasher::Sha3_256(hasher) => hasher.update(data),
+            Hasher::Sha3_512(hasher) => hasher.update(data),
+            Hasher::Blake3(hasher) => { hasher.update(data); },
+            Hasher::Whirlpool(hasher) => hasher.update(data),
+            Hasher::Crc32(hasher) => hasher.update(data),
+            Hasher::Xxh64(hasher) => hasher.update(data),
+            Hasher::Xxh3(hasher) => hasher.update(data)
+        }
+    }
+
+    /// The digest as lowercase hex.
+    pub fn finalize(self) -> String {
+        match self {
+            Hasher::Md5(context) => format!("{:x}", context.finalize()),
+            Hasher::Sha1(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Sha224(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Sha256(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Sha384(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Sha512(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Sha3_256(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Sha3_512(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Blake3(hasher) => hasher.finalize().to_hex().to_string(),
+            Hasher::Whirlpool(hasher) => to_hex(&hasher.finalize()),
+            Hasher::Crc32(hasher) => format!("{:08x}", hasher.finalize()),
+            Hasher::Xxh64(hasher) => format!("{:016x}", hasher.digest()),
+            Hasher::Xxh3(hasher) => format!("{:016x}", hasher.digest())
+        }
+    }
+}
+
+pub fn digest(input: &[u8], algorithm: Algorithm) -> String {
+    let mut hasher = Hasher::new(algorithm);
+    hasher.update(input);
+    hasher.finalize()
+}
+
+/// HMAC of the message with the key, or None if the algorithm isn't one of `Algorithm::hmac`.
+pub fn hmac(key: &[u8], message: &[u8], algorithm: Algorithm) -> Option<String> {
+    fn compute<D: hmac::Mac + hmac::digest::KeyInit>(key: &[u8], message: &[u8]) -> String {
+        let mut mac = <D as hmac::digest::KeyInit>::new_from_slice(key).expect("HMAC accepts keys of any length");
+        mac.update(message);
+        to_hex(&mac.finalize().into_bytes())
+    }
+
+    Some(match algorithm {
+        Algorithm::Sha1 => compute::<Hmac<sha1::Sha1>>(key, message),
+        Algorithm::Sha256 => compute::<Hmac<sha2::Sha256>>(key, message),
+        Algorithm::Sha384 => compute::<Hmac<sha2::Sha384>>(key, message),
+        Algorithm::Sha512 => compute::<Hmac<sha2::Sha512>>(key, message),
+        Algorithm::Sha3_256 => compute::<Hmac<sha3::Sha3_256>>(key, message),
+        Algorithm::Sha3_512 => compute::<Hmac<sha3::Sha3_512>>(key, message),
+        _ => return None
+    })
+}
@@ -1,38 +1,260 @@
-pub fn lazy_match(haystack: &str, needle: &str) -> bool {
-    let mut needle_chars: Vec<char> = needle.chars().collect();
-    needle_chars.dedup();
+// An fzf-style fuzzy matcher. Every character of the pattern has to appear in the text in
+// order, and out of all the ways to place them, the best scoring one is found with a
+// Smith-Waterman style dynamic program: matches at word boundaries and runs of consecutive
+// matches score higher, while gaps between matched characters cost a little.
 
-    for c in haystack.chars() {
-        if let Some(index) = needle_chars.iter().position(|&x| x == c) {
-            needle_chars.remove(index);
-        }
+const SCORE_MATCH: i32 = 16;
+const SCORE_GAP_START: i32 = -3;
+const SCORE_GAP_EXTENSION: i32 = -1;
+
+const BONUS_BOUNDARY: i32 = SCORE_MATCH / 2;
+const BONUS_BOUNDARY_WHITE: i32 = BONUS_BOUNDARY + 2;
+const BONUS_BOUNDARY_DELIMITER: i32 = BONUS_BOUNDARY + 1;
+const BONUS_NON_WORD: i32 = SCORE_MATCH / 2;
+const BONUS_CAMEL_123: i32 = BONUS_BOUNDARY + SCORE_GAP_EXTENSION;
+const BONUS_CONSECUTIVE: i32 = -(SCORE_GAP_START + SCORE_GAP_EXTENSION);
+const BONUS_FIRST_CHAR_MULTIPLIER: i32 = 2;
+
+const NO_MATCH: i32 = i32::MIN / 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    White,
+    Delimiter,
+    NonWord,
+    Lower,
+    Upper,
+    Number,
+    Letter
+}
+
+fn char_class(c: char) -> CharClass {
+    if c.is_whitespace() {
+        CharClass::White
+    } else if matches!(c, '/' | ',' | ':' | ';' | '|') {
+        CharClass::Delimiter
+    } else if c.is_lowercase() {
+        CharClass::Lower
+    } else if c.is_uppercase() {
+        CharClass::Upper
+    } else if c.is_numeric() {
+        CharClass::Number
+    } else if c.is_alphabetic() {
+        CharClass::Letter
+    } else {
+        CharClass::NonWord
     }
+}
+
+/// The bonus for matching a character of class `class` that comes after one of class `previous`.
+fn bonus_for(previous: CharClass, class: CharClass) -> i32 {
+    use CharClass::*;
+
+    match (previous, class) {
+        (_, White) => BONUS_BOUNDARY_WHITE,
+        (_, Delimiter | NonWord) => BONUS_NON_WORD,
+        (White, _) => BONUS_BOUNDARY_WHITE,
+        (Delimiter, _) => BONUS_BOUNDARY_DELIMITER,
+        (NonWord, _) => BONUS_BOUNDARY,
+        (Lower, Upper) => BONUS_CAMEL_123,
+        (Lower | Upper | Letter, Number) => BONUS_CAMEL_123,
+        _ => 0
+    }
+}
+
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+/// Whether the characters of `needle` all appear in `haystack` in order, ignoring case.
+/// Doesn't allocate, so it's a cheap way to narrow down candidates before scoring them.
+pub fn is_subsequence(haystack: &str, needle: &str) -> bool {
+    let mut remaining = haystack.chars().map(fold_case);
+    needle.chars().map(fold_case).all(|c| remaining.any(|h| h == c))
+}
 
-    needle_chars.is_empty()
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FuzzyMatch {
+    pub score: i32,
+    /// The character (not byte) indices of the matched characters in the text.
+    pub positions: Vec<usize>
 }
 
-pub fn lazy_match_indices(haystack: &str, needle: &str) -> Vec<(usize, usize)> {
-    let mut needle_chars: Vec<char> = needle.chars().collect();
-    let mut indices: Vec<(usize, usize)> = Vec::new();
+/// Scores how well `needle` fuzzily matches `haystack`, ignoring case. Returns `None` if
+/// the characters of `needle` don't all appear in `haystack` in order.
+pub fn fuzzy_score(haystack: &str, needle: &str) -> Option<FuzzyMatch> {
+    let needle = needle.chars().map(fold_case).collect::<Vec<_>>();
+    let chars = haystack.chars().collect::<Vec<_>>();
+    let folded = chars.iter().copied().map(fold_case).collect::<Vec<_>>();
+    let (n, m) = (chars.len(), needle.len());
 
-    for (i, c) in haystack.chars().enumerate() {
-        if let Some(index) = needle_chars.iter().position(|&x| x == c) {
-            indices.push((i, index));
-            needle_chars.remove(index);
+    if m == 0 || m > n {
+        return None;
+    }
+
+    // Cheap check before doing the actual work
+    let mut remaining = folded.iter();
+    if !needle.iter().all(|c| remaining.any(|h| h == c)) {
+        return None;
+    }
+
+    let bonuses = (0..n)
+        .map(|j| bonus_for(
+            if j == 0 { CharClass::White } else { char_class(chars[j - 1]) },
+            char_class(chars[j])
+        ))
+        .collect::<Vec<_>>();
+
+    // For every needle character i matched at haystack character j: the best score so far,
+    // the length of the run of consecutive matches ending there, and where needle character
+    // i - 1 was matched to get that score
+    let mut scores = vec![vec![NO_MATCH; n]; m];
+    let mut runs = vec![vec![0_usize; n]; m];
+    let mut previous = vec![vec![0_usize; n]; m];
+
+    for j in 0..n {
+        if folded[j] == needle[0] {
+            scores[0][j] = SCORE_MATCH + bonuses[j] * BONUS_FIRST_CHAR_MULTIPLIER;
+            runs[0][j] = 1;
         }
     }
 
-    indices
-}
+    for i in 1..m {
+        // The best way to reach j with a gap, already including the gap penalty
+        let mut gap_score = NO_MATCH;
+        let mut gap_from = 0;
 
-pub fn fuzzy_match(haystack: &str, needle: &str) -> bool {
-    let mut needle = needle.chars();
+        for j in i..n {
+            if j >= 2 {
+                if gap_score > NO_MATCH {
+                    gap_score += SCORE_GAP_EXTENSION;
+                }
 
-    for c in haystack.chars() {
-        if needle.clone().next().is_some_and(|n| c == n) {
-            needle.next();
+                let from_score = scores[i - 1][j - 2];
+                if from_score > NO_MATCH && from_score + SCORE_GAP_START >= gap_score {
+                    gap_score = from_score + SCORE_GAP_START;
+                    gap_from = j - 2;
+                }
+            }
+
+            if folded[j] != needle[i] {
+                continue;
+            }
+
+            let mut best = NO_MATCH;
+            let mut best_from = 0;
+            let mut best_run = 1;
+
+            if scores[i - 1][j - 1] > NO_MATCH {
+                // A run of consecutive matches keeps the bonus of its first character
+                let run = runs[i - 1][j - 1] + 1;
+                let bonus = bonuses[j].max(BONUS_CONSECUTIVE).max(bonuses[j + 1 - run]);
+
+                best = scores[i - 1][j - 1] + SCORE_MATCH + bonus;
+                best_from = j - 1;
+                best_run = run;
+            }
+
+            if gap_score > NO_MATCH && gap_score + SCORE_MATCH + bonuses[j] > best {
+                best = gap_score + SCORE_MATCH + bonuses[j];
+                best_from = gap_from;
+                best_run = 1;
+            }
+
+            scores[i][j] = best;
+            previous[i][j] = best_from;
+            runs[i][j] = if best > NO_MATCH { best_run } else { 0 };
         }
     }
 
-    needle.next().is_none()
-}
\ No newline at end of file
+    // Prefer the earliest end on ties
+    let (end, score) = (0..n)
+        .map(|j| (j, scores[m - 1][j]))
+        .filter(|(_, score)| *score > NO_MATCH)
+        .fold(None, |best: Option<(usize, i32)>, (j, score)| match best {
+            Some((_, best_score)) if best_score >= score => best,
+            _ => Some((j, score))
+        })?;
+
+    let mut positions = vec![0; m];
+    let mut j = end;
+    for i in (0..m).rev() {
+        positions[i] = j;
+        if i > 0 {
+            j = previous[i][j];
+        }
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn positions(haystack: &str, needle: &str) -> Option<Vec<usize>> {
+        fuzzy_score(haystack, needle).map(|m| m.positions)
+    }
+
+    fn score(haystack: &str, needle: &str) -> i32 {
+        fuzzy_score(haystack, needle).map_or(i32::MIN, |m| m.score)
+    }
+
+    #[test]
+    fn rejects_missing_or_unordered_characters() {
+        assert_eq!(fuzzy_score("firefox", ""), None);
+        assert_eq!(fuzzy_score("firefox", "firefoxes"), None);
+        assert_eq!(fuzzy_score("firefox", "xf"), None);
+        assert_eq!(fuzzy_score("firefox", "q"), None);
+    }
+
+    #[test]
+    fn checks_subsequences_without_scoring() {
+        assert!(is_subsequence("Firefox", "ffx"));
+        assert!(is_subsequence("firefox", ""));
+        assert!(!is_subsequence("firefox", "xf"));
+    }
+
+    #[test]
+    fn ignores_case() {
+        assert_eq!(positions("Firefox", "FIRE"), Some(vec![0, 1, 2, 3]));
+        assert_eq!(positions("LibreOffice Writer", "WRITER"), Some(vec![12, 13, 14, 15, 16, 17]));
+    }
+
+    #[test]
+    fn matches_word_boundaries() {
+        assert_eq!(positions("Visual Studio Code", "code"), Some(vec![14, 15, 16, 17]));
+        assert_eq!(positions("foo-bar", "fb"), Some(vec![0, 4]));
+        assert_eq!(positions("fooBar", "b"), Some(vec![3]));
+        assert_eq!(positions("gnome-system-monitor", "sm"), Some(vec![6, 13]));
+    }
+
+    #[test]
+    fn finds_the_best_alignment_rather_than_the_first() {
+        assert_eq!(positions("axxb ab", "ab"), Some(vec![5, 6]));
+        assert_eq!(positions("xcode Code", "code"), Some(vec![6, 7, 8, 9]));
+    }
+
+    #[test]
+    fn positions_are_character_indices() {
+        assert_eq!(positions("Ünïcödé Editor", "edit"), Some(vec![8, 9, 10, 11]));
+    }
+
+    #[test]
+    fn prefers_consecutive_matches() {
+        assert!(score("ab", "ab") > score("axb", "ab"));
+        assert!(score("terminal", "term") > score("the error manual", "term"));
+    }
+
+    #[test]
+    fn prefers_boundary_matches() {
+        assert!(score("Code", "code") > score("Barcode", "code"));
+        assert!(score("VS Code", "code") > score("Barcode", "code"));
+        assert!(score("file-manager", "fm") > score("firmware", "fm"));
+    }
+
+    #[test]
+    fn shorter_gaps_score_higher() {
+        assert!(score("a_b", "ab") > score("a___b", "ab"));
+    }
+}
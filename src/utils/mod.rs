@@ -3,6 +3,7 @@ pub mod broadcast;
 pub mod display;
 pub mod filesystem;
 pub mod gesture;
+pub mod hashing;
 pub mod matching;
 pub mod process;
 pub mod timeout;
@@ -1,5 +1,6 @@
 use std::{ffi::CString, path::Path, sync::LazyLock};
 use regex::Regex;
+use hyprland::dispatch::{Dispatch, DispatchType};
 use libc::{open, close, setsid, dup2, O_RDWR, STDIN_FILENO, STDOUT_FILENO, STDERR_FILENO};
 
 static FIELD_CODE_REGEX: LazyLock<Regex> = LazyLock::new(|| {
@@ -59,39 +60,69 @@ pub fn send_signal(pid: u32, signal: &str) {
         .output();
 }
 
+/// Builds a command line that runs a shell command in a new terminal window.
+pub fn terminal_command(command: &str) -> String {
+    format!("foot fish -C \"{}\"", command.replace('"', "\\\""))
+}
+
+/// Builds the argv that runs `argv` in a new terminal window, which stays open after it exits.
+/// Unlike `terminal_command`, nothing is re-parsed or interpreted by another shell.
+pub fn terminal_argv(argv: &[&str]) -> Vec<String> {
+    ["foot", "--hold", "--"].iter()
+        .chain(argv)
+        .map(|arg| (*arg).to_owned())
+        .collect()
+}
+
+/// Launches a command through Hyprland with a rule that opens its window on `workspace`.
+pub fn launch_on_workspace(input: &str, workspace: i32) {
+    let command = FIELD_CODE_REGEX.replace_all(input, "");
+    let command = format!("[workspace {}] {}", workspace, command.trim());
+
+    if let Err(err) = Dispatch::call(DispatchType::Exec(&command)) {
+        error!(input, workspace, %err, "Failed to launch command on workspace");
+    }
+}
+
 pub fn launch(input: &str) {
     // Remove field codes from argv (including those that are deprecated), we won't be needing them...
     let argv: Vec<String> = if let Some(args) = shlex::split(input) {
         args.iter()
             .map(|s| FIELD_CODE_REGEX.replace_all(s, "").to_string())
+            .filter(|arg| !arg.is_empty())
             .collect()
     } else {
         error!(input, "Failed to parse command");
         return;
     };
 
+    launch_argv(&argv);
+}
+
+/// Launches a program with exactly these arguments, detached from the shell.
+pub fn launch_argv(argv: &[String]) {
+    if argv.is_empty() {
+        warn!("No command to execute");
+        return;
+    }
+
     let binding = glib::environ();
     let envp: Vec<&Path> = binding
         .iter()
         .map(Path::new)
         .collect();
 
-    if !argv.is_empty() {
-        let argv_paths: Vec<&Path> = argv.iter()
-            .map(AsRef::as_ref)
-            .filter(|path: &&Path| !path.as_os_str().is_empty())
-            .collect();
-
-        if let Err(err) = glib::spawn_async(
-            None::<&str>,
-            &argv_paths,
-            &envp,
-            glib::SpawnFlags::SEARCH_PATH_FROM_ENVP | glib::SpawnFlags::SEARCH_PATH,
-            Some(Box::new(detach_child)),
-        ) {
-            error!(input, %err, "Failed to launch command");
-        }
-    } else {
-        warn!("No command to execute");
+    let argv_paths: Vec<&Path> = argv.iter()
+        .map(AsRef::as_ref)
+        .collect();
+
+    if let Err(err) = glib::spawn_async(
+        None::<&str>,
+        &argv_paths,
+        &envp,
+        glib::SpawnFlags::SEARCH_PATH_FROM_ENVP | glib::SpawnFlags::SEARCH_PATH,
+        Some(Box::new(detach_child)),
+    ) {
+        error!(?argv, %err, "Failed to launch command");
     }
-}
\ No newline at end of file
+}
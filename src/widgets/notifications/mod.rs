@@ -86,15 +86,27 @@ impl NotificationsWindow {
 
         self.container.prepend(&clamp);
 
-        let timeout = if widget.notification.borrow().hints.iter().any(|hint| {
-            matches!(hint, NotificationHint::Urgency(u) if *u >= 2)
-        }) {
-            CRITICAL_NOTIF_DISPLAY_TIMEOUT
-        } else {
-            NOTIF_DISPLAY_TIMEOUT
-        } as u64;
-
-        glib::timeout_add_local_once(Duration::from_millis(timeout), clone!(
+        let timeout = {
+            let notification = widget.notification.borrow();
+            let is_critical = notification.hints.iter().any(|hint| {
+                matches!(hint, NotificationHint::Urgency(u) if *u >= 2)
+            });
+
+            notification.rule.as_ref().and_then(|rule| rule.popup_timeout).unwrap_or_else(|| {
+                Duration::from_millis(if is_critical {
+                    CRITICAL_NOTIF_DISPLAY_TIMEOUT
+                } else {
+                    NOTIF_DISPLAY_TIMEOUT
+                } as u64)
+            })
+        };
+
+        // A rule can keep the popup up until it's dismissed
+        if timeout.is_zero() {
+            return;
+        }
+
+        glib::timeout_add_local_once(timeout, clone!(
             #[weak] widget,
             move || {
                 widget.queue_destroy(Some(NotificationDismissAnimation::Right));
@@ -110,7 +122,9 @@ pub fn listen_for_notifications() {
     glib::spawn_future_local(async move {
         while let Ok(message) = receiver.recv().await {
             match message {
-                BusEvent::NotificationAdded(notification) => if !APP.do_not_disturb.get() {
+                BusEvent::NotificationAdded(notification) => if !APP.do_not_disturb.get()
+                    || notification.rule.as_ref().is_some_and(|rule| rule.ignore_dnd)
+                {
                     APP_LOCAL.with(move |app| {
                         for container in app.notification_containers.borrow().iter() {
                             let mut notif_widget = NotificationWidget::new(notification.clone());
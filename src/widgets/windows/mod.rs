@@ -5,6 +5,7 @@ pub mod session;
 pub mod sidebar_left;
 pub mod sidebar_right;
 pub mod clipboard;
+pub mod text_action;
 
 use std::any::Any;
 
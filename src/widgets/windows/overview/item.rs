@@ -4,8 +4,8 @@ use gtk::prelude::*;
 use crate::pixbuf;
 use crate::scss;
 use crate::widgets::windows;
-use crate::services::{apps, clipboard};
-use crate::utils::matching;
+use crate::services::{apps, clipboard, hyprland::{self, WindowAction}};
+use crate::utils::{gesture, matching};
 use crate::color::models::ColorModel as _;
 
 pub static ITEM_ANIMATION_DURATION: u32 = 175;
@@ -22,11 +22,30 @@ static EXACT_ID_COMP: [&str; 1] = [
 #[derive(Clone, Debug)]
 pub enum OverviewSearchItemAction {
     Launch(String),
+    /// Launches a command, tracking the launch under `key` rather than the command.
+    LaunchTracked {
+        command: String,
+        key: String
+    },
+    LaunchOnWorkspace {
+        command: String,
+        workspace: i32
+    },
+    Window {
+        address: String,
+        action: WindowAction
+    },
     RunCommand(String),
     Copy(String),
     Custom {
         id: &'static str,
         func: fn()
+    },
+    /// Runs another action, then calls `on_run` with `key`, so services can remember what was picked.
+    Tracked {
+        action: Box<OverviewSearchItemAction>,
+        key: String,
+        on_run: fn(&str)
     }
 }
 
@@ -37,7 +56,19 @@ impl PartialEq for OverviewSearchItemAction {
             (Launch(a), Launch(b))
             | (RunCommand(a), RunCommand(b))
             | (Copy(a), Copy(b)) => a == b,
+            (LaunchTracked { command: a, key: a_key }, LaunchTracked { command: b, key: b_key }) => {
+                a == b && a_key == b_key
+            },
+            (LaunchOnWorkspace { command: a, workspace: a_workspace }, LaunchOnWorkspace { command: b, workspace: b_workspace }) => {
+                a == b && a_workspace == b_workspace
+            },
+            (Window { address: a, action: a_action }, Window { address: b, action: b_action }) => {
+                a == b && a_action == b_action
+            },
             (Custom { id: a, .. }, Custom { id: b, .. }) => a == b,
+            (Tracked { action: a, key: a_key, .. }, Tracked { action: b, key: b_key, .. }) => {
+                a == b && a_key == b_key
+            },
             _ => false,
         }
     }
@@ -45,6 +76,59 @@ impl PartialEq for OverviewSearchItemAction {
 
 impl Eq for OverviewSearchItemAction {}
 
+/// An alternative to an item's main action, listed in the item's menu (Tab or right-click).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverviewSearchSecondaryAction {
+    pub label: String,
+    pub action: OverviewSearchItemAction
+}
+
+/// The data behind a search item. Modules produce these away from the GTK thread, and
+/// the widgets are only created for results that aren't already shown.
+#[derive(Clone, Debug)]
+pub struct OverviewSearchResult {
+    pub id: String,
+    pub title: String,
+    pub subtitle: Option<String>,
+    pub icon: String,
+    pub action_text: String,
+    pub action: OverviewSearchItemAction,
+    pub secondary_actions: Vec<OverviewSearchSecondaryAction>,
+    pub query: Option<String>
+}
+
+impl OverviewSearchResult {
+    pub fn new(
+        id: String,
+        title: String,
+        subtitle: Option<String>,
+        icon: String,
+        action_text: String,
+        action: OverviewSearchItemAction,
+        query: Option<String>
+    ) -> Self {
+        Self {
+            id,
+            title,
+            subtitle,
+            icon,
+            action_text,
+            action,
+            secondary_actions: Vec::new(),
+            query
+        }
+    }
+
+    pub fn with_secondary_actions(mut self, secondary_actions: Vec<OverviewSearchSecondaryAction>) -> Self {
+        self.secondary_actions = secondary_actions;
+        self
+    }
+
+    pub fn exact_id_comp_has(&self) -> bool {
+        EXACT_ID_COMP.contains(&self.id.as_str())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct OverviewSearchItem {
     pub id: String,
@@ -53,10 +137,14 @@ pub struct OverviewSearchItem {
     pub icon: String,
     pub action_text: String,
     pub action: Rc<RefCell<OverviewSearchItemAction>>,
+    pub secondary_actions: Vec<OverviewSearchSecondaryAction>,
     pub query: Option<String>,
     row: gtk::ListBoxRow,
     widget: gtk::Revealer,
-    title_label: gtk::Label
+    title_label: gtk::Label,
+    menu: gtk::Popover,
+    menu_box: gtk::Box,
+    menu_hint: gtk::Label
 }
 
 impl OverviewSearchItem {
@@ -80,6 +168,27 @@ impl OverviewSearchItem {
                 set_ellipsize: gtk::pango::EllipsizeMode::End
             },
             
+            menu_box = gtk::Box {
+                set_css_classes: &["overview-search-item-menu-box"],
+                set_orientation: gtk::Orientation::Vertical
+            },
+
+            menu = gtk::Popover {
+                set_css_classes: &["overview-search-item-menu"],
+                set_has_arrow: false,
+                set_position: gtk::PositionType::Bottom,
+                set_halign: gtk::Align::End,
+                set_child: Some(&menu_box)
+            },
+
+            menu_hint = gtk::Label {
+                set_css_classes: &["overview-search-item-menu-hint"],
+                set_label: "more_horiz",
+                set_tooltip_text: Some("More actions (Tab)"),
+                set_valign: gtk::Align::Center,
+                set_visible: false
+            },
+
             action_slide_revealer = gtk::Revealer {
                 set_transition_type: gtk::RevealerTransitionType::SlideLeft,
                 set_transition_duration: 175,
@@ -107,6 +216,12 @@ impl OverviewSearchItem {
                     #[weak] action_slide_revealer,
                     move |button| action_slide_revealer.set_reveal_child(button.has_focus())
                 ),
+
+                // Unparent the menu so it doesn't outlive the item
+                connect_destroy: clone!(
+                    #[weak] menu,
+                    move |_| menu.unparent()
+                ),
             
                 gtk::Box {
                     set_css_classes: &["overview-search-item-box"],
@@ -134,8 +249,9 @@ impl OverviewSearchItem {
 
                         append: &title_label
                     },
-                
-                    append: &action_slide_revealer
+
+                    append: &action_slide_revealer,
+                    append: &menu_hint
                 }
             },
 
@@ -160,33 +276,88 @@ impl OverviewSearchItem {
             icon,
             action_text,
             action,
+            secondary_actions: Vec::new(),
             query,
             row,
             widget,
-            title_label
+            title_label,
+            menu,
+            menu_box,
+            menu_hint
         };
 
+        item.menu.set_parent(&button);
+
+        // Tab or right-click opens the menu of alternative actions
+        let key_controller = gtk::EventControllerKey::new();
+        key_controller.connect_key_pressed(clone!(
+            #[weak(rename_to = menu)] item.menu,
+            #[weak(rename_to = menu_box)] item.menu_box,
+            #[upgrade_or] glib::Propagation::Proceed,
+            move |_, key, _, _| if key == gdk::Key::Tab && popup_menu(&menu, &menu_box) {
+                glib::Propagation::Stop
+            } else {
+                glib::Propagation::Proceed
+            }
+        ));
+        button.add_controller(key_controller);
+
+        button.add_controller(gesture::on_secondary_down(clone!(
+            #[weak(rename_to = menu)] item.menu,
+            #[weak(rename_to = menu_box)] item.menu_box,
+            move |_, _, _| {
+                popup_menu(&menu, &menu_box);
+            }
+        )));
+
         item.set_title_markup();
         item
     }
 
+    fn add_menu_button(&self, label: &str, on_clicked: impl Fn() + 'static) {
+        let button = gtk::Button::with_label(label);
+        button.set_css_classes(&["overview-search-item-menu-button"]);
+        button.connect_clicked(clone!(
+            #[weak(rename_to = menu)] self.menu,
+            move |_| {
+                menu.popdown();
+                on_clicked();
+            }
+        ));
+
+        self.menu_box.append(&button);
+    }
+
     pub fn get_row(&self) -> gtk::ListBoxRow {
         self.row.clone()
     }
 
-    pub fn eq(&self, other: &Self) -> bool {
+    pub fn from_result(result: &OverviewSearchResult) -> Self {
+        let mut item = Self::new(
+            result.id.clone(),
+            result.title.clone(),
+            result.subtitle.clone(),
+            result.icon.clone(),
+            result.action_text.clone(),
+            result.action.clone(),
+            result.query.clone()
+        );
+
+        item.set_secondary_actions(result.secondary_actions.clone());
+        item
+    }
+
+    pub fn eq(&self, other: &OverviewSearchResult) -> bool {
         self.id == other.id &&
         self.title == other.title &&
         self.subtitle == other.subtitle &&
         self.icon == other.icon &&
         self.action_text == other.action_text &&
-        match (self.action.try_borrow(), other.action.try_borrow()) {
-            (Ok(a), Ok(b)) => a.clone() == b.clone(),
-            _ => false
-        }
+        self.secondary_actions == other.secondary_actions &&
+        self.action.try_borrow().is_ok_and(|action| *action == other.action)
     }
 
-    pub fn id_eq(&self, other: &Self) -> bool {
+    pub fn id_eq(&self, other: &OverviewSearchResult) -> bool {
         self.id == other.id
     }
 
@@ -194,7 +365,7 @@ impl OverviewSearchItem {
         EXACT_ID_COMP.contains(&self.id.as_str())
     }
 
-    pub fn smart_compare(&self, other: &Self) -> bool {
+    pub fn smart_compare(&self, other: &OverviewSearchResult) -> bool {
         // Results that should be compared exactly with eq
         if self.exact_id_comp_has() {
             self.eq(other)
@@ -221,16 +392,16 @@ impl OverviewSearchItem {
 
     pub fn set_title_markup(&self) {
         if let Some(query) = &self.query {
-            // Build the markup for our query using lazy match indices
-            let indices: Vec<(usize, usize)> = matching::lazy_match_indices(
-                &self.title.to_lowercase(),
-                &query.to_lowercase()
-            );
+            // Build the markup for our query, bolding the fuzzily matched characters. Entries
+            // that only matched on another field (keywords, comment...) get nothing bolded.
+            let positions = matching::fuzzy_score(&self.title, query.trim())
+                .map(|m| m.positions)
+                .unwrap_or_default();
 
             let mut chars: Vec<String> = Vec::new();
 
             for (i, c) in self.title.chars().enumerate() {
-                chars.push(if indices.iter().any(|(start, _)| *start == i) {
+                chars.push(if positions.contains(&i) {
                     format!(
                         "<b>{}</b>",
                         scss::escape_html(c)
@@ -256,12 +427,64 @@ impl OverviewSearchItem {
             *act = action;
         }
     }
+
+    pub fn set_secondary_actions(&mut self, secondary_actions: Vec<OverviewSearchSecondaryAction>) {
+        if self.secondary_actions == secondary_actions {
+            return;
+        }
+
+        while let Some(child) = self.menu_box.first_child() {
+            self.menu_box.remove(&child);
+        }
+
+        // The main action comes first so the menu reads as a full list of choices
+        if !secondary_actions.is_empty() {
+            self.add_menu_button(&self.action_text, clone!(
+                #[strong(rename_to = action)] self.action,
+                move || if let Ok(action) = action.try_borrow() {
+                    run_action(&action);
+                }
+            ));
+        }
+
+        for secondary_action in &secondary_actions {
+            let action = secondary_action.action.clone();
+            self.add_menu_button(&secondary_action.label, move || run_action(&action));
+        }
+
+        self.menu_hint.set_visible(!secondary_actions.is_empty());
+        self.secondary_actions = secondary_actions;
+    }
+}
+
+/// Shows an item's menu of alternative actions, if it has any.
+fn popup_menu(menu: &gtk::Popover, menu_box: &gtk::Box) -> bool {
+    let Some(first) = menu_box.first_child() else {
+        return false;
+    };
+
+    menu.popup();
+    first.grab_focus();
+    true
 }
 
 pub fn run_action(action: &OverviewSearchItemAction) {
+    run_action_inner(action);
+
+    // Hide the overview after clicking an item
+    windows::hide("overview");
+}
+
+fn run_action_inner(action: &OverviewSearchItemAction) {
     match action {
         OverviewSearchItemAction::Launch(command) => apps::launch_and_track(command),
 
+        OverviewSearchItemAction::LaunchTracked { command, key } => apps::launch_and_track_as(command, key),
+
+        OverviewSearchItemAction::LaunchOnWorkspace { command, workspace } => {
+            apps::launch_on_workspace_and_track(command, *workspace);
+        },
+
         OverviewSearchItemAction::RunCommand(command) => {
             std::thread::spawn(clone!(
                 #[strong] command,
@@ -272,6 +495,10 @@ pub fn run_action(action: &OverviewSearchItemAction) {
             ));
         },
     
+        OverviewSearchItemAction::Window { address, action } => {
+            hyprland::dispatch_window_action(address, *action);
+        },
+
         OverviewSearchItemAction::Copy(text) => {
             std::thread::spawn(clone!(
                 #[strong] text,
@@ -279,9 +506,11 @@ pub fn run_action(action: &OverviewSearchItemAction) {
             ));
         }
     
-        OverviewSearchItemAction::Custom { func, .. } => func()
-    }
+        OverviewSearchItemAction::Custom { func, .. } => func(),
 
-    // Hide the overview after clicking an item
-    windows::hide("overview");
+        OverviewSearchItemAction::Tracked { action, key, on_run } => {
+            run_action_inner(action);
+            on_run(key);
+        }
+    }
 }
\ No newline at end of file
@@ -1,15 +1,12 @@
-use std::{cell::RefCell, rc::Rc, time::Duration};
+use std::time::Duration;
 use gtk::prelude::*;
 
-use super::item::{OverviewSearchItem, ITEM_ANIMATION_DURATION};
-
-static LOCK_HOLD_DURATION: Duration = Duration::from_millis(1);
+use super::item::{OverviewSearchItem, OverviewSearchResult, ITEM_ANIMATION_DURATION};
 
 #[derive(Debug, Clone)]
 pub struct OverviewSearchList {
     pub items: Vec<OverviewSearchItem>,
-    widget: gtk::ListBox,
-    lock: Rc<RefCell<bool>> // To prevent race conditions when the user types too fast
+    widget: gtk::ListBox
 }
 
 pub fn get_button_from_row(row: &gtk::ListBoxRow) -> Option<gtk::Button> {
@@ -45,8 +42,7 @@ impl OverviewSearchList {
 
         Self {
             items: Vec::new(),
-            widget,
-            lock: Rc::new(RefCell::new(false))
+            widget
         }
     }
 
@@ -54,57 +50,82 @@ impl OverviewSearchList {
         self.widget.clone()
     }
 
-    pub fn lock(&self) {
-        *self.lock.borrow_mut() = true;
-
-        // Unlock after LOCK_HOLD_DURATION
-        glib::timeout_add_local_once(LOCK_HOLD_DURATION, {
-            let lock = self.lock.clone();
-            move || *lock.borrow_mut() = false
-        });
+    /// Where the row of the item at `index` goes in the list box. Rows of removed items
+    /// stay in the list box while they animate out, so the indices don't line up.
+    fn row_position(&self, index: usize) -> i32 {
+        self.items.get(index + 1).map_or(-1, |next| next.get_row().index())
     }
 
     pub fn insert(&mut self, item: &OverviewSearchItem, position: usize) {
-        if self.lock.try_borrow().map_or(true, |lock| *lock) || position > self.items.len() {
+        if position > self.items.len() {
             return;
         }
 
-        self.widget.insert(&item.get_row(), position as i32);
+        self.items.insert(position, item.clone());
+        self.widget.insert(&item.get_row(), self.row_position(position));
 
         // Reveal this item after 1ms
         glib::timeout_add_local_once(Duration::from_millis(1), clone!(
             #[strong] item,
             move || item.reveal()
         ));
-
-        self.items.insert(position, item.clone());
     }
 
     pub fn remove(&mut self, position: usize) {
-        if self.lock.try_borrow().map_or(true, |lock| *lock) || position >= self.items.len() {
+        if position >= self.items.len() {
             return;
         }
 
-        if position < self.items.len() {
-            let item = self.items.remove(position);
-            item.hide();
+        let item = self.items.remove(position);
+        item.hide();
 
-            glib::timeout_add_local_once(Duration::from_millis(ITEM_ANIMATION_DURATION as u64), {
-                let widget = self.widget.clone();
-                move || widget.remove(&item.get_row())
-            });
-        }
+        glib::timeout_add_local_once(Duration::from_millis(ITEM_ANIMATION_DURATION as u64), {
+            let widget = self.widget.clone();
+            move || widget.remove(&item.get_row())
+        });
     }
 
     pub fn move_item(&mut self, from: usize, to: usize) {
         if from != to && from < self.items.len() && to < self.items.len() {
             let item = self.items.remove(from);
-            self.items.insert(to, item);
+            self.items.insert(to, item.clone());
 
             // Update the ListBox
-            let row = self.widget.row_at_index(from as i32).unwrap();
+            let row = item.get_row();
             self.widget.remove(&row);
-            self.widget.insert(&row, to as i32);
+            self.widget.insert(&row, self.row_position(to));
         }
     }
-}
\ No newline at end of file
+
+    /// Brings the list in line with a new set of results. Items that are already shown
+    /// are updated and moved instead of being re-created, so they don't animate in again.
+    pub fn update(&mut self, results: &[OverviewSearchResult]) {
+        for (i, result) in results.iter().enumerate() {
+            if let Some(index) = self.items.iter().position(|item| item.smart_compare(result)) {
+                let existing_item = &mut self.items[index];
+                if result.exact_id_comp_has() {
+                    if result.query.is_some() && existing_item.query != result.query {
+                        existing_item.query = result.query.clone();
+                        existing_item.set_title_markup();
+                    }
+                } else {
+                    existing_item.set_title_label(&result.title);
+                    existing_item.set_action(result.action.clone());
+                    existing_item.set_secondary_actions(result.secondary_actions.clone());
+                }
+
+                // Move this item if its position has changed
+                self.move_item(index, i);
+            } else {
+                self.insert(&OverviewSearchItem::from_result(result), i);
+            }
+        }
+
+        // Remove items that are not in results
+        for i in (0..self.items.len()).rev() {
+            if !results.iter().any(|result| self.items[i].smart_compare(result)) {
+                self.remove(i);
+            }
+        }
+    }
+}
@@ -1,31 +1,50 @@
 mod item;
 mod list;
 mod modules;
+mod search;
 mod windows;
 
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::LazyLock;
-use freedesktop_desktop_entry::get_languages_from_env;
 use gtk::prelude::*;
 use regex::Regex;
-use urlencoding::encode;
 
+use crate::config::read_config;
 use crate::ipc;
-use crate::services::apps;
+use crate::services::search_plugins;
 use crate::utils::gesture;
-use self::item::{OverviewSearchItem, OverviewSearchItemAction};
 use self::list::{OverviewSearchList, get_button_from_row};
-use self::modules::{OverviewSearchModule, input_without_extensions, validate_input};
-use self::windows::{frequent::OverviewFrequentWindow, recent::OverviewRecentWindow};
+use self::modules::{OverviewSearchModule, validate_input};
+use self::search::OverviewSearch;
+use self::windows::{frequent::OverviewFrequentWindow, recent::OverviewRecentWindow, recent_files::OverviewRecentFilesWindow};
 use super::fullscreen::FullscreenWindow;
 
-static MODULES: LazyLock<Vec<&(dyn OverviewSearchModule + Send + Sync)>> = LazyLock::new(|| vec![
-    &modules::calculator::OverviewCalculatorModule,
-    &modules::text::OverviewTextModule,
-    &modules::terminal::OverviewTerminalModule,
-    &modules::hashing::OverviewHashingModule
-]);
+/// The compiled-in modules, followed by the plugins declared in the config, which can
+/// change while the shell is running.
+fn modules() -> Vec<Box<dyn OverviewSearchModule>> {
+    let mut modules: Vec<Box<dyn OverviewSearchModule>> = vec![
+        Box::new(modules::calculator::OverviewCalculatorModule),
+        Box::new(modules::text::OverviewTextModule),
+        Box::new(modules::terminal::OverviewTerminalModule),
+        Box::new(modules::hashing::OverviewHashingModule),
+        Box::new(modules::window_switcher::OverviewWindowSwitcherModule),
+        Box::new(modules::files::OverviewFilesModule),
+        Box::new(modules::characters::OverviewCharactersModule),
+        Box::new(modules::dev::OverviewDevModule),
+        Box::new(modules::browser::OverviewBrowserModule),
+        Box::new(modules::ssh::OverviewSshModule),
+        Box::new(modules::recent_files::OverviewRecentFilesModule)
+    ];
+
+    let plugins = read_config().overview.plugins.clone();
+    search_plugins::prune(&plugins);
+    for config in plugins {
+        modules.push(Box::new(modules::plugin::OverviewPluginModule { config }));
+    }
+
+    modules
+}
 
 static ALPHANUMERIC_SYMBOLIC_REGEX: LazyLock<Regex> = LazyLock::new(|| {
     Regex::new("^[a-zA-Z0-9 ~!@#$%^&*()_+\\-=\\[\\]{}|;':\",./<>?]+$").expect("Failed to compile alphanumeric symbolic regex")
@@ -41,66 +60,26 @@ fn generate_entry_box_icon_stack() -> gtk::Stack {
     stack.add_titled(&default_label, Some("search"), "search");
     stack.set_visible_child_name("search");
 
-    for module in MODULES.iter() {
-        let label = gtk::Label::new(Some(module.icon()));
-        stack.add_titled(&label, Some(module.icon()), module.icon());
+    for module in modules() {
+        add_entry_box_icon(&stack, module.icon());
     }
 
     stack
 }
 
-fn generate_search_results(query: &str) -> Vec<OverviewSearchItem> {
-    let mut results = Vec::new();
-
-    if MODULES.iter().any(|module| validate_input(*module, query)) {
-        // Iterate through all modules and collect results
-        for module in MODULES.iter() {
-            if validate_input(*module, query) {
-                let input = input_without_extensions(*module, query);
-                for item in module.run(&input) {
-                    results.push(item);
-                }
-            }
-        }
-    } else {
-        // Filter and weigh the applications based on the query
-        let locales = get_languages_from_env();
-        let desktops = apps::query_desktops(query);
-        for i in 0..8 {
-            if let Some(entry) = desktops.get(i) {
-                let entry = &entry.entry;
-
-                results.push(OverviewSearchItem::new(
-                    "application-result".to_owned(),
-                    entry.name(&locales).unwrap_or_default().to_string(),
-                    None,
-                    entry.icon().map(|icon| icon.to_owned()).unwrap_or_default(),
-                    "launch".to_owned(),
-                    OverviewSearchItemAction::Launch(entry.exec().unwrap_or_default().to_owned()),
-                    Some(query.to_owned())
-                ));
-            }
-        }
+fn add_entry_box_icon(stack: &gtk::Stack, icon: &str) {
+    if stack.child_by_name(icon).is_none() {
+        let label = gtk::Label::new(Some(icon));
+        stack.add_titled(&label, Some(icon), icon);
     }
-
-    // web search as final fallback
-    results.push(OverviewSearchItem::new(
-        "web-search".to_owned(),
-        query.to_owned(),
-        Some("Search the web".to_owned()),
-        "search".to_owned(),
-        "search".to_owned(),
-        OverviewSearchItemAction::RunCommand(format!("xdg-open https://duckduckgo.com/?q={}", encode(query))),
-        None
-    ));
-
-    results
 }
 
 pub fn new(application: &libadwaita::Application) -> FullscreenWindow {
     let search_results = Rc::new(RefCell::new(OverviewSearchList::new()));
+    let search = OverviewSearch::new(search_results.clone());
     let frequent_window = OverviewFrequentWindow::new();
     let recent_window = OverviewRecentWindow::new();
+    let recent_files_window = OverviewRecentFilesWindow::new();
 
     view! {
         entry_prompt_revealer = gtk::Revealer {
@@ -134,7 +113,8 @@ pub fn new(application: &libadwaita::Application) -> FullscreenWindow {
             set_vexpand: true,
 
             append: &frequent_window.widget,
-            append: &recent_window.widget
+            append: &recent_window.widget,
+            append: &recent_files_window.widget
         },
 
         windows_revealer = gtk::Revealer {
@@ -292,89 +272,67 @@ pub fn new(application: &libadwaita::Application) -> FullscreenWindow {
         }
     )));
     
-    entry.connect_changed(move |entry| {
-        glib::spawn_future_local(clone!(
-            #[weak] entry,
-            #[weak] entry_prompt_revealer,
-            #[weak] windows_revealer,
-            #[weak] search_results,
-            #[weak] search_results_revealer,
-            #[weak] entry_box,
-            #[weak] entry_box_icon,
-            async move {
-                if entry.text().is_empty() {
-                    entry_prompt_revealer.set_reveal_child(true);
-                    windows_revealer.add_css_class("revealed");
-                    windows_revealer.set_reveal_child(true);
-                    search_results_revealer.remove_css_class("revealed");
-                    search_results_revealer.set_reveal_child(false);
-                    entry_box.style_context().remove_class("entry-extended");
-                    entry_box_icon.set_visible_child_name("search");
-                } else {
-                    entry_prompt_revealer.set_reveal_child(false);
-                    windows_revealer.remove_css_class("revealed");
-                    windows_revealer.set_reveal_child(false);
-                    search_results_revealer.add_css_class("revealed");
-                    search_results_revealer.set_reveal_child(true);
-                    entry_box.style_context().add_class("entry-extended");
-    
-                    let results = generate_search_results(&entry.text());
-    
-                    // Insert the new items into the search results list
-                    let mut search_results_mut = search_results.borrow_mut();
-                    for (i, item) in results.iter().enumerate() {
-                        if let Some(index) = search_results_mut.items.iter().position(|r| r.smart_compare(item)) {
-                            let existing_item = &mut search_results_mut.items[index];
-                            if item.exact_id_comp_has() {
-                                if item.query.is_some() && existing_item.query != item.query {
-                                    existing_item.query = item.query.clone();
-                                    existing_item.set_title_markup();
-                                }
-                            } else {
-                                existing_item.set_title_label(&item.title);
-    
-                                if let Ok(action) = item.action.try_borrow() {
-                                    existing_item.set_action(action.clone());
-                                }
-                            }
-    
-                            // Move this item if its position has changed
-                            search_results_mut.move_item(index, i);
-                        } else {
-                            search_results_mut.insert(item, i);
-                        }
-                    }
-    
-                    // Remove items that are not in results
-                    for (i, item) in search_results_mut.items.clone().iter().enumerate().rev() {
-                        if !results.iter().any(|r| r.smart_compare(item)) {
-                            search_results_mut.remove(i);
-                        }
+    entry.connect_changed(clone!(
+        #[strong] search,
+        move |entry| {
+            glib::spawn_future_local(clone!(
+                #[weak] entry,
+                #[weak] entry_prompt_revealer,
+                #[weak] windows_revealer,
+                #[strong] search,
+                #[weak] search_results_revealer,
+                #[weak] entry_box,
+                #[weak] entry_box_icon,
+                async move {
+                    if entry.text().is_empty() {
+                        search.cancel();
+                        entry_prompt_revealer.set_reveal_child(true);
+                        windows_revealer.add_css_class("revealed");
+                        windows_revealer.set_reveal_child(true);
+                        search_results_revealer.remove_css_class("revealed");
+                        search_results_revealer.set_reveal_child(false);
+                        entry_box.style_context().remove_class("entry-extended");
+                        entry_box_icon.set_visible_child_name("search");
+                    } else {
+                        entry_prompt_revealer.set_reveal_child(false);
+                        windows_revealer.remove_css_class("revealed");
+                        windows_revealer.set_reveal_child(false);
+                        search_results_revealer.add_css_class("revealed");
+                        search_results_revealer.set_reveal_child(true);
+                        entry_box.style_context().add_class("entry-extended");
+
+                        // Update the entry box icon if any module extensions matched
+                        let modules = modules();
+                        let matched_icon = modules.iter()
+                            .find(|module| validate_input(module.as_ref(), &entry.text()))
+                            .map_or_else(|| "search".to_owned(), |module| module.icon().to_owned());
+                        add_entry_box_icon(&entry_box_icon, &matched_icon);
+                        entry_box_icon.set_visible_child_name(&matched_icon);
+
+                        // Results are merged into the list as they arrive
+                        search.search(&entry.text(), modules);
                     }
-    
-                    // Update the entry box icon if any module extensions matched
-                    let mut matched_icon = "search";
-                    for module in MODULES.iter() {
-                        if validate_input(*module, &entry.text()) {
-                            matched_icon = module.icon();
-                            break;
-                        }
-                    }
-                    entry_box_icon.set_visible_child_name(matched_icon);
-    
-                    search_results_mut.lock();
                 }
+            ));
+        }
+    ));
+
+    ipc::listen_for_messages_local(clone!(
+        #[weak] entry,
+        move |message| {
+            if message.as_str() == "update_overview_windows" {
+                // Tell the windows to update their contents
+                frequent_window.update();
+                recent_window.update();
+                recent_files_window.update();
+            }
+
+            // Sent by services whose results changed in the background, like file hashing progress
+            else if message.as_str() == "refresh_overview_search" && !entry.text().is_empty() {
+                search.search(&entry.text(), modules());
             }
-        ));
-    });
-
-    ipc::listen_for_messages_local(move |message| {
-        if message.as_str() == "update_overview_windows" {
-            // Tell the windows to update their contents
-            frequent_window.update();
-            recent_window.update();
         }
-    });
+    ));
 
     fullscreen
 }
\ No newline at end of file
@@ -0,0 +1,58 @@
+use crate::services::browser_index;
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction, OverviewSearchSecondaryAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+const RESULT_ID_PREFIX: &str = "browser-result-";
+const RESULT_LIMIT: usize = 20;
+
+fn open_action(url: &str) -> OverviewSearchItemAction {
+    let url = shlex::try_quote(url).map(|url| url.into_owned()).unwrap_or_default();
+    OverviewSearchItemAction::RunCommand(format!("xdg-open {}", url))
+}
+
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    browser_index::search(query, RESULT_LIMIT)
+        .into_iter()
+        .map(|entry| {
+            let title = if entry.title.is_empty() { entry.url.clone() } else { entry.title };
+            let icon = if entry.bookmarked { "user-bookmarks" } else { "web-browser" };
+
+            OverviewSearchResult::new(
+                format!("{}{}", RESULT_ID_PREFIX, entry.url),
+                title,
+                Some(entry.url.clone()),
+                icon.to_owned(),
+                "open".to_owned(),
+                open_action(&entry.url),
+                Some(query.to_owned())
+            )
+        })
+        .collect()
+}
+
+pub struct OverviewBrowserModule;
+
+impl OverviewSearchModule for OverviewBrowserModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec!["bookmark", "bm", "@"]
+    }
+
+    fn icon(&self) -> &str {
+        "bookmarks"
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query))
+    }
+
+    fn secondary_actions(&self, _query: &str, result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        let Some(url) = result.id.strip_prefix(RESULT_ID_PREFIX) else {
+            return Vec::new();
+        };
+
+        vec![OverviewSearchSecondaryAction {
+            label: "Copy URL".to_owned(),
+            action: OverviewSearchItemAction::Copy(url.to_owned())
+        }]
+    }
+}
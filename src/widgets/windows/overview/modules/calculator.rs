@@ -1,7 +1,8 @@
 use regex::Regex;
 
-use crate::ffi::libqalculate::ffi;
-use super::super::{item::{OverviewSearchItem, OverviewSearchItemAction}, modules::OverviewSearchModule};
+use crate::ffi::libqalculate::ffi::CalculationResult;
+use crate::services::calculator;
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction, OverviewSearchSecondaryAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
 
 /// Table of number suffixes & amount of zeroes needed to reach them
 const NUM_SUFFIXES: [(u16, &str); 102] = [
@@ -83,6 +84,140 @@ fn add_suffix_to_notation(num: &str) -> String {
     }
 }
 
+fn result(id: &str, title: String, subtitle: &str, action: OverviewSearchItemAction) -> OverviewSearchResult {
+    OverviewSearchResult::new(
+        id.to_owned(),
+        title,
+        Some(subtitle.to_owned()),
+        "accessories-calculator".to_owned(),
+        "copy".to_owned(),
+        action,
+        None
+    )
+}
+
+/// The recently copied results, shown before anything is typed.
+fn history_results() -> Vec<OverviewSearchResult> {
+    calculator::history()
+        .into_iter()
+        .enumerate()
+        .map(|(i, entry)| result(
+            &format!("calculator-history-{}", i),
+            entry.result.clone(),
+            &entry.expression,
+            OverviewSearchItemAction::Copy(entry.result)
+        ))
+        .collect()
+}
+
+/// `ans` is only set when a result is copied, so queries using it show which result it is.
+fn ans_result(query: &str) -> Option<OverviewSearchResult> {
+    let uses_ans = query.split(|c: char| !c.is_alphanumeric() && c != '_').any(|word| word == "ans");
+    if !uses_ans {
+        return None;
+    }
+
+    let last = calculator::history().into_iter().next()?;
+    Some(result(
+        "calculator-ans",
+        last.result.clone(),
+        &format!("ans, the last copied result ({})", last.expression),
+        OverviewSearchItemAction::Copy(last.result)
+    ))
+}
+
+fn message_results(calculation: &CalculationResult) -> Vec<OverviewSearchResult> {
+    calculation.messages.iter()
+        .enumerate()
+        .map(|(i, message)| {
+            let (subtitle, icon) = if message.is_error { ("Error", "dialog-error") } else { ("Warning", "dialog-warning") };
+            OverviewSearchResult::new(
+                format!("calculator-message-{}", i),
+                message.text.clone(),
+                Some(subtitle.to_owned()),
+                icon.to_owned(),
+                "copy".to_owned(),
+                OverviewSearchItemAction::Copy(message.text.clone()),
+                None
+            )
+        })
+        .collect()
+}
+
+/// The result in every form libqalculate gave, after the default one.
+fn alternative_results(calculation: &CalculationResult) -> Vec<OverviewSearchResult> {
+    [
+        ("calculator-result-exact", "Exact", &calculation.exact),
+        ("calculator-result-decimal", "Decimal", &calculation.decimal),
+        ("calculator-result-units", "Simplified units", &calculation.simplified_units),
+        ("calculator-result-hexadecimal", "Hexadecimal", &calculation.hexadecimal),
+        ("calculator-result-binary", "Binary", &calculation.binary),
+        ("calculator-result-octal", "Octal", &calculation.octal)
+    ]
+        .into_iter()
+        .filter(|(_, _, form)| !form.is_empty())
+        .map(|(id, subtitle, form)| result(id, form.clone(), subtitle, OverviewSearchItemAction::Copy(form.clone())))
+        .collect()
+}
+
+/// `name := expression` previews the value, which is stored once the result is picked.
+fn assignment_results(name: &str, expression: &str) -> Vec<OverviewSearchResult> {
+    let calculation = calculator::calculate(expression);
+    let mut results = Vec::new();
+
+    if !calculation.result.is_empty() {
+        // Store the exact value when there is one, so no precision is lost
+        let value = if calculation.exact.is_empty() { &calculation.result } else { &calculation.exact };
+
+        results.push(result(
+            "calculator-result",
+            format!("{} = {}", name, calculation.result),
+            &format!("Store as {}", name),
+            OverviewSearchItemAction::Tracked {
+                action: Box::new(OverviewSearchItemAction::Copy(calculation.result.clone())),
+                key: calculator::action_key(name, value),
+                on_run: calculator::store_variable
+            }
+        ));
+    }
+
+    results.extend(message_results(&calculation));
+    results
+}
+
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    if query.trim().is_empty() {
+        return history_results();
+    }
+
+    if let Some((name, expression)) = calculator::parse_assignment(query) {
+        return assignment_results(name, expression);
+    }
+
+    let calculation = calculator::calculate(query);
+    let mut results = Vec::new();
+
+    if !calculation.result.is_empty() {
+        let value = add_suffix_to_notation(&calculation.result);
+        results.push(result(
+            "calculator-result",
+            value.clone(),
+            "Math result, copy it to keep it in the history as ans",
+            OverviewSearchItemAction::Tracked {
+                action: Box::new(OverviewSearchItemAction::Copy(value)),
+                key: calculator::action_key(query, &calculation.result),
+                on_run: calculator::remember
+            }
+        ));
+
+        results.extend(alternative_results(&calculation));
+    }
+
+    results.extend(ans_result(query));
+    results.extend(message_results(&calculation));
+    results
+}
+
 pub struct OverviewCalculatorModule;
 
 impl OverviewSearchModule for OverviewCalculatorModule {
@@ -94,18 +229,26 @@ impl OverviewSearchModule for OverviewCalculatorModule {
         "calculate"
     }
 
-    fn run(&self, query: &str) -> Vec<OverviewSearchItem> {
-        let unlocalized = ffi::unlocalizeExpression(query.to_owned());
-        let result = add_suffix_to_notation(&ffi::calculateAndPrint(unlocalized, 1000));
-
-        vec![OverviewSearchItem::new(
-            "calculator-result".to_owned(),
-            result.clone(),
-            Some("Math result".to_owned()),
-            "accessories-calculator".to_owned(),
-            "copy".to_owned(),
-            OverviewSearchItemAction::Copy(result),
-            None
-        )]
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query))
+    }
+
+    fn secondary_actions(&self, query: &str, result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        if !result.id.starts_with("calculator-result") {
+            return Vec::new();
+        }
+
+        vec![
+            OverviewSearchSecondaryAction {
+                label: "Copy expression".to_owned(),
+                action: OverviewSearchItemAction::Copy(query.to_owned())
+            },
+
+            OverviewSearchSecondaryAction {
+                label: "Copy expression and result".to_owned(),
+                action: OverviewSearchItemAction::Copy(format!("{} = {}", query, result.title))
+            }
+        ]
     }
 }
\ No newline at end of file
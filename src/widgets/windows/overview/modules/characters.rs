@@ -0,0 +1,80 @@
+use crate::services::characters::{self, Character};
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction, OverviewSearchSecondaryAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+const RESULT_ID_PREFIX: &str = "character-result-";
+const RESULT_LIMIT: usize = 30;
+
+/// Runs an action, then moves the character to the front of the recents.
+fn tracked(action: OverviewSearchItemAction, character: &str) -> OverviewSearchItemAction {
+    OverviewSearchItemAction::Tracked {
+        action: Box::new(action),
+        key: character.to_owned(),
+        on_run: characters::record_use
+    }
+}
+
+/// Types the character with wtype once the overview is gone and focus is back on the window.
+fn type_action(character: &str) -> OverviewSearchItemAction {
+    let character = shlex::try_quote(character).map(|character| character.into_owned()).unwrap_or_default();
+    OverviewSearchItemAction::RunCommand(format!("sleep 0.25 && wtype -- {}", character))
+}
+
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    characters::search(query, RESULT_LIMIT)
+        .into_iter()
+        .map(|Character { character, name }| {
+            let icon = if emojis::get(&character).is_some() { "face-smile" } else { "accessories-character-map" };
+            OverviewSearchResult::new(
+                format!("{}{}", RESULT_ID_PREFIX, character),
+                character.clone(),
+                Some(name),
+                icon.to_owned(),
+                "copy".to_owned(),
+                tracked(OverviewSearchItemAction::Copy(character.clone()), &character),
+                None
+            )
+        })
+        .collect()
+}
+
+pub struct OverviewCharactersModule;
+
+impl OverviewSearchModule for OverviewCharactersModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec![":", "emoji", "char"]
+    }
+
+    fn icon(&self) -> &str {
+        "mood"
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query))
+    }
+
+    fn secondary_actions(&self, _query: &str, result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        let Some(character) = result.id.strip_prefix(RESULT_ID_PREFIX) else {
+            return Vec::new();
+        };
+
+        let mut actions = vec![OverviewSearchSecondaryAction {
+            label: "Type into focused window".to_owned(),
+            action: tracked(type_action(character), character)
+        }];
+
+        // Picking another tone copies it and makes it the default from then on
+        for (key, label, variant) in characters::skin_tone_variants(character) {
+            actions.push(OverviewSearchSecondaryAction {
+                label: format!("Use {} skin tone {}", label.to_lowercase(), variant),
+                action: OverviewSearchItemAction::Tracked {
+                    action: Box::new(tracked(OverviewSearchItemAction::Copy(variant.clone()), &variant)),
+                    key: key.to_owned(),
+                    on_run: characters::set_skin_tone
+                }
+            });
+        }
+
+        actions
+    }
+}
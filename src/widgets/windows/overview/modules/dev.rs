@@ -0,0 +1,320 @@
+use base64::{Engine as _, alphabet, engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig, general_purpose}};
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use chrono_tz::{TZ_VARIANTS, Tz};
+use uuid::Uuid;
+
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction}, modules::{OverviewSearchFuture, OverviewSearchModule}};
+
+/// Decodes base64 with or without padding, since it's often copied without.
+const BASE64_STANDARD: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent)
+);
+
+const BASE64_URL_SAFE: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::URL_SAFE,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent)
+);
+
+const DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S %:z";
+
+#[derive(Debug, Clone)]
+enum DevOperation {
+    Base64,
+    Url,
+    Jwt,
+    Uuid,
+    Timestamp,
+    Json,
+    NumberBase,
+    HtmlEntities
+}
+
+fn all_operations() -> Vec<DevOperation> {
+    use self::DevOperation::*;
+
+    vec![Jwt, Json, Timestamp, NumberBase, Uuid, Base64, Url, HtmlEntities]
+}
+
+/// One output of an operation, with a description of what it is.
+struct Conversion {
+    description: String,
+    output: String
+}
+
+impl Conversion {
+    fn new(description: impl Into<String>, output: impl Into<String>) -> Self {
+        Self {
+            description: description.into(),
+            output: output.into()
+        }
+    }
+}
+
+fn decode_base64(input: &str) -> Option<String> {
+    BASE64_STANDARD.decode(input)
+        .or_else(|_| BASE64_URL_SAFE.decode(input))
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        // Most words and numbers are valid base64 too, but rarely decode to readable text
+        .filter(|text| !text.chars().any(|c| c.is_control() && !c.is_whitespace()))
+}
+
+fn decode_jwt(input: &str) -> Vec<Conversion> {
+    let parts = input.split('.').collect::<Vec<_>>();
+    let [header, payload, _signature] = parts.as_slice() else {
+        return Vec::new();
+    };
+
+    let decode_part = |part: &str| {
+        let json = decode_base64(part)?;
+        serde_json::from_str::<serde_json::Value>(&json).ok()
+    };
+
+    let (Some(header), Some(payload)) = (decode_part(header), decode_part(payload)) else {
+        return Vec::new();
+    };
+
+    let mut payload_description = "JWT payload".to_owned();
+    if let Some(expiry) = payload.get("exp").and_then(|exp| exp.as_i64()).and_then(|exp| DateTime::from_timestamp(exp, 0)) {
+        let verb = if expiry < Utc::now() { "expired" } else { "expires" };
+        payload_description.push_str(&format!(", {} {}", verb, expiry.with_timezone(&Local).format(DATE_FORMAT)));
+    }
+
+    vec![
+        Conversion::new("JWT header", serde_json::to_string_pretty(&header).unwrap_or_default()),
+        Conversion::new(payload_description, serde_json::to_string_pretty(&payload).unwrap_or_default())
+    ]
+}
+
+/// Finds a time zone by its IANA name or just its city, e.g. "Europe/Berlin", "berlin" or "new york".
+fn find_time_zone(name: &str) -> Option<Tz> {
+    let name = name.replace(' ', "_");
+    TZ_VARIANTS.iter()
+        .find(|tz| {
+            tz.name().eq_ignore_ascii_case(&name)
+                || tz.name().rsplit('/').next().is_some_and(|city| city.eq_ignore_ascii_case(&name))
+        })
+        .copied()
+}
+
+/// Splits an optional time zone off the end of the input, e.g. "1700000000 tokyo".
+fn split_time_zone(input: &str) -> (String, Option<Tz>) {
+    let words = input.split_whitespace().collect::<Vec<_>>();
+
+    // Time zone names are at most two words, and there has to be something left to convert
+    for count in (1..words.len().min(3)).rev() {
+        let (rest, name) = words.split_at(words.len() - count);
+        if let Some(tz) = find_time_zone(&name.join(" ")) {
+            return (rest.join(" "), Some(tz));
+        }
+    }
+
+    (input.to_owned(), None)
+}
+
+fn parse_date(input: &str, tz: Option<Tz>) -> Option<DateTime<Utc>> {
+    if input.eq_ignore_ascii_case("now") {
+        return Some(Utc::now());
+    }
+
+    if let Ok(date) = DateTime::parse_from_rfc3339(input) {
+        return Some(date.with_timezone(&Utc));
+    }
+
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%dT%H:%M:%S", "%Y-%m-%d %H:%M"].iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(input, format).ok())
+        .or_else(|| NaiveDate::parse_from_str(input, "%Y-%m-%d").ok().and_then(|date| date.and_hms_opt(0, 0, 0)))?;
+
+    match tz {
+        Some(tz) => tz.from_local_datetime(&naive).earliest().map(|date| date.with_timezone(&Utc)),
+        None => Local.from_local_datetime(&naive).earliest().map(|date| date.with_timezone(&Utc)),
+    }
+}
+
+fn convert_timestamp(input: &str) -> Vec<Conversion> {
+    let (input, tz) = split_time_zone(input);
+    let input = input.as_str();
+
+    // Unix timestamps in seconds or milliseconds to dates
+    if input.len() >= 9 && input.len() <= 13 && input.chars().all(|c| c.is_ascii_digit()) {
+        let Ok(timestamp) = input.parse::<i64>() else {
+            return Vec::new();
+        };
+
+        let (date, unit) = if input.len() == 13 {
+            (DateTime::from_timestamp_millis(timestamp), "milliseconds")
+        } else {
+            (DateTime::from_timestamp(timestamp, 0), "seconds")
+        };
+
+        let Some(date) = date else {
+            return Vec::new();
+        };
+
+        let mut conversions = vec![
+            Conversion::new(format!("Unix timestamp ({}) in local time", unit), date.with_timezone(&Local).format(DATE_FORMAT).to_string()),
+            Conversion::new(format!("Unix timestamp ({}) in UTC", unit), date.to_rfc3339())
+        ];
+
+        if let Some(tz) = tz {
+            conversions.insert(0, Conversion::new(
+                format!("Unix timestamp ({}) in {}", unit, tz.name()),
+                date.with_timezone(&tz).format(DATE_FORMAT).to_string()
+            ));
+        }
+
+        return conversions;
+    }
+
+    // Dates to Unix timestamps
+    let Some(date) = parse_date(input, tz) else {
+        return Vec::new();
+    };
+
+    let zone = tz.map_or_else(|| "local time".to_owned(), |tz| tz.name().to_owned());
+    vec![
+        Conversion::new("Unix timestamp (seconds)", date.timestamp().to_string()),
+        Conversion::new("Unix timestamp (milliseconds)", date.timestamp_millis().to_string()),
+        Conversion::new(format!("Date in {}", zone), match tz {
+            Some(tz) => date.with_timezone(&tz).format(DATE_FORMAT).to_string(),
+            None => date.with_timezone(&Local).format(DATE_FORMAT).to_string(),
+        }),
+        Conversion::new("Date in UTC", date.to_rfc3339())
+    ]
+}
+
+fn convert_number_base(input: &str) -> Vec<Conversion> {
+    let digits = input.replace('_', "");
+    let lowercase = digits.to_ascii_lowercase();
+
+    let (number, base) = if let Some(hex) = lowercase.strip_prefix("0x") {
+        (u128::from_str_radix(hex, 16), 16)
+    } else if let Some(binary) = lowercase.strip_prefix("0b") {
+        (u128::from_str_radix(binary, 2), 2)
+    } else if let Some(octal) = lowercase.strip_prefix("0o") {
+        (u128::from_str_radix(octal, 8), 8)
+    } else {
+        (digits.parse::<u128>(), 10)
+    };
+
+    let Ok(number) = number else {
+        return Vec::new();
+    };
+
+    [
+        (10, "Decimal", number.to_string()),
+        (16, "Hexadecimal", format!("0x{:x}", number)),
+        (2, "Binary", format!("0b{:b}", number)),
+        (8, "Octal", format!("0o{:o}", number))
+    ]
+        .into_iter()
+        .filter(|(to_base, ..)| *to_base != base)
+        .map(|(_, description, output)| Conversion::new(description, output))
+        .collect()
+}
+
+fn convert(input: &str, operation: &DevOperation) -> Vec<Conversion> {
+    match operation {
+        DevOperation::Base64 => {
+            let mut conversions = vec![Conversion::new("Base64 encoded", general_purpose::STANDARD.encode(input))];
+
+            if input.len() >= 4 && let Some(decoded) = decode_base64(input) {
+                conversions.insert(0, Conversion::new("Base64 decoded", decoded));
+            }
+
+            conversions
+        },
+
+        DevOperation::Url => {
+            let mut conversions = Vec::new();
+
+            if input.contains(['%', '+']) && let Ok(decoded) = urlencoding::decode(&input.replace('+', " ")) {
+                conversions.push(Conversion::new("URL decoded", decoded));
+            }
+
+            conversions.push(Conversion::new("URL encoded", urlencoding::encode(input)));
+            conversions
+        },
+
+        DevOperation::Jwt => decode_jwt(input),
+
+        DevOperation::Uuid => if input.is_empty() || input.eq_ignore_ascii_case("uuid") {
+            vec![
+                Conversion::new("UUID v4", Uuid::new_v4().to_string()),
+                Conversion::new("UUID v7", Uuid::now_v7().to_string())
+            ]
+        } else {
+            Vec::new()
+        },
+
+        DevOperation::Timestamp => convert_timestamp(input),
+
+        DevOperation::Json => match serde_json::from_str::<serde_json::Value>(input) {
+            Ok(value) if value.is_object() || value.is_array() => vec![
+                Conversion::new("JSON pretty-printed", serde_json::to_string_pretty(&value).unwrap_or_default()),
+                Conversion::new("JSON minified", value.to_string())
+            ],
+
+            _ => Vec::new()
+        },
+
+        DevOperation::NumberBase => convert_number_base(input),
+
+        DevOperation::HtmlEntities => {
+            let mut conversions = Vec::new();
+
+            if input.contains('&') {
+                conversions.push(Conversion::new("HTML entities decoded", html_escape::decode_html_entities(input)));
+            }
+
+            conversions.push(Conversion::new("HTML entities encoded", html_escape::encode_safe(input)));
+            conversions
+        }
+    }
+}
+
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    let query = query.trim();
+    let mut results = Vec::new();
+
+    for operation in all_operations() {
+        let conversions = convert(query, &operation);
+
+        for (i, conversion) in conversions.into_iter().enumerate() {
+            if conversion.output.is_empty() || conversion.output == query {
+                continue;
+            }
+
+            // Multi-line outputs like pretty-printed JSON are shown on one line but copied as they are
+            let title = conversion.output.lines().map(str::trim).collect::<Vec<_>>().join(" ");
+            results.push(OverviewSearchResult::new(
+                format!("dev-result-{}-{}", format!("{:?}", operation).to_lowercase(), i),
+                title,
+                Some(conversion.description),
+                "applications-development".to_owned(),
+                "copy".to_owned(),
+                OverviewSearchItemAction::Copy(conversion.output),
+                None
+            ));
+        }
+    }
+
+    results
+}
+
+pub struct OverviewDevModule;
+
+impl OverviewSearchModule for OverviewDevModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec!["dev"]
+    }
+
+    fn icon(&self) -> &str {
+        "code"
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        Box::pin(std::future::ready(results(query)))
+    }
+}
@@ -0,0 +1,85 @@
+use std::path::Path;
+
+use crate::services::file_index;
+use crate::utils::filesystem::get_home_directory;
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction, OverviewSearchSecondaryAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+const RESULT_ID_PREFIX: &str = "file-result-";
+const RESULT_LIMIT: usize = 20;
+
+fn open_action(path: &Path) -> OverviewSearchItemAction {
+    let path = shlex::try_quote(&path.to_string_lossy()).map(|path| path.into_owned()).unwrap_or_default();
+    OverviewSearchItemAction::RunCommand(format!("xdg-open {}", path))
+}
+
+/// Shortens paths in the home directory to start with `~`.
+fn display_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    match path.strip_prefix(&get_home_directory()) {
+        Some(rest) => format!("~{}", rest),
+        None => path.into_owned(),
+    }
+}
+
+fn icon(path: &Path, is_dir: bool) -> String {
+    if is_dir {
+        return "folder".to_owned();
+    }
+
+    let (content_type, _) = gio::content_type_guess(Some(path), None);
+    gio::content_type_get_generic_icon_name(&content_type)
+        .map_or_else(|| "text-x-generic".to_owned(), |icon| icon.to_string())
+}
+
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    file_index::search(query, RESULT_LIMIT)
+        .into_iter()
+        .map(|file| OverviewSearchResult::new(
+            format!("{}{}", RESULT_ID_PREFIX, file.path.to_string_lossy()),
+            file.path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+            file.path.parent().map(display_path),
+            icon(&file.path, file.is_dir),
+            "open".to_owned(),
+            open_action(&file.path),
+            Some(query.to_owned())
+        ))
+        .collect()
+}
+
+pub struct OverviewFilesModule;
+
+impl OverviewSearchModule for OverviewFilesModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec!["file", "f", "/"]
+    }
+
+    fn icon(&self) -> &str {
+        "folder_open"
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query))
+    }
+
+    fn secondary_actions(&self, _query: &str, result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        let Some(path) = result.id.strip_prefix(RESULT_ID_PREFIX).map(Path::new) else {
+            return Vec::new();
+        };
+
+        let mut actions = Vec::new();
+        if let Some(parent) = path.parent() {
+            actions.push(OverviewSearchSecondaryAction {
+                label: "Open containing folder".to_owned(),
+                action: open_action(parent)
+            });
+        }
+
+        actions.push(OverviewSearchSecondaryAction {
+            label: "Copy path".to_owned(),
+            action: OverviewSearchItemAction::Copy(path.to_string_lossy().into_owned())
+        });
+
+        actions
+    }
+}
@@ -1,46 +1,157 @@
-use std::fmt::Write as _;
-use md5;
-use sha::{self, utils::{Digest as _, DigestExt as _}};
-use whirlpool::{Whirlpool, Digest as _};
-
-use super::super::{item::{OverviewSearchItem, OverviewSearchItemAction}, modules::OverviewSearchModule};
-
-#[derive(Debug, Clone)]
-enum Algorithm {
-    Md5,
-    Sha1,
-    Sha224,
-    Sha256,
-    Sha384,
-    Sha512,
-    Whirlpool
+use std::path::{Path, PathBuf};
+
+use crate::services::file_hashing::{self, FileHashState};
+use crate::utils::{filesystem::{expand_tilde, get_home_directory}, hashing::{self, Algorithm}};
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+fn digest_result(id: String, digest: String, description: String) -> OverviewSearchResult {
+    OverviewSearchResult::new(
+        id,
+        digest.clone(),
+        Some(description),
+        "hashit".to_owned(),
+        "copy".to_owned(),
+        OverviewSearchItemAction::Copy(digest),
+        None
+    )
 }
 
-fn all_algorithms() -> Vec<Algorithm> {
-    use self::Algorithm::*;
+fn text_results(query: &str) -> Vec<OverviewSearchResult> {
+    let mut results = Vec::new();
+
+    for algorithm in Algorithm::all() {
+        let result = hashing::digest(query.as_bytes(), algorithm);
+
+        if query != result {
+            results.push(digest_result(
+                format!("hash-result-{}", format!("{:?}", algorithm).to_lowercase()),
+                result,
+                format!("Operation: {}", algorithm.name())
+            ));
+        }
+    }
 
-    vec![Md5, Sha1, Sha224, Sha256, Sha384, Sha512, Whirlpool]
+    results
 }
 
-fn digest(input: &str, algorithm: &Algorithm) -> String {
-    match algorithm {
-        Algorithm::Md5 => format!("{:x}", md5::compute(input)),
-        Algorithm::Sha1 => sha::sha1::Sha1::default().digest(input.as_bytes()).to_hex(),
-        Algorithm::Sha224 => sha::sha224::Sha224::default().digest(input.as_bytes()).to_hex(),
-        Algorithm::Sha256 => sha::sha256::Sha256::default().digest(input.as_bytes()).to_hex(),
-        Algorithm::Sha384 => sha::sha384::Sha384::default().digest(input.as_bytes()).to_hex(),
-        Algorithm::Sha512 => sha::sha512::Sha512::default().digest(input.as_bytes()).to_hex(),
-        Algorithm::Whirlpool => {
-            let mut hasher = Whirlpool::new();
-            hasher.update(input.as_bytes());
-            hasher.finalize().to_vec().iter().fold(String::new(), |mut acc, b| {
-                write!(&mut acc, "{:02x}", b).unwrap();
-                acc
-            })
+/// `hmac <key> <message>`
+fn hmac_results(input: &str) -> Vec<OverviewSearchResult> {
+    let (key, message) = input.split_once(' ').unwrap_or((input, ""));
+
+    Algorithm::hmac().into_iter()
+        .filter_map(|algorithm| Some((algorithm, hashing::hmac(key.as_bytes(), message.as_bytes(), algorithm)?)))
+        .map(|(algorithm, result)| digest_result(
+            format!("hash-result-hmac-{}", format!("{:?}", algorithm).to_lowercase()),
+            result,
+            format!("Operation: HMAC-{}", algorithm.name())
+        ))
+        .collect()
+}
+
+fn resolve_path(path: &str) -> PathBuf {
+    let path = PathBuf::from(expand_tilde(path.trim()));
+    if path.is_relative() {
+        Path::new(&get_home_directory()).join(path)
+    } else {
+        path
+    }
+}
+
+/// Finds the file in the query, optionally followed by an expected digest to compare against.
+/// Only `file <path>` and absolute or `~` paths are files, so other text is still hashed as is.
+/// Relative paths are in the home directory.
+fn parse_file_query(query: &str) -> Option<(PathBuf, Option<String>)> {
+    let query = query.trim();
+    let (input, explicit) = match query.strip_prefix("file ") {
+        Some(input) => (input.trim(), true),
+        None if query.starts_with('/') || query.starts_with('~') => (query, false),
+        None => return None,
+    };
+
+    let path = resolve_path(input);
+    if path.is_file() {
+        return Some((path, None));
+    }
+
+    let with_expected = input.rsplit_once(' ').and_then(|(path, expected)| {
+        let path = resolve_path(path);
+        let expected = expected.to_lowercase();
+
+        (path.is_file() && !expected.is_empty() && expected.chars().all(|c| c.is_ascii_hexdigit()))
+            .then_some((path, Some(expected)))
+    });
+
+    // A missing file after `file` shows why it can't be hashed, instead of hashing the text
+    with_expected.or(explicit.then_some((path, None)))
+}
+
+fn file_results(path: &Path, expected: Option<&str>) -> Vec<OverviewSearchResult> {
+    let name = path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default();
+
+    match file_hashing::hash_file(path) {
+        FileHashState::Hashing { bytes_read, size } => vec![OverviewSearchResult::new(
+            "hash-result-file-progress".to_owned(),
+            format!("Hashing {}... {}%", name, (bytes_read * 100).checked_div(size).unwrap_or(0)),
+            Some(format!("{} of {}", glib::format_size(bytes_read), glib::format_size(size))),
+            "hashit".to_owned(),
+            "copy path".to_owned(),
+            OverviewSearchItemAction::Copy(path.to_string_lossy().into_owned()),
+            None
+        )],
+
+        FileHashState::Failed(err) => vec![OverviewSearchResult::new(
+            "hash-result-file-error".to_owned(),
+            format!("Couldn't hash {}", name),
+            Some(err),
+            "dialog-error".to_owned(),
+            "copy path".to_owned(),
+            OverviewSearchItemAction::Copy(path.to_string_lossy().into_owned()),
+            None
+        )],
+
+        FileHashState::Done(digests) => {
+            let mut results = Vec::new();
+
+            if let Some(expected) = expected {
+                let matched = digests.iter().find(|(_, digest)| digest == expected);
+                let (title, icon) = match matched {
+                    Some((algorithm, _)) => (format!("Matches the {} digest", algorithm.name()), "emblem-ok"),
+                    None => ("Doesn't match any digest".to_owned(), "dialog-error"),
+                };
+
+                results.push(OverviewSearchResult::new(
+                    "hash-result-file-comparison".to_owned(),
+                    title,
+                    Some(format!("Expected {}", expected)),
+                    icon.to_owned(),
+                    "copy".to_owned(),
+                    OverviewSearchItemAction::Copy(expected.to_owned()),
+                    None
+                ));
+            }
+
+            results.extend(digests.into_iter().map(|(algorithm, digest)| digest_result(
+                format!("hash-result-file-{}", format!("{:?}", algorithm).to_lowercase()),
+                digest,
+                format!("{} of {}", algorithm.name(), name)
+            )));
+
+            results
         }
     }
 }
 
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    if let Some(input) = query.strip_prefix("hmac ") {
+        return hmac_results(input);
+    }
+
+    match parse_file_query(query) {
+        Some((path, expected)) => file_results(&path, expected.as_deref()),
+        None => text_results(query),
+    }
+}
+
 pub struct OverviewHashingModule;
 
 impl OverviewSearchModule for OverviewHashingModule {
@@ -52,25 +163,8 @@ impl OverviewSearchModule for OverviewHashingModule {
         "tag"
     }
 
-    fn run(&self, query: &str) -> Vec<OverviewSearchItem> {
-        let mut results = Vec::new();
-
-        for algorithm in all_algorithms() {
-            let result = digest(query, &algorithm);
-            
-            if query != result {
-                results.push(OverviewSearchItem::new(
-                    format!("hash-result-{}", format!("{:?}", algorithm).to_lowercase()),
-                    result.clone(),
-                    Some(format!("Operation: {}", format!("{:?}", algorithm).to_lowercase())),
-                    "hashit".to_owned(),
-                    "copy".to_owned(),
-                    OverviewSearchItemAction::Copy(result),
-                    None
-                ));
-            }
-        }
-
-        results
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query))
     }
-}
\ No newline at end of file
+}
@@ -1,14 +1,72 @@
-use super::item::OverviewSearchItem;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+
+use super::item::{OverviewSearchResult, OverviewSearchSecondaryAction};
 
 pub mod calculator;
 pub mod text;
 pub mod terminal;
 pub mod hashing;
+pub mod plugin;
+pub mod window_switcher;
+pub mod files;
+pub mod characters;
+pub mod dev;
+pub mod browser;
+pub mod ssh;
+pub mod recent_files;
+
+/// How long a module gets to answer a query, unless it asks for something else.
+pub const DEFAULT_DEADLINE: Duration = Duration::from_millis(1500);
 
-pub trait OverviewSearchModule {
+pub type OverviewSearchFuture = Pin<Box<dyn Future<Output = Vec<OverviewSearchResult>> + Send + 'static>>;
+
+pub trait OverviewSearchModule: Send + Sync {
     fn extensions(&self) -> Vec<&str>;
     fn icon(&self) -> &str;
-    fn run(&self, query: &str) -> Vec<OverviewSearchItem>;
+
+    /// Results that arrive after the deadline are dropped.
+    fn deadline(&self) -> Duration {
+        DEFAULT_DEADLINE
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture;
+
+    /// Alternative actions for one of this module's results, listed in the result's menu.
+    fn secondary_actions(&self, _query: &str, _result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        Vec::new()
+    }
+}
+
+/// Runs a module on the query, without its extension, and adds the module's secondary
+/// actions to the results.
+pub fn run_module(module: Box<dyn OverviewSearchModule>, query: &str) -> OverviewSearchFuture {
+    let query = input_without_extensions(module.as_ref(), query);
+    let future = module.run(&query);
+
+    Box::pin(async move {
+        let mut results = future.await;
+        for result in &mut results {
+            let secondary_actions = module.secondary_actions(&query, result);
+            result.secondary_actions.extend(secondary_actions);
+        }
+
+        results
+    })
+}
+
+/// Runs synchronous module work on the blocking thread pool so it can't freeze typing.
+pub fn run_blocking<F>(func: F) -> OverviewSearchFuture
+where
+    F: FnOnce() -> Vec<OverviewSearchResult> + Send + 'static,
+{
+    Box::pin(async move {
+        tokio::task::spawn_blocking(func).await.unwrap_or_else(|err| {
+            error!(%err, "Overview search module panicked");
+            Vec::new()
+        })
+    })
 }
 
 pub fn validate_input(module: &dyn OverviewSearchModule, query: &str) -> bool {
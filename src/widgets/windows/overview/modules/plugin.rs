@@ -0,0 +1,61 @@
+use std::time::Duration;
+
+use crate::config::structs::OverviewPluginConfig;
+use crate::services::search_plugins::{self, PluginAction};
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+fn results(config: &OverviewPluginConfig, query: &str) -> Vec<OverviewSearchResult> {
+    let results = match search_plugins::query(config, query) {
+        Ok(results) => results,
+        Err(err) => {
+            warn!(plugin = %config.name, %err, "Search plugin failed");
+            return Vec::new();
+        }
+    };
+
+    results.into_iter()
+        .enumerate()
+        .map(|(i, result)| {
+            let (action_text, action) = match result.action {
+                PluginAction::Launch(command) => ("launch", OverviewSearchItemAction::Launch(command)),
+                PluginAction::RunCommand(command) => ("run", OverviewSearchItemAction::RunCommand(command)),
+                PluginAction::Copy(text) => ("copy", OverviewSearchItemAction::Copy(text)),
+            };
+
+            OverviewSearchResult::new(
+                format!("plugin-{}-{}", config.name, i),
+                result.title,
+                result.subtitle,
+                result.icon.unwrap_or_else(|| "system-search".to_owned()),
+                result.action_text.unwrap_or_else(|| action_text.to_owned()),
+                action,
+                None
+            )
+        })
+        .collect()
+}
+
+pub struct OverviewPluginModule {
+    pub config: OverviewPluginConfig,
+}
+
+impl OverviewSearchModule for OverviewPluginModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec![&self.config.prefix]
+    }
+
+    fn icon(&self) -> &str {
+        &self.config.icon
+    }
+
+    fn deadline(&self) -> Duration {
+        // The plugin enforces its own timeout, leave some room for starting it
+        Duration::from_millis(self.config.timeout_ms + 250)
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let config = self.config.clone();
+        let query = query.to_owned();
+        run_blocking(move || results(&config, &query))
+    }
+}
@@ -0,0 +1,87 @@
+use std::path::Path;
+
+use crate::services::recent_files;
+use crate::utils::filesystem::get_home_directory;
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction, OverviewSearchSecondaryAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+const RESULT_ID_PREFIX: &str = "recent-file-result-";
+const RESULT_LIMIT: usize = 20;
+
+fn open_action(path: &Path) -> OverviewSearchItemAction {
+    let path = shlex::try_quote(&path.to_string_lossy()).map(|path| path.into_owned()).unwrap_or_default();
+    OverviewSearchItemAction::RunCommand(format!("xdg-open {}", path))
+}
+
+/// Shortens paths in the home directory to start with `~`.
+fn display_path(path: &Path) -> String {
+    let path = path.to_string_lossy();
+    match path.strip_prefix(&get_home_directory()) {
+        Some(rest) => format!("~{}", rest),
+        None => path.into_owned(),
+    }
+}
+
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    recent_files::search(query, RESULT_LIMIT)
+        .into_iter()
+        .map(|file| {
+            let directory = file.path.parent().map(display_path).unwrap_or_default();
+            let description = match &file.application {
+                Some(application) => format!("{} · Opened with {}", directory, application.name),
+                None => directory,
+            };
+
+            OverviewSearchResult::new(
+                format!("{}{}", RESULT_ID_PREFIX, file.path.to_string_lossy()),
+                file.name(),
+                Some(description),
+                file.icon(),
+                "reopen".to_owned(),
+                OverviewSearchItemAction::RunCommand(file.reopen_command()),
+                Some(query.to_owned())
+            )
+        })
+        .collect()
+}
+
+pub struct OverviewRecentFilesModule;
+
+impl OverviewSearchModule for OverviewRecentFilesModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec!["recent", "r"]
+    }
+
+    fn icon(&self) -> &str {
+        "history"
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query))
+    }
+
+    fn secondary_actions(&self, _query: &str, result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        let Some(path) = result.id.strip_prefix(RESULT_ID_PREFIX).map(Path::new) else {
+            return Vec::new();
+        };
+
+        let mut actions = vec![OverviewSearchSecondaryAction {
+            label: "Open with default application".to_owned(),
+            action: open_action(path)
+        }];
+
+        if let Some(parent) = path.parent() {
+            actions.push(OverviewSearchSecondaryAction {
+                label: "Open containing folder".to_owned(),
+                action: open_action(parent)
+            });
+        }
+
+        actions.push(OverviewSearchSecondaryAction {
+            label: "Copy path".to_owned(),
+            action: OverviewSearchItemAction::Copy(path.to_string_lossy().into_owned())
+        });
+
+        actions
+    }
+}
@@ -0,0 +1,76 @@
+use crate::services::ssh_hosts::{self, SshHost, SshHostSource};
+use crate::utils::process;
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction, OverviewSearchSecondaryAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+const RESULT_ID_PREFIX: &str = "ssh-result-";
+const RESULT_LIMIT: usize = 20;
+
+fn description(host: &SshHost) -> String {
+    let mut target = host.hostname.clone().unwrap_or_else(|| host.name.clone());
+    if let Some(user) = &host.user {
+        target = format!("{}@{}", user, target);
+    }
+
+    if let Some(port) = host.port {
+        target = format!("{}:{}", target, port);
+    }
+
+    let source = match host.source {
+        SshHostSource::Config => "SSH config",
+        SshHostSource::Custom => "Custom host",
+        SshHostSource::KnownHosts => "Known host",
+    };
+
+    match host.connections {
+        0 => format!("{} · {}", target, source),
+        1 => format!("{} · {} · 1 connection", target, source),
+        connections => format!("{} · {} · {} connections", target, source, connections),
+    }
+}
+
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    ssh_hosts::search(query, RESULT_LIMIT)
+        .into_iter()
+        .map(|host| OverviewSearchResult::new(
+            format!("{}{}", RESULT_ID_PREFIX, host.command()),
+            host.name.clone(),
+            Some(description(&host)),
+            "network-server".to_owned(),
+            "connect".to_owned(),
+            OverviewSearchItemAction::Tracked {
+                action: Box::new(OverviewSearchItemAction::Launch(process::terminal_command(&host.command()))),
+                key: host.key(),
+                on_run: ssh_hosts::record_connection
+            },
+            Some(query.to_owned())
+        ))
+        .collect()
+}
+
+pub struct OverviewSshModule;
+
+impl OverviewSearchModule for OverviewSshModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec!["ssh"]
+    }
+
+    fn icon(&self) -> &str {
+        "dns"
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query))
+    }
+
+    fn secondary_actions(&self, _query: &str, result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        let Some(command) = result.id.strip_prefix(RESULT_ID_PREFIX) else {
+            return Vec::new();
+        };
+
+        vec![OverviewSearchSecondaryAction {
+            label: "Copy command".to_owned(),
+            action: OverviewSearchItemAction::Copy(command.to_owned())
+        }]
+    }
+}
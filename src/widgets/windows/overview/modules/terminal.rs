@@ -1,4 +1,5 @@
-use super::super::{item::{OverviewSearchItem, OverviewSearchItemAction}, modules::OverviewSearchModule};
+use crate::utils::process;
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction}, modules::{OverviewSearchFuture, OverviewSearchModule}};
 
 pub struct OverviewTerminalModule;
 
@@ -73,6 +74,36 @@ const COMMON_FOREGROUND_COMMANDS: &[&str] = &[
     "mutt", "neomutt",
 ];
 
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    let items = vec![
+        OverviewSearchResult::new(
+            "command-run-in-background".to_owned(),
+            query.to_owned(),
+            Some("Run in background".to_owned()),
+            "utilities-terminal".to_owned(),
+            "run".to_owned(),
+            OverviewSearchItemAction::RunCommand(query.to_owned()),
+            None
+        ),
+
+        OverviewSearchResult::new(
+            "command-run-in-terminal".to_owned(),
+            query.to_owned(),
+            Some("Run in terminal".to_owned()),
+            "utilities-terminal".to_owned(),
+            "run".to_owned(),
+            OverviewSearchItemAction::Launch(process::terminal_command(query)),
+            None
+        )
+    ];
+
+    if COMMON_FOREGROUND_COMMANDS.iter().any(|&cmd| query.starts_with(cmd)) {
+        items.into_iter().rev().collect()
+    } else {
+        items
+    }
+}
+
 impl OverviewSearchModule for OverviewTerminalModule {
     fn extensions(&self) -> Vec<&str> {
         vec!["terminal", "term", "cmd", "t", "$"]
@@ -82,33 +113,7 @@ impl OverviewSearchModule for OverviewTerminalModule {
         "terminal"
     }
 
-    fn run(&self, query: &str) -> Vec<OverviewSearchItem> {
-        let items = vec![
-            OverviewSearchItem::new(
-                "command-run-in-background".to_owned(),
-                query.to_owned(),
-                Some("Run in background".to_owned()),
-                "utilities-terminal".to_owned(),
-                "run".to_owned(),
-                OverviewSearchItemAction::RunCommand(query.to_owned()),
-                None
-            ),
-
-            OverviewSearchItem::new(
-                "command-run-in-terminal".to_owned(),
-                query.to_owned(),
-                Some("Run in terminal".to_owned()),
-                "utilities-terminal".to_owned(),
-                "run".to_owned(),
-                OverviewSearchItemAction::Launch(format!("foot fish -C \"{}\"", query.replace('"', "\\\""))),
-                None
-            )
-        ];
-
-        if COMMON_FOREGROUND_COMMANDS.iter().any(|&cmd| query.starts_with(cmd)) {
-            items.into_iter().rev().collect()
-        } else {
-            items
-        }
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        Box::pin(std::future::ready(results(query)))
     }
 }
\ No newline at end of file
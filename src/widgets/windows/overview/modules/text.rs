@@ -1,7 +1,7 @@
 use std::fmt::Write as _;
 use rand::seq::SliceRandom as _;
 
-use super::super::{item::{OverviewSearchItem, OverviewSearchItemAction}, modules::OverviewSearchModule};
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction}, modules::{OverviewSearchFuture, OverviewSearchModule}};
 
 #[derive(Debug, Clone)]
 enum TextOperation {
@@ -129,6 +129,28 @@ fn digest(input: &str, operation: &TextOperation) -> String {
     }
 }
 
+fn results(query: &str) -> Vec<OverviewSearchResult> {
+    let mut results = Vec::new();
+
+    for operation in all_operations() {
+        let result = digest(query, &operation);
+        
+        if query != result {
+            results.push(OverviewSearchResult::new(
+                format!("text-result-{}", format!("{:?}", operation).to_lowercase()),
+                result.clone(),
+                Some(format!("Operation: {}", format!("{:?}", operation).to_lowercase())),
+                "text-x-generic".to_owned(),
+                "copy".to_owned(),
+                OverviewSearchItemAction::Copy(result),
+                None
+            ));
+        }
+    }
+
+    results
+}
+
 pub struct OverviewTextModule;
 
 impl OverviewSearchModule for OverviewTextModule {
@@ -140,25 +162,7 @@ impl OverviewSearchModule for OverviewTextModule {
         "text_compare"
     }
 
-    fn run(&self, query: &str) -> Vec<OverviewSearchItem> {
-        let mut results = Vec::new();
-
-        for operation in all_operations() {
-            let result = digest(query, &operation);
-            
-            if query != result {
-                results.push(OverviewSearchItem::new(
-                    format!("text-result-{}", format!("{:?}", operation).to_lowercase()),
-                    result.clone(),
-                    Some(format!("Operation: {}", format!("{:?}", operation).to_lowercase())),
-                    "text-x-generic".to_owned(),
-                    "copy".to_owned(),
-                    OverviewSearchItemAction::Copy(result),
-                    None
-                ));
-            }
-        }
-
-        results
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        Box::pin(std::future::ready(results(query)))
     }
 }
\ No newline at end of file
@@ -0,0 +1,132 @@
+use freedesktop_desktop_entry::get_languages_from_env;
+use hyprland::data::{Clients, Monitors};
+use hyprland::shared::HyprData as _;
+
+use crate::services::apps;
+use crate::services::hyprland::WindowAction;
+use crate::utils::matching;
+use super::super::{item::{OverviewSearchResult, OverviewSearchItemAction, OverviewSearchSecondaryAction}, modules::{OverviewSearchFuture, OverviewSearchModule, run_blocking}};
+
+/// How many windows are mixed into the default results.
+const BLENDED_LIMIT: usize = 3;
+
+fn secondary_actions(address: &str) -> Vec<OverviewSearchSecondaryAction> {
+    [
+        ("Close window", WindowAction::Close),
+        ("Move to current workspace", WindowAction::MoveToActiveWorkspace),
+        ("Toggle floating", WindowAction::ToggleFloating)
+    ]
+        .into_iter()
+        .map(|(label, action)| OverviewSearchSecondaryAction {
+            label: label.to_owned(),
+            action: OverviewSearchItemAction::Window {
+                address: address.to_owned(),
+                action
+            }
+        })
+        .collect()
+}
+
+/// The open windows matching the query, best match first. An empty query lists every
+/// window, most recently focused first.
+fn results(query: &str, limit: usize) -> Vec<OverviewSearchResult> {
+    let (Ok(clients), Ok(monitors)) = (Clients::get(), Monitors::get()) else {
+        return Vec::new();
+    };
+
+    let locales = get_languages_from_env();
+    let query = query.trim();
+
+    let mut windows = clients.into_iter()
+        .filter(|client| client.mapped && !(client.title.is_empty() && client.class.is_empty()))
+        .filter_map(|client| {
+            let entry = apps::get_from_class(&client.class);
+            let app_name = entry.as_ref()
+                .and_then(|entry| entry.name(&locales).map(|name| name.to_string()))
+                .unwrap_or_else(|| client.class.clone());
+
+            let score = if query.is_empty() {
+                0
+            } else {
+                [&client.title, &client.class, &app_name]
+                    .into_iter()
+                    .filter_map(|text| matching::fuzzy_score(text, query))
+                    .map(|m| m.score)
+                    .max()?
+            };
+
+            Some((score, client, entry, app_name))
+        })
+        .collect::<Vec<_>>();
+
+    windows.sort_by(|(a_score, a, ..), (b_score, b, ..)| {
+        b_score.cmp(a_score).then(a.focus_history_id.cmp(&b.focus_history_id))
+    });
+
+    windows.into_iter()
+        .take(limit)
+        .map(|(_, client, entry, app_name)| {
+            let address = client.address.to_string();
+            let monitor = monitors.iter()
+                .find(|monitor| Some(monitor.id) == client.monitor)
+                .map_or_else(|| "unknown monitor".to_owned(), |monitor| monitor.name.clone());
+
+            let icon = entry.as_ref()
+                .and_then(|entry| entry.icon().map(|icon| icon.to_owned()))
+                .unwrap_or_else(|| client.class.to_lowercase());
+
+            let title = if client.title.is_empty() { app_name.clone() } else { client.title.clone() };
+
+            OverviewSearchResult::new(
+                format!("window-result-{}", address),
+                title,
+                Some(format!("{} · Workspace {} · {}", app_name, client.workspace.name, monitor)),
+                icon,
+                "focus".to_owned(),
+                OverviewSearchItemAction::Window {
+                    address: address.clone(),
+                    action: WindowAction::Focus
+                },
+                Some(query.to_owned())
+            )
+        })
+        .collect()
+}
+
+/// The few best matching windows, for mixing into the default results.
+pub fn blended_results(query: &str) -> OverviewSearchFuture {
+    let query = query.to_owned();
+    run_blocking(move || {
+        results(&query, BLENDED_LIMIT)
+            .into_iter()
+            .map(|result| {
+                let secondary_actions = OverviewWindowSwitcherModule.secondary_actions(&query, &result);
+                result.with_secondary_actions(secondary_actions)
+            })
+            .collect()
+    })
+}
+
+pub struct OverviewWindowSwitcherModule;
+
+impl OverviewSearchModule for OverviewWindowSwitcherModule {
+    fn extensions(&self) -> Vec<&str> {
+        vec!["win", "w"]
+    }
+
+    fn icon(&self) -> &str {
+        "select_window"
+    }
+
+    fn run(&self, query: &str) -> OverviewSearchFuture {
+        let query = query.to_owned();
+        run_blocking(move || results(&query, usize::MAX))
+    }
+
+    fn secondary_actions(&self, _query: &str, result: &OverviewSearchResult) -> Vec<OverviewSearchSecondaryAction> {
+        match &result.action {
+            OverviewSearchItemAction::Window { address, .. } => secondary_actions(address),
+            _ => Vec::new()
+        }
+    }
+}
@@ -0,0 +1,297 @@
+// Runs overview searches without blocking typing. Every source, meaning each matched module
+// or the applications and open windows when no module prefix matches, runs on its own
+// with a deadline, and its results are merged into the list as soon as they arrive. A new
+// query cancels everything still running for the previous one.
+
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use freedesktop_desktop_entry::{DesktopEntry, get_languages_from_env};
+use urlencoding::encode;
+
+use crate::config::{read_config, structs::SearchEngineConfig};
+use crate::services::{apps, hyprland::HYPRLAND};
+use super::item::{OverviewSearchItemAction, OverviewSearchResult, OverviewSearchSecondaryAction};
+use super::list::OverviewSearchList;
+use super::modules::{
+    DEFAULT_DEADLINE,
+    OverviewSearchFuture,
+    OverviewSearchModule,
+    run_blocking,
+    run_module,
+    validate_input,
+    window_switcher,
+};
+
+fn application_results(query: &str) -> OverviewSearchFuture {
+    let query = query.to_owned();
+    run_blocking(move || {
+        // Filter and weigh the applications based on the query
+        let locales = get_languages_from_env();
+        let mut workspaces = HYPRLAND.workspaces.get_cloned()
+            .map(|workspaces| workspaces.into_iter()
+                .filter(|workspace| workspace.id > 0)
+                .map(|workspace| workspace.id)
+                .collect::<Vec<_>>()
+            )
+            .unwrap_or_default();
+        workspaces.sort_unstable();
+
+        apps::query_desktops(&query)
+            .into_iter()
+            .take(8)
+            .map(|weighted| {
+                let entry = &weighted.entry;
+                let name = entry.name(&locales).unwrap_or_default().to_string();
+                let icon = entry.icon().map(|icon| icon.to_owned()).unwrap_or_default();
+
+                match weighted.action {
+                    Some(action) => OverviewSearchResult::new(
+                        "application-result".to_owned(),
+                        apps::action_title(&name, &action.name),
+                        None,
+                        icon,
+                        "launch".to_owned(),
+                        OverviewSearchItemAction::LaunchTracked { command: action.exec, key: action.key },
+                        Some(query.clone())
+                    ),
+
+                    None => OverviewSearchResult::new(
+                        "application-result".to_owned(),
+                        name,
+                        None,
+                        icon,
+                        "launch".to_owned(),
+                        OverviewSearchItemAction::Launch(entry.exec().unwrap_or_default().to_owned()),
+                        Some(query.clone())
+                    ).with_secondary_actions(application_secondary_actions(entry, &workspaces))
+                }
+            })
+            .collect()
+    })
+}
+
+/// The desktop's own actions, then launching on each open workspace, then opening the
+/// .desktop file itself.
+fn application_secondary_actions(entry: &DesktopEntry, workspaces: &[i32]) -> Vec<OverviewSearchSecondaryAction> {
+    let exec = entry.exec().unwrap_or_default();
+    let mut actions = apps::desktop_actions(entry)
+        .into_iter()
+        .map(|action| OverviewSearchSecondaryAction {
+            label: action.name,
+            action: OverviewSearchItemAction::LaunchTracked {
+                command: action.exec,
+                key: action.key
+            }
+        })
+        .collect::<Vec<_>>();
+
+    actions.extend(workspaces.iter().map(|workspace| OverviewSearchSecondaryAction {
+        label: format!("Launch on workspace {}", workspace),
+        action: OverviewSearchItemAction::LaunchOnWorkspace {
+            command: exec.to_owned(),
+            workspace: *workspace
+        }
+    }));
+
+    if let Ok(path) = shlex::try_quote(&entry.path.to_string_lossy()) {
+        actions.push(OverviewSearchSecondaryAction {
+            label: "Open .desktop file".to_owned(),
+            action: OverviewSearchItemAction::RunCommand(format!("xdg-open {}", path))
+        });
+    }
+
+    actions
+}
+
+fn web_search_action(engine: &SearchEngineConfig, query: &str) -> OverviewSearchItemAction {
+    let url = if engine.url.contains("{query}") {
+        engine.url.replace("{query}", &encode(query))
+    } else {
+        format!("{}{}", engine.url, encode(query))
+    };
+
+    let url = shlex::try_quote(&url).map(|url| url.into_owned()).unwrap_or_default();
+    OverviewSearchItemAction::RunCommand(format!("xdg-open {}", url))
+}
+
+/// Splits a `!bang` naming one of the engines off the start or end of the query, returning
+/// the engine's index and the rest of the query.
+fn parse_bang(query: &str, engines: &[SearchEngineConfig]) -> Option<(usize, String)> {
+    let words = query.split_whitespace().collect::<Vec<_>>();
+    let find_engine = |word: &str| {
+        let bang = word.strip_prefix('!')?;
+        engines.iter().position(|engine| engine.bang.as_deref().is_some_and(|b| b.eq_ignore_ascii_case(bang)))
+    };
+
+    if let Some(index) = words.first().and_then(|word| find_engine(word)) {
+        return Some((index, words[1..].join(" ")));
+    }
+
+    if let Some(index) = words.last().and_then(|word| find_engine(word)) {
+        return Some((index, words[..words.len() - 1].join(" ")));
+    }
+
+    None
+}
+
+fn has_bang(query: &str) -> bool {
+    parse_bang(query, &read_config().search.engines).is_some()
+}
+
+fn web_search_result(query: &str) -> Option<OverviewSearchResult> {
+    let config = read_config().search.clone();
+    let default_engine = config.engines.iter()
+        .position(|engine| engine.name.eq_ignore_ascii_case(&config.default_engine))
+        .unwrap_or(0);
+
+    let (engine, query, subtitle) = match parse_bang(query, &config.engines) {
+        Some((engine, query)) => (engine, query, format!("Search with {}", config.engines[engine].name)),
+        None => (default_engine, query.to_owned(), "Search the web".to_owned()),
+    };
+
+    let main_engine = config.engines.get(engine)?;
+    Some(OverviewSearchResult::new(
+        "web-search".to_owned(),
+        query.clone(),
+        Some(subtitle),
+        "search".to_owned(),
+        "search".to_owned(),
+        web_search_action(main_engine, &query),
+        None
+    ).with_secondary_actions(
+        config.engines.iter()
+            .enumerate()
+            .filter(|(i, _)| *i != engine)
+            .map(|(_, engine)| OverviewSearchSecondaryAction {
+                label: format!("Search with {}", engine.name),
+                action: web_search_action(engine, &query)
+            })
+            .collect()
+    ))
+}
+
+pub struct OverviewSearch {
+    list: Rc<RefCell<OverviewSearchList>>,
+    generation: Cell<u64>,
+    tasks: RefCell<Vec<tokio::task::AbortHandle>>,
+
+    // The latest results of each source, in display order. Sources that haven't answered
+    // the current query yet keep their previous results so the list doesn't flicker.
+    sources: RefCell<Vec<(String, Vec<OverviewSearchResult>)>>,
+    fallback: RefCell<Option<OverviewSearchResult>>,
+}
+
+impl OverviewSearch {
+    pub fn new(list: Rc<RefCell<OverviewSearchList>>) -> Rc<Self> {
+        Rc::new(Self {
+            list,
+            generation: Cell::new(0),
+            tasks: RefCell::new(Vec::new()),
+            sources: RefCell::new(Vec::new()),
+            fallback: RefCell::new(None),
+        })
+    }
+
+    /// Cancels the searches that are still running.
+    pub fn cancel(&self) {
+        self.generation.set(self.generation.get() + 1);
+        for task in self.tasks.borrow_mut().drain(..) {
+            task.abort();
+        }
+    }
+
+    pub fn search(self: &Rc<Self>, query: &str, modules: Vec<Box<dyn OverviewSearchModule>>) {
+        self.cancel();
+        let generation = self.generation.get();
+
+        let matched_modules = modules.into_iter()
+            .filter(|module| validate_input(module.as_ref(), query))
+            .collect::<Vec<_>>();
+
+        // Queries with a bang only go to the web search
+        let sources = if has_bang(query) {
+            Vec::new()
+        } else if matched_modules.is_empty() {
+            vec![
+                ("applications".to_owned(), application_results(query), DEFAULT_DEADLINE),
+                ("windows".to_owned(), window_switcher::blended_results(query), DEFAULT_DEADLINE)
+            ]
+        } else {
+            matched_modules.into_iter()
+                .map(|module| {
+                    let key = format!("{}:{}", module.icon(), module.extensions().join(","));
+                    let deadline = module.deadline();
+                    (key, run_module(module, query), deadline)
+                })
+                .collect()
+        };
+
+        {
+            let previous = self.sources.take();
+            *self.sources.borrow_mut() = sources.iter()
+                .map(|(key, _, _)| {
+                    let results = previous.iter()
+                        .find(|(previous_key, _)| previous_key == key)
+                        .map(|(_, results)| results.clone())
+                        .unwrap_or_default();
+
+                    (key.clone(), results)
+                })
+                .collect();
+        }
+
+        // Web search as final fallback
+        *self.fallback.borrow_mut() = web_search_result(query);
+        self.apply();
+
+        for (key, future, deadline) in sources {
+            let handle = tokio::spawn(tokio::time::timeout(deadline, future));
+            self.tasks.borrow_mut().push(handle.abort_handle());
+
+            let search = self.clone();
+            glib::spawn_future_local(clone!(
+                #[weak] search,
+                async move {
+                    let results = match handle.await {
+                        Ok(Ok(results)) => results,
+
+                        Ok(Err(_)) => {
+                            warn!(source = %key, "Overview search source missed its deadline");
+                            Vec::new()
+                        },
+
+                        Err(err) if err.is_cancelled() => return,
+
+                        Err(err) => {
+                            error!(source = %key, %err, "Overview search source failed");
+                            Vec::new()
+                        },
+                    };
+
+                    if search.generation.get() != generation {
+                        return;
+                    }
+
+                    if let Some((_, source_results)) = search.sources.borrow_mut()
+                        .iter_mut()
+                        .find(|(source_key, _)| *source_key == key)
+                    {
+                        *source_results = results;
+                    }
+
+                    search.apply();
+                }
+            ));
+        }
+    }
+
+    fn apply(&self) {
+        let mut results = self.sources.borrow()
+            .iter()
+            .flat_map(|(_, results)| results.iter().cloned())
+            .collect::<Vec<_>>();
+
+        results.extend(self.fallback.borrow().iter().cloned());
+        self.list.borrow_mut().update(&results);
+    }
+}
@@ -7,6 +7,7 @@ use crate::services::apps;
 
 pub mod frequent;
 pub mod recent;
+pub mod recent_files;
 
 pub fn build_window(label: &str) -> (gtk::Box, gtk::Box) {
     let widget = gtk::Box::new(gtk::Orientation::Vertical, 12);
@@ -29,9 +30,23 @@ pub fn build_window(label: &str) -> (gtk::Box, gtk::Box) {
     (widget, children)
 }
 
+/// Makes a button for a tracked command, which is either a desktop entry's command or the
+/// key of one of its desktop actions.
 pub fn make_item_from_command(command: &str) -> Option<gtk::Button> {
     let locales = get_languages_from_env();
-    let entry = apps::get_from_command(command)?;
+    let (entry, label, exec) = match apps::get_from_action_key(command) {
+        Some((entry, action)) => {
+            let name = entry.name(&locales).map(|name| name.to_string()).unwrap_or_default();
+            (entry, apps::action_title(&name, &action.name), action.exec)
+        },
+
+        None => {
+            let entry = apps::get_from_command(command)?;
+            let label = entry.name(&locales).map_or_else(|| "Unnamed".to_owned(), |name| name.to_string());
+            (entry, label, command.to_owned())
+        }
+    };
+
     let icon_pixbuf = pixbuf::get_pixbuf_or_fallback(entry.icon().unwrap_or_default(), "emote-love");
 
     view! {
@@ -40,7 +55,7 @@ pub fn make_item_from_command(command: &str) -> Option<gtk::Button> {
             connect_clicked: {
                 let command = command.to_owned();
                 move |_| {
-                    apps::launch_and_track(&command);
+                    apps::launch_and_track_as(&exec, &command);
 
                     // Hide the overview after clicking an item
                     windows::hide("overview");
@@ -59,7 +74,7 @@ pub fn make_item_from_command(command: &str) -> Option<gtk::Button> {
                 },
 
                 gtk::Label {
-                    set_label: entry.name(&locales).as_ref().map_or("Unnamed", |v| v),
+                    set_label: &label,
                     set_css_classes: &["overview-window-button-label"],
                     set_halign: gtk::Align::Start,
                     set_valign: gtk::Align::Center,
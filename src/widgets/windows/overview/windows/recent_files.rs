@@ -0,0 +1,87 @@
+use gtk::prelude::*;
+use relm4::RelmIterChildrenExt as _;
+
+use crate::pixbuf;
+use crate::services::recent_files::{self, RecentFile};
+use super::super::item::{self, OverviewSearchItemAction};
+
+#[derive(Debug, Clone)]
+pub struct OverviewRecentFilesWindow {
+    pub widget: gtk::Box,
+    pub children: gtk::Box,
+}
+
+fn make_item(file: &RecentFile) -> gtk::Button {
+    let icon_pixbuf = pixbuf::get_pixbuf_or_fallback(&file.icon(), "text-x-generic");
+    let application = file.application.as_ref()
+        .map_or_else(|| "Opened recently".to_owned(), |application| format!("Opened with {}", application.name));
+
+    view! {
+        button = gtk::Button {
+            set_css_classes: &["overview-window-button"],
+            set_tooltip_text: Some(&file.path.to_string_lossy()),
+            connect_clicked: {
+                // Run through a shell like the search results, as `process::launch` would strip
+                // percent-encoded characters from the file's URI
+                let action = OverviewSearchItemAction::RunCommand(file.reopen_command());
+                move |_| item::run_action(&action)
+            },
+
+            gtk::Box {
+                set_css_classes: &["overview-window-button-box"],
+                set_orientation: gtk::Orientation::Horizontal,
+                set_hexpand: true,
+
+                gtk::Image {
+                    set_from_pixbuf: icon_pixbuf.as_ref(),
+                    set_pixel_size: 24,
+                    set_css_classes: &["overview-window-button-icon"],
+                },
+
+                gtk::Box {
+                    set_orientation: gtk::Orientation::Vertical,
+                    set_valign: gtk::Align::Center,
+
+                    gtk::Label {
+                        set_label: &file.name(),
+                        set_css_classes: &["overview-window-button-label"],
+                        set_halign: gtk::Align::Start,
+                        set_ellipsize: gtk::pango::EllipsizeMode::Middle,
+                        set_max_width_chars: 32,
+                    },
+
+                    gtk::Label {
+                        set_label: &application,
+                        set_css_classes: &["overview-window-button-sublabel"],
+                        set_halign: gtk::Align::Start,
+                        set_ellipsize: gtk::pango::EllipsizeMode::End,
+                        set_max_width_chars: 32,
+                    }
+                }
+            }
+        }
+    }
+
+    button
+}
+
+impl OverviewRecentFilesWindow {
+    pub fn new() -> Self {
+        let (widget, children) = super::build_window("Recent Files");
+
+        Self {
+            widget,
+            children
+        }
+    }
+
+    pub fn update(&self) {
+        self.children.iter_children().for_each(|child| {
+            self.children.remove(&child);
+        });
+
+        for file in recent_files::recent(10) {
+            self.children.append(&make_item(&file));
+        }
+    }
+}
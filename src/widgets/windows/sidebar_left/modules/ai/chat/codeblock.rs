@@ -0,0 +1,136 @@
+// gtk4cmark renders fenced code blocks itself, so the copy and run buttons are added to
+// their headers after every render, and the highlighting is fixed up for languages named
+// differently from GtkSourceView. Blocks are reused between renders, so this is idempotent.
+
+use gtk::prelude::*;
+use relm4::RelmIterChildrenExt as _;
+use sourceview5::prelude::*;
+
+use crate::services::clipboard;
+use crate::utils::process;
+use super::markdown::{self, SHELL_LANGUAGES};
+
+const DECORATED_CLASS: &str = "ai-chat-codeblock-decorated";
+
+fn codeblock_button(icon: &str, tooltip: &str) -> gtk::Button {
+    let button = gtk::Button::new();
+    button.set_css_classes(&["ai-chat-codeblock-button"]);
+    button.set_label(icon);
+    button.set_tooltip_text(Some(tooltip));
+    button.set_valign(gtk::Align::Center);
+
+    button
+}
+
+fn find_descendant<T: IsA<gtk::Widget>>(widget: &gtk::Widget, predicate: &dyn Fn(&gtk::Widget) -> bool) -> Option<T> {
+    let mut child = widget.first_child();
+    while let Some(current) = child {
+        if predicate(&current)
+            && let Ok(found) = current.clone().downcast::<T>()
+        {
+            return Some(found);
+        }
+
+        if let Some(found) = find_descendant(&current, predicate) {
+            return Some(found);
+        }
+
+        child = current.next_sibling();
+    }
+
+    None
+}
+
+fn codeblock_text(root: &gtk::Widget) -> Option<String> {
+    let text_view = find_descendant::<gtk::TextView>(root, &|widget| widget.is::<gtk::TextView>())?;
+    let buffer = text_view.buffer();
+    Some(buffer.text(&buffer.start_iter(), &buffer.end_iter(), false).to_string())
+}
+
+/// The GtkSourceView language ID of the block, from the language shown in its header.
+fn codeblock_language(root: &gtk::Widget) -> Option<String> {
+    find_descendant::<gtk::Label>(root, &|widget| widget.has_css_class("cmark-codeblock-lang"))
+        .map(|label| markdown::sourceview_language(&label.label()))
+}
+
+/// gtk4cmark highlights with the fence language as is, which misses aliases like "py".
+fn highlight(root: &gtk::Widget, language: Option<&str>) {
+    let Some(buffer) = find_descendant::<gtk::TextView>(root, &|widget| widget.is::<gtk::TextView>())
+        .and_then(|text_view| text_view.buffer().downcast::<sourceview5::Buffer>().ok())
+    else {
+        return;
+    };
+
+    let language = language.and_then(|language| sourceview5::LanguageManager::default().language(language));
+    if buffer.language().map(|current| current.id()) != language.as_ref().map(|language| language.id()) {
+        buffer.set_language(language.as_ref());
+    }
+}
+
+fn decorate(root: &gtk::Widget) {
+    let Some(header) = find_descendant::<gtk::Box>(root, &|widget| widget.has_css_class("cmark-codeblock-header")) else {
+        return;
+    };
+
+    if !root.has_css_class(DECORATED_CLASS) {
+        root.add_css_class(DECORATED_CLASS);
+
+        // Push the buttons to the end of the header
+        if let Some(language_label) = find_descendant::<gtk::Label>(&header.clone().upcast(), &|widget| widget.has_css_class("cmark-codeblock-lang")) {
+            language_label.set_hexpand(true);
+            language_label.set_xalign(0.0);
+        }
+
+        let copy_button = codeblock_button("content_copy", "Copy");
+        copy_button.connect_clicked(clone!(
+            #[weak] root,
+            move |button| if let Some(text) = codeblock_text(&root) {
+                clipboard::copy_text(&text);
+                button.set_label("check");
+                glib::timeout_add_local_once(std::time::Duration::from_secs(2), clone!(
+                    #[weak] button,
+                    move || button.set_label("content_copy")
+                ));
+            }
+        ));
+
+        let run_button = codeblock_button("terminal", "Run in Terminal");
+        run_button.add_css_class("run");
+        run_button.connect_clicked(clone!(
+            #[weak] root,
+            move |_| if let Some(text) = codeblock_text(&root) {
+                // The code is passed as a single argument, so nothing in it is expanded early
+                let shell = if codeblock_language(&root).as_deref() == Some("fish") { "fish" } else { "bash" };
+                process::launch_argv(&process::terminal_argv(&[shell, "-c", text.trim()]));
+            }
+        ));
+
+        header.append(&run_button);
+        header.append(&copy_button);
+    }
+
+    // The language can change while the block is being streamed in
+    let language = codeblock_language(root);
+    highlight(root, language.as_deref());
+
+    let is_shell = language.as_ref().is_some_and(|language| SHELL_LANGUAGES.contains(&language.as_str()));
+    for child in header.iter_children() {
+        if child.has_css_class("run") {
+            child.set_visible(is_shell);
+        }
+    }
+}
+
+/// Adds copy and run buttons to every code block rendered by a markdown view.
+pub fn decorate_code_blocks(widget: &impl IsA<gtk::Widget>) {
+    let mut child = widget.as_ref().first_child();
+    while let Some(current) = child {
+        if current.has_css_class("cmark-codeblock") {
+            decorate(&current);
+        } else {
+            decorate_code_blocks(&current);
+        }
+
+        child = current.next_sibling();
+    }
+}
@@ -6,6 +6,8 @@ mod imp {
     use gtk::prelude::*;
     use gtk::subclass::prelude::*;
     use glib::Properties;
+
+    use super::super::{codeblock, markdown};
     
     #[derive(Default, Properties)]
     #[properties(wrapper_type = super::ChatMessageContent)]
@@ -130,7 +132,8 @@ mod imp {
                 self.content.replace(content.clone());
                 
                 if let Some(markdown_view) = self.markdown_view.borrow().as_ref() {
-                    markdown_view.set_markdown(content);
+                    markdown_view.set_markdown(markdown::prepare(&content));
+                    codeblock::decorate_code_blocks(markdown_view);
                 }
             }
             
@@ -158,7 +161,8 @@ mod imp {
             self.content.replace(content.to_owned());
             
             if let Some(markdown_view) = self.markdown_view.borrow().as_ref() {
-                markdown_view.set_markdown(content);
+                markdown_view.set_markdown(markdown::prepare(content));
+                codeblock::decorate_code_blocks(markdown_view);
             }
         }
         
@@ -0,0 +1,295 @@
+// A small LaTeX to Unicode converter for rendering math in labels. It understands the common
+// subset models produce (greek letters, operators, fractions, roots, scripts, text commands),
+// written out on one line, so fractions become `a/b`. Environments like matrices, and commands
+// it does not know, can't be written that way, so it gives up on them.
+
+const SYMBOLS: &[(&str, &str)] = &[
+    ("alpha", "α"), ("beta", "β"), ("gamma", "γ"), ("delta", "δ"), ("epsilon", "ε"), ("varepsilon", "ε"),
+    ("zeta", "ζ"), ("eta", "η"), ("theta", "θ"), ("vartheta", "ϑ"), ("iota", "ι"), ("kappa", "κ"),
+    ("lambda", "λ"), ("mu", "μ"), ("nu", "ν"), ("xi", "ξ"), ("pi", "π"), ("varpi", "ϖ"), ("rho", "ρ"),
+    ("sigma", "σ"), ("varsigma", "ς"), ("tau", "τ"), ("upsilon", "υ"), ("phi", "ϕ"), ("varphi", "φ"),
+    ("chi", "χ"), ("psi", "ψ"), ("omega", "ω"),
+    ("Gamma", "Γ"), ("Delta", "Δ"), ("Theta", "Θ"), ("Lambda", "Λ"), ("Xi", "Ξ"), ("Pi", "Π"),
+    ("Sigma", "Σ"), ("Upsilon", "Υ"), ("Phi", "Φ"), ("Psi", "Ψ"), ("Omega", "Ω"),
+    ("times", "×"), ("cdot", "·"), ("div", "÷"), ("pm", "±"), ("mp", "∓"), ("ast", "∗"), ("star", "⋆"),
+    ("leq", "≤"), ("le", "≤"), ("geq", "≥"), ("ge", "≥"), ("neq", "≠"), ("ne", "≠"), ("ll", "≪"), ("gg", "≫"),
+    ("approx", "≈"), ("equiv", "≡"), ("sim", "∼"), ("simeq", "≃"), ("cong", "≅"), ("propto", "∝"),
+    ("infty", "∞"), ("sum", "∑"), ("prod", "∏"), ("coprod", "∐"), ("int", "∫"), ("iint", "∬"),
+    ("iiint", "∭"), ("oint", "∮"), ("partial", "∂"), ("nabla", "∇"),
+    ("in", "∈"), ("notin", "∉"), ("ni", "∋"), ("subset", "⊂"), ("subseteq", "⊆"), ("supset", "⊃"),
+    ("supseteq", "⊇"), ("cup", "∪"), ("cap", "∩"), ("setminus", "∖"), ("emptyset", "∅"), ("varnothing", "∅"),
+    ("forall", "∀"), ("exists", "∃"), ("nexists", "∄"), ("neg", "¬"), ("lnot", "¬"), ("land", "∧"),
+    ("wedge", "∧"), ("lor", "∨"), ("vee", "∨"), ("oplus", "⊕"), ("otimes", "⊗"),
+    ("rightarrow", "→"), ("to", "→"), ("leftarrow", "←"), ("gets", "←"), ("leftrightarrow", "↔"),
+    ("Rightarrow", "⇒"), ("implies", "⇒"), ("Leftarrow", "⇐"), ("Leftrightarrow", "⇔"), ("iff", "⇔"),
+    ("mapsto", "↦"), ("uparrow", "↑"), ("downarrow", "↓"), ("longrightarrow", "⟶"),
+    ("ldots", "…"), ("dots", "…"), ("cdots", "⋯"), ("vdots", "⋮"), ("ddots", "⋱"),
+    ("circ", "∘"), ("bullet", "•"), ("degree", "°"), ("angle", "∠"), ("perp", "⊥"), ("parallel", "∥"),
+    ("mid", "∣"), ("langle", "⟨"), ("rangle", "⟩"), ("lfloor", "⌊"), ("rfloor", "⌋"), ("lceil", "⌈"),
+    ("rceil", "⌉"), ("lvert", "|"), ("rvert", "|"), ("vert", "|"), ("Vert", "‖"),
+    ("hbar", "ℏ"), ("ell", "ℓ"), ("Re", "ℜ"), ("Im", "ℑ"), ("aleph", "ℵ"), ("prime", "′"),
+    ("therefore", "∴"), ("because", "∵"), ("quad", "  "), ("qquad", "    "),
+];
+
+const FUNCTIONS: &[&str] = &[
+    "sin", "cos", "tan", "sec", "csc", "cot", "arcsin", "arccos", "arctan", "sinh", "cosh", "tanh",
+    "log", "ln", "lg", "exp", "lim", "sup", "inf", "max", "min", "det", "dim", "ker", "gcd", "deg",
+    "arg", "mod", "bmod", "Pr",
+];
+
+// Commands that only change the style of their argument
+const STYLE_COMMANDS: &[&str] = &[
+    "text", "textrm", "textbf", "textit", "textsf", "texttt", "mathrm", "mathbf", "mathit", "mathsf",
+    "mathtt", "mathcal", "mathscr", "mathfrak", "boldsymbol", "bm", "operatorname", "mbox", "displaystyle",
+];
+
+// Commands that size delimiters, the delimiter that follows is kept
+const SIZING_COMMANDS: &[&str] = &[
+    "left", "right", "big", "Big", "bigg", "Bigg", "bigl", "bigr", "Bigl", "Bigr", "biggl", "biggr",
+    "middle", "textstyle", "scriptstyle", "limits", "nolimits",
+];
+
+const ACCENTS: &[(&str, char)] = &[
+    ("hat", '\u{0302}'), ("widehat", '\u{0302}'), ("bar", '\u{0304}'), ("overline", '\u{0305}'),
+    ("vec", '\u{20D7}'), ("dot", '\u{0307}'), ("ddot", '\u{0308}'), ("tilde", '\u{0303}'),
+    ("widetilde", '\u{0303}'), ("underline", '\u{0332}'),
+];
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'), ('1', '¹'), ('2', '²'), ('3', '³'), ('4', '⁴'), ('5', '⁵'), ('6', '⁶'), ('7', '⁷'),
+    ('8', '⁸'), ('9', '⁹'), ('+', '⁺'), ('-', '⁻'), ('−', '⁻'), ('=', '⁼'), ('(', '⁽'), (')', '⁾'),
+    ('a', 'ᵃ'), ('b', 'ᵇ'), ('c', 'ᶜ'), ('d', 'ᵈ'), ('e', 'ᵉ'), ('f', 'ᶠ'), ('g', 'ᵍ'), ('h', 'ʰ'),
+    ('i', 'ⁱ'), ('j', 'ʲ'), ('k', 'ᵏ'), ('l', 'ˡ'), ('m', 'ᵐ'), ('n', 'ⁿ'), ('o', 'ᵒ'), ('p', 'ᵖ'),
+    ('r', 'ʳ'), ('s', 'ˢ'), ('t', 'ᵗ'), ('u', 'ᵘ'), ('v', 'ᵛ'), ('w', 'ʷ'), ('x', 'ˣ'), ('y', 'ʸ'),
+    ('z', 'ᶻ'), ('T', 'ᵀ'), ('′', '′'), ('∗', '*'), ('*', '*'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'), ('1', '₁'), ('2', '₂'), ('3', '₃'), ('4', '₄'), ('5', '₅'), ('6', '₆'), ('7', '₇'),
+    ('8', '₈'), ('9', '₉'), ('+', '₊'), ('-', '₋'), ('−', '₋'), ('=', '₌'), ('(', '₍'), (')', '₎'),
+    ('a', 'ₐ'), ('e', 'ₑ'), ('h', 'ₕ'), ('i', 'ᵢ'), ('j', 'ⱼ'), ('k', 'ₖ'), ('l', 'ₗ'), ('m', 'ₘ'),
+    ('n', 'ₙ'), ('o', 'ₒ'), ('p', 'ₚ'), ('r', 'ᵣ'), ('s', 'ₛ'), ('t', 'ₜ'), ('u', 'ᵤ'), ('v', 'ᵥ'),
+    ('x', 'ₓ'),
+];
+
+fn double_struck(c: char) -> char {
+    match c {
+        'C' => 'ℂ', 'H' => 'ℍ', 'N' => 'ℕ', 'P' => 'ℙ', 'Q' => 'ℚ', 'R' => 'ℝ', 'Z' => 'ℤ',
+        'A'..='Z' => char::from_u32(0x1D538 + (c as u32 - 'A' as u32)).unwrap_or(c),
+        _ => c,
+    }
+}
+
+/// Wraps an expression in parentheses if it is longer than a single symbol.
+fn group(expression: &str) -> String {
+    if expression.chars().count() > 1 {
+        format!("({})", expression)
+    } else {
+        expression.to_owned()
+    }
+}
+
+fn script(argument: &str, table: &[(char, char)], marker: char) -> String {
+    let argument = argument.trim();
+    let mapped = argument.chars()
+        .filter(|c| !c.is_whitespace())
+        .map(|c| table.iter().find(|(from, _)| *from == c).map(|(_, to)| *to))
+        .collect::<Option<String>>();
+
+    mapped.unwrap_or_else(|| format!("{}{}", marker, group(argument)))
+}
+
+struct Parser {
+    chars: Vec<char>,
+    pos: usize,
+    /// Something was found that can't be converted.
+    unsupported: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.pos).copied()
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.pos += 1;
+        }
+    }
+
+    /// Converts everything up to the given closing character, or the end of the input.
+    fn sequence(&mut self, end: Option<char>) -> String {
+        let mut output = String::new();
+        while let Some(c) = self.peek() {
+            if Some(c) == end {
+                self.pos += 1;
+                break;
+            }
+
+            match c {
+                '\\' => output.push_str(&self.command()),
+                '{' => {
+                    self.pos += 1;
+                    output.push_str(&self.sequence(Some('}')));
+                },
+                '^' => {
+                    self.pos += 1;
+                    let argument = self.argument();
+                    output.push_str(&script(&argument, SUPERSCRIPTS, '^'));
+                },
+                '_' => {
+                    self.pos += 1;
+                    let argument = self.argument();
+                    output.push_str(&script(&argument, SUBSCRIPTS, '_'));
+                },
+                '}' => self.pos += 1,
+                '&' | '~' => {
+                    self.pos += 1;
+                    output.push(' ');
+                },
+                '-' => {
+                    self.pos += 1;
+                    output.push('−');
+                },
+                '\'' => {
+                    self.pos += 1;
+                    output.push('′');
+                },
+                _ => {
+                    self.pos += 1;
+                    output.push(c);
+                },
+            }
+        }
+
+        output
+    }
+
+    /// Converts a single argument, either a braced group, a command or a character.
+    fn argument(&mut self) -> String {
+        self.skip_whitespace();
+        match self.peek() {
+            Some('{') => {
+                self.pos += 1;
+                self.sequence(Some('}'))
+            },
+            Some('\\') => self.command(),
+            Some(c) => {
+                self.pos += 1;
+                c.to_string()
+            },
+            None => String::new(),
+        }
+    }
+
+    fn command(&mut self) -> String {
+        self.pos += 1; // backslash
+
+        let Some(first) = self.peek() else {
+            return "\\".to_owned();
+        };
+
+        if !first.is_ascii_alphabetic() {
+            self.pos += 1;
+            return match first {
+                '\\' => "\n".to_owned(),
+                ',' | ':' | ';' | ' ' => " ".to_owned(),
+                '!' => String::new(),
+                '|' => "‖".to_owned(),
+                c => c.to_string(),
+            };
+        }
+
+        let mut name = String::new();
+        while let Some(c) = self.peek().filter(char::is_ascii_alphabetic) {
+            name.push(c);
+            self.pos += 1;
+        }
+
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" | "cfrac" => {
+                let numerator = self.argument();
+                let denominator = self.argument();
+                format!("{}/{}", group(&numerator), group(&denominator))
+            },
+
+            "binom" => {
+                let n = self.argument();
+                let k = self.argument();
+                format!("C({}, {})", n, k)
+            },
+
+            "sqrt" => {
+                self.skip_whitespace();
+                let index = if self.peek() == Some('[') {
+                    self.pos += 1;
+                    Some(self.sequence(Some(']')))
+                } else {
+                    None
+                };
+
+                let radicand = self.argument();
+                let root = match index.as_deref() {
+                    Some("3") => "∛",
+                    Some("4") => "∜",
+                    _ => "√",
+                };
+
+                match index.as_deref() {
+                    Some("3" | "4") | None => format!("{}{}", root, group(&radicand)),
+                    Some(index) => format!("{}{}{}", script(index, SUPERSCRIPTS, '^'), root, group(&radicand)),
+                }
+            },
+
+            "mathbb" => self.argument().chars().map(double_struck).collect(),
+
+            "begin" | "end" => {
+                self.unsupported = true;
+                self.argument();
+                String::new()
+            },
+
+            name if STYLE_COMMANDS.contains(&name) => self.argument(),
+
+            name if SIZING_COMMANDS.contains(&name) => {
+                // `\left.` and `\right.` are invisible delimiters
+                self.skip_whitespace();
+                if self.peek() == Some('.') {
+                    self.pos += 1;
+                }
+                String::new()
+            },
+
+            name if FUNCTIONS.contains(&name) => name.to_owned(),
+
+            name => if let Some((_, accent)) = ACCENTS.iter().find(|(accent, _)| *accent == name) {
+                self.argument().chars().flat_map(|c| [c, *accent]).collect()
+            } else if let Some((_, symbol)) = SYMBOLS.iter().find(|(symbol, _)| *symbol == name) {
+                (*symbol).to_owned()
+            } else {
+                self.unsupported = true;
+                format!("\\{}", name)
+            },
+        }
+    }
+}
+
+/// Converts a LaTeX math expression into plain Unicode text, or `None` if it can't be.
+pub fn to_unicode(latex: &str) -> Option<String> {
+    let mut parser = Parser {
+        chars: latex.chars().collect(),
+        pos: 0,
+        unsupported: false,
+    };
+
+    let output = parser.sequence(None);
+    if parser.unsupported {
+        return None;
+    }
+
+    // Collapse the whitespace LaTeX ignores
+    Some(output.split(' ')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" "))
+}
@@ -0,0 +1,219 @@
+// Prepares model output before it is handed to gtk4cmark: LaTeX math is converted to Unicode
+// since GFM has no notion of math. That only covers math that can be written on one line,
+// anything else (matrices, unknown commands...) is shown as its LaTeX source in code.
+
+use super::latex;
+
+/// Common code fence languages that do not match a GtkSourceView language ID.
+const LANGUAGE_ALIASES: &[(&str, &str)] = &[
+    ("py", "python3"), ("python", "python3"), ("javascript", "js"), ("jsx", "js"), ("mjs", "js"),
+    ("node", "js"), ("ts", "typescript"), ("tsx", "typescript-jsx"), ("rs", "rust"), ("bash", "sh"),
+    ("shell", "sh"), ("zsh", "sh"), ("console", "sh"), ("shellscript", "sh"), ("terminal", "sh"),
+    ("c++", "cpp"), ("cxx", "cpp"), ("hpp", "cpp"), ("cc", "cpp"), ("h", "c"), ("yml", "yaml"),
+    ("golang", "go"), ("cs", "c-sharp"), ("csharp", "c-sharp"), ("c#", "c-sharp"), ("kt", "kotlin"),
+    ("rb", "ruby"), ("md", "markdown"), ("docker", "dockerfile"), ("make", "makefile"),
+    ("conf", "ini"), ("cfg", "ini"), ("htm", "html"), ("patch", "diff"), ("jsonc", "json"),
+    ("ps1", "powershell"), ("pwsh", "powershell"), ("hs", "haskell"), ("ex", "elixir"), ("exs", "elixir"),
+    ("ml", "ocaml"), ("pl", "perl"), ("vimscript", "vim"), ("viml", "vim"), ("tex", "latex"),
+];
+
+/// GtkSourceView language IDs whose code blocks can be run in a terminal.
+pub const SHELL_LANGUAGES: &[&str] = &["sh", "fish"];
+
+// Characters that would otherwise be interpreted as markdown inside converted math
+const MARKDOWN_SPECIAL_CHARS: &[char] = &['\\', '`', '*', '_', '[', ']', '<', '>', '~', '|', '$', '#'];
+
+/// The GtkSourceView language ID for a code fence language, which is what's used to highlight
+/// code blocks. The fence itself is left as the model wrote it, for the block's label.
+pub fn sourceview_language(language: &str) -> String {
+    let lowercase = language.to_lowercase();
+    LANGUAGE_ALIASES.iter()
+        .find(|(alias, _)| *alias == lowercase)
+        .map_or(lowercase, |(_, id)| (*id).to_owned())
+}
+
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if MARKDOWN_SPECIAL_CHARS.contains(&c) {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// The longest run of `c` in the text, so a fence around it can be made longer.
+fn longest_run(text: &str, c: char) -> usize {
+    text.split(|other| other != c).map(str::len).max().unwrap_or(0)
+}
+
+fn render_math(latex: &str, inline: bool) -> String {
+    let Some(mut unicode) = latex::to_unicode(latex) else {
+        let latex = latex.trim();
+        return if inline {
+            // Spaces keep backticks at the edges from joining the fence
+            let fence = "`".repeat(longest_run(latex, '`') + 1);
+            format!("{} {} {}", fence, latex.replace('\n', " "), fence)
+        } else {
+            let fence = "`".repeat(longest_run(latex, '`').max(2) + 1);
+            format!("{}latex\n{}\n{}", fence, latex, fence)
+        };
+    };
+
+    if inline {
+        unicode = unicode.replace('\n', " ");
+    }
+
+    format!("*{}*", escape_markdown(unicode.trim()))
+}
+
+/// Finds the end of an inline `$...$` span starting at `start`, following pandoc's rules:
+/// the content may not start or end with whitespace, and the closing `$` may not be followed by a digit.
+fn find_inline_dollar_end(chars: &[char], start: usize) -> Option<usize> {
+    if chars.get(start + 1).is_none_or(|c| c.is_whitespace() || *c == '$') {
+        return None;
+    }
+
+    let mut i = start + 1;
+    while i < chars.len() {
+        match chars[i] {
+            '\\' => i += 1,
+            '$' => {
+                return (!chars[i - 1].is_whitespace() && !chars.get(i + 1).is_some_and(char::is_ascii_digit))
+                    .then_some(i);
+            },
+            _ => {},
+        }
+        i += 1;
+    }
+
+    None
+}
+
+fn find_sequence(chars: &[char], from: usize, sequence: &[char]) -> Option<usize> {
+    (from..chars.len()).find(|&i| chars[i..].starts_with(sequence))
+}
+
+/// Converts math in a line outside of code blocks, leaving inline code spans untouched.
+fn convert_inline_math(line: &str) -> String {
+    let chars = line.chars().collect::<Vec<_>>();
+    let mut output = String::with_capacity(line.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+
+        // Copy inline code spans as-is
+        if c == '`' {
+            let ticks = chars[i..].iter().take_while(|c| **c == '`').count();
+            let fence = vec!['`'; ticks];
+            if let Some(end) = find_sequence(&chars, i + ticks, &fence) {
+                output.extend(&chars[i..end + ticks]);
+                i = end + ticks;
+            } else {
+                output.extend(&chars[i..i + ticks]);
+                i += ticks;
+            }
+            continue;
+        }
+
+        if c == '\\' && matches!(chars.get(i + 1), Some('(' | '['))
+            && let closing = if chars[i + 1] == '(' { ['\\', ')'] } else { ['\\', ']'] }
+            && let Some(end) = find_sequence(&chars, i + 2, &closing)
+        {
+            let latex = chars[i + 2..end].iter().collect::<String>();
+            output.push_str(&render_math(&latex, true));
+            i = end + 2;
+            continue;
+        }
+
+        if c == '$' && chars.get(i + 1) == Some(&'$')
+            && let Some(end) = find_sequence(&chars, i + 2, &['$', '$'])
+        {
+            let latex = chars[i + 2..end].iter().collect::<String>();
+            output.push_str(&render_math(&latex, true));
+            i = end + 2;
+            continue;
+        }
+
+        if c == '$' && (i == 0 || chars[i - 1] != '\\')
+            && let Some(end) = find_inline_dollar_end(&chars, i)
+        {
+            let latex = chars[i + 1..end].iter().collect::<String>();
+            output.push_str(&render_math(&latex, true));
+            i = end + 1;
+            continue;
+        }
+
+        output.push(c);
+        i += 1;
+    }
+
+    output
+}
+
+/// Returns the fence characters if a line opens or closes a fenced code block.
+fn code_fence(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    let fence_char = trimmed.chars().next().filter(|c| matches!(c, '`' | '~'))?;
+    let length = trimmed.chars().take_while(|c| *c == fence_char).count();
+    (length >= 3).then(|| &trimmed[..length])
+}
+
+pub fn prepare(content: &str) -> String {
+    let mut output = Vec::new();
+    let mut open_fence: Option<String> = None;
+    let mut display_math: Option<(String, Vec<String>)> = None; // (closing delimiter, lines)
+
+    for line in content.lines() {
+        if let Some((closing, lines)) = &mut display_math {
+            if let Some(before) = line.trim_end().strip_suffix(closing.as_str()) {
+                lines.push(before.to_owned());
+                output.push(render_math(&lines.join("\n"), false));
+                display_math = None;
+            } else {
+                lines.push(line.to_owned());
+            }
+            continue;
+        }
+
+        if let Some(fence) = &open_fence {
+            if code_fence(line).is_some_and(|closing| closing.starts_with(fence.as_str()))
+                && line.trim().trim_start_matches(fence.chars().next().unwrap_or('`')).is_empty()
+            {
+                open_fence = None;
+            }
+            output.push(line.to_owned());
+            continue;
+        }
+
+        if let Some(fence) = code_fence(line) {
+            open_fence = Some(fence.to_owned());
+            output.push(line.to_owned());
+            continue;
+        }
+
+        // Display math that spans several lines
+        let trimmed = line.trim();
+        for (opening, closing) in [("$$", "$$"), ("\\[", "\\]")] {
+            if let Some(rest) = trimmed.strip_prefix(opening)
+                && !rest.contains(closing)
+            {
+                display_math = Some((closing.to_owned(), vec![rest.to_owned()]));
+                break;
+            }
+        }
+
+        if display_math.is_none() {
+            output.push(convert_inline_math(line));
+        }
+    }
+
+    // Unterminated display math is likely still being streamed
+    if let Some((_, lines)) = display_math {
+        output.push(render_math(&lines.join("\n"), false));
+    }
+
+    output.join("\n")
+}
@@ -1,11 +1,15 @@
 pub mod message;
 pub mod content;
+mod codeblock;
+mod latex;
+mod markdown;
 
 use std::rc::Rc;
 use std::cell::RefCell;
 use gtk::prelude::*;
 use relm4::RelmIterChildrenExt as _;
 
+use crate::services::ai::{self, AiChannelMessage, confirmation};
 use crate::services::ai::images::uuid_to_file_path;
 use self::message::{ChatMessage, ChatRole};
 
@@ -167,4 +171,102 @@ impl Chat {
             }
         }
     }
-}
\ No newline at end of file
+
+    pub fn append_file_to_latest_message(&self, name: &str, mime_type: &str) {
+        let mut messages = self.messages.borrow_mut();
+        if let Some(latest_message) = messages.last_mut() {
+            let file_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+            file_box.set_css_classes(&["ai-chat-message-file"]);
+            file_box.set_halign(gtk::Align::Start);
+
+            let icon = gtk::Label::new(Some(if mime_type == "application/pdf" {
+                "picture_as_pdf"
+            } else {
+                "description"
+            }));
+            icon.set_css_classes(&["ai-chat-message-file-icon"]);
+            file_box.append(&icon);
+
+            let info_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
+            file_box.append(&info_box);
+
+            let name_label = gtk::Label::new(Some(name));
+            name_label.set_css_classes(&["ai-chat-message-file-name"]);
+            name_label.set_xalign(0.0);
+            name_label.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+            info_box.append(&name_label);
+
+            let mime_type_label = gtk::Label::new(Some(mime_type));
+            mime_type_label.set_css_classes(&["ai-chat-message-file-mime-type"]);
+            mime_type_label.set_xalign(0.0);
+            info_box.append(&mime_type_label);
+
+            latest_message.footer.append(&file_box);
+            *latest_message.attachments.borrow_mut() += 1;
+
+            if latest_message.content.is_none() {
+                latest_message.set_content("");
+            }
+        }
+    }
+
+    pub fn append_confirmation_to_latest_message(&self, id: u64, description: &str) {
+        let mut messages = self.messages.borrow_mut();
+        if let Some(latest_message) = messages.last_mut() {
+            let confirmation_box = gtk::Box::new(gtk::Orientation::Vertical, 8);
+            confirmation_box.set_css_classes(&["ai-chat-message-confirmation"]);
+
+            let description_label = gtk::Label::new(Some(description));
+            description_label.set_css_classes(&["ai-chat-message-confirmation-description"]);
+            description_label.set_xalign(0.0);
+            description_label.set_wrap(true);
+            description_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+            confirmation_box.append(&description_label);
+
+            let buttons_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+            buttons_box.set_halign(gtk::Align::End);
+            confirmation_box.append(&buttons_box);
+
+            let deny_button = gtk::Button::with_label("Deny");
+            deny_button.set_css_classes(&["ai-chat-message-confirmation-button"]);
+            deny_button.connect_clicked(move |_| confirmation::respond(id, false));
+            buttons_box.append(&deny_button);
+
+            let allow_button = gtk::Button::with_label("Allow");
+            allow_button.set_css_classes(&["ai-chat-message-confirmation-button", "allow"]);
+            allow_button.connect_clicked(move |_| confirmation::respond(id, true));
+            buttons_box.append(&allow_button);
+
+            let status_label = gtk::Label::new(None);
+            status_label.set_css_classes(&["ai-chat-message-confirmation-status"]);
+            status_label.set_xalign(0.0);
+            status_label.set_visible(false);
+            confirmation_box.append(&status_label);
+
+            if let Some(mut receiver) = ai::CHANNEL.get().map(|channel| channel.subscribe()) {
+                glib::spawn_future_local(clone!(
+                    #[weak] buttons_box,
+                    #[weak] status_label,
+                    async move {
+                        while let Ok(message) = receiver.recv().await {
+                            if let AiChannelMessage::ConfirmationResolved(resolved_id, allowed) = message
+                                && resolved_id == id
+                            {
+                                buttons_box.set_visible(false);
+                                status_label.set_text(if allowed { "Allowed" } else { "Denied" });
+                                status_label.set_visible(true);
+                                break;
+                            }
+                        }
+                    }
+                ));
+            }
+
+            latest_message.footer.append(&confirmation_box);
+
+            if latest_message.content.is_none() {
+                latest_message.set_content("");
+            }
+        }
+    }
+}
@@ -1,10 +1,12 @@
-use std::cell::RefCell;
+use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 use gtk::prelude::*;
+use relm4::RelmIterChildrenExt as _;
 
 use crate::sql::wrappers::aichats;
 use crate::services::ai::{self, AiChannelMessage, types::AiConversation};
 use crate::utils::gesture;
+use super::conversation_ui_header_button;
 
 fn conversation_control_button(icon_name: &str, tooltip: &str) -> gtk::Button {
     let button = gtk::Button::new();
@@ -23,12 +25,49 @@ fn message_count_str(count: usize) -> String {
     }
 }
 
+fn tags_str(tags: &[String]) -> String {
+    tags.iter()
+        .map(|tag| format!("#{}", tag))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn section_label(text: &str) -> gtk::Label {
+    let label = gtk::Label::new(Some(text));
+    label.set_css_classes(&["ai-chat-conversation-section-label"]);
+    label.set_xalign(0.0);
+    label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+
+    label
+}
+
+/// Whether a conversation matches the search filter. Words starting with `#` must match
+/// a tag exactly, other words can appear anywhere in the title, folder or tags.
+fn matches_filter(conversation: &AiConversation, filter: &str) -> bool {
+    filter.split_whitespace().all(|word| {
+        let word = word.to_lowercase();
+        if let Some(tag) = word.strip_prefix('#') {
+            return tag.is_empty() || conversation.tags.iter().any(|t| *t == tag);
+        }
+
+        conversation.title.to_lowercase().contains(&word)
+            || conversation.folder.as_ref().is_some_and(|folder| folder.to_lowercase().contains(&word))
+            || conversation.tags.iter().any(|tag| tag.contains(&word))
+    })
+}
+
 #[derive(Debug, Clone)]
 pub struct ConversationItem {
     pub conversation: Rc<RefCell<AiConversation>>,
     pub root: gtk::Box,
     pub title_label: gtk::Label,
     pub length_label: gtk::Label,
+    pub tags_label: gtk::Label,
+    pub select_check: gtk::CheckButton,
+    pub pin_button: gtk::Button,
+    pub archive_button: gtk::Button,
+    pub folder_input: gtk::Entry,
+    pub tags_input: gtk::Entry,
 }
 
 impl ConversationItem {
@@ -38,11 +77,17 @@ impl ConversationItem {
         let root = gtk::Box::new(gtk::Orientation::Horizontal, 0);
         root.set_css_classes(&["ai-chat-conversation-item"]);
 
+        let select_check = gtk::CheckButton::new();
+        select_check.set_css_classes(&["ai-chat-conversation-item-select-check"]);
+        select_check.set_valign(gtk::Align::Center);
+        select_check.set_visible(false);
+        root.append(&select_check);
+
         let info_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
         info_box.set_css_classes(&["ai-chat-conversation-item-info-box"]);
         info_box.set_hexpand(true);
         root.append(&info_box);
-        
+
         let title_label = gtk::Label::new(Some(&conversation.borrow().title));
         title_label.set_css_classes(&["ai-chat-conversation-item-title-label"]);
         title_label.set_hexpand(true);
@@ -85,9 +130,83 @@ impl ConversationItem {
         length_label.set_xalign(0.0);
         info_box.append(&length_label);
 
+        let tags_label = gtk::Label::new(None);
+        tags_label.set_css_classes(&["ai-chat-conversation-item-tags-label"]);
+        tags_label.set_halign(gtk::Align::Start);
+        tags_label.set_xalign(0.0);
+        tags_label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+        info_box.append(&tags_label);
+
+        // Folder and tags editor, shown with the organize button
+        let organize_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
+        organize_box.set_css_classes(&["ai-chat-conversation-item-organize-box"]);
+        organize_box.set_visible(false);
+        info_box.append(&organize_box);
+
+        let folder_input = gtk::Entry::new();
+        folder_input.set_css_classes(&["ai-chat-conversation-item-organize-input"]);
+        folder_input.set_placeholder_text(Some("Folder"));
+        folder_input.connect_activate(clone!(
+            #[strong] conversation,
+            move |input| {
+                let folder = input.text().to_string();
+                glib::spawn_future_local(clone!(
+                    #[strong(rename_to = id)] conversation.borrow().id,
+                    async move {
+                        ai::conversation::set_conversation_folder(id, &folder).await;
+                    }
+                ));
+            }
+        ));
+        organize_box.append(&folder_input);
+
+        let tags_input = gtk::Entry::new();
+        tags_input.set_css_classes(&["ai-chat-conversation-item-organize-input"]);
+        tags_input.set_placeholder_text(Some("Tags, separated by commas"));
+        tags_input.connect_activate(clone!(
+            #[strong] conversation,
+            move |input| {
+                let tags = ai::conversation::parse_tags(&input.text());
+                glib::spawn_future_local(clone!(
+                    #[strong(rename_to = id)] conversation.borrow().id,
+                    async move {
+                        ai::conversation::set_conversation_tags(id, &tags).await;
+                    }
+                ));
+            }
+        ));
+        organize_box.append(&tags_input);
+
         let controls_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
         controls_box.set_css_classes(&["ai-chat-conversation-item-controls-box"]);
 
+        let pin_button = conversation_control_button("push_pin", "Pin Conversation");
+        pin_button.connect_clicked(clone!(
+            #[strong] conversation,
+            move |_| {
+                let (id, pinned) = {
+                    let conversation = conversation.borrow();
+                    (conversation.id, conversation.pinned)
+                };
+                glib::spawn_future_local(ai::conversation::set_conversation_pinned(id, !pinned));
+            }
+        ));
+        controls_box.append(&pin_button);
+
+        let organize_button = conversation_control_button("folder", "Folder & Tags");
+        organize_button.connect_clicked(clone!(
+            #[weak] organize_box,
+            #[weak] folder_input,
+            move |_| {
+                let organizing = WidgetExt::is_visible(&organize_box);
+                organize_box.set_visible(!organizing);
+                if !organizing {
+                    folder_input.grab_focus();
+                }
+            }
+        ));
+        controls_box.append(&organize_button);
+
         let rename_button = conversation_control_button("edit", "Rename Conversation");
         rename_button.connect_clicked(clone!(
             #[weak] title_label,
@@ -107,6 +226,19 @@ impl ConversationItem {
         ));
         controls_box.append(&rename_button);
 
+        let archive_button = conversation_control_button("archive", "Archive Conversation");
+        archive_button.connect_clicked(clone!(
+            #[strong] conversation,
+            move |_| {
+                let (id, archived) = {
+                    let conversation = conversation.borrow();
+                    (conversation.id, conversation.archived)
+                };
+                glib::spawn_future_local(ai::conversation::set_conversation_archived(id, !archived));
+            }
+        ));
+        controls_box.append(&archive_button);
+
         let delete_button = conversation_control_button("close", "Delete Conversation");
         delete_button.connect_clicked(clone!(
             #[strong] conversation,
@@ -126,8 +258,9 @@ impl ConversationItem {
 
         root.add_controller(gesture::on_enter(clone!(
             #[weak] controls_revealer,
+            #[weak] select_check,
             move |_, _| {
-                controls_revealer.set_reveal_child(true);
+                controls_revealer.set_reveal_child(!WidgetExt::is_visible(&select_check));
             }
         )));
 
@@ -137,16 +270,56 @@ impl ConversationItem {
 
         info_box.add_controller(gesture::on_primary_up(clone!(
             #[strong] conversation,
-            move |_, _, _| if !WidgetExt::is_visible(&title_input) {
+            #[weak] select_check,
+            #[weak] organize_box,
+            move |_, _, _| if WidgetExt::is_visible(&select_check) {
+                select_check.set_active(!select_check.is_active());
+            } else if !WidgetExt::is_visible(&title_input) && !WidgetExt::is_visible(&organize_box) {
                 glib::spawn_future_local(ai::conversation::load_conversation(conversation.borrow().id));
             }
         )));
 
-        Self {
+        let item = Self {
             conversation,
             root,
             title_label,
             length_label,
+            tags_label,
+            select_check,
+            pin_button,
+            archive_button,
+            folder_input,
+            tags_input,
+        };
+
+        item.refresh();
+        item
+    }
+
+    /// Updates the widgets that reflect the conversation's organization.
+    fn refresh(&self) {
+        let conversation = self.conversation.borrow();
+
+        self.tags_label.set_text(&tags_str(&conversation.tags));
+        self.tags_label.set_visible(!conversation.tags.is_empty());
+
+        self.folder_input.set_text(conversation.folder.as_deref().unwrap_or_default());
+        self.tags_input.set_text(&conversation.tags.join(", "));
+
+        if conversation.pinned {
+            self.root.add_css_class("pinned");
+            self.pin_button.set_tooltip_text(Some("Unpin Conversation"));
+        } else {
+            self.root.remove_css_class("pinned");
+            self.pin_button.set_tooltip_text(Some("Pin Conversation"));
+        }
+
+        if conversation.archived {
+            self.archive_button.set_label("unarchive");
+            self.archive_button.set_tooltip_text(Some("Restore Conversation"));
+        } else {
+            self.archive_button.set_label("archive");
+            self.archive_button.set_tooltip_text(Some("Archive Conversation"));
         }
     }
 
@@ -157,12 +330,22 @@ impl ConversationItem {
     pub fn set_length(&self, new_length: usize) {
         self.length_label.set_text(&message_count_str(new_length));
     }
+
+    pub fn set_conversation(&self, conversation: AiConversation) {
+        self.set_title(&conversation.title);
+        *self.conversation.borrow_mut() = conversation;
+        self.refresh();
+    }
 }
 
 #[derive(Debug, Clone, glib::Downgrade)]
 pub struct ConversationsList {
     pub root: gtk::Box,
+    pub toolbar: gtk::Box,
     pub conversations: Rc<RefCell<Vec<ConversationItem>>>,
+    pub filter: Rc<RefCell<String>>,
+    pub show_archived: Rc<Cell<bool>>,
+    pub selecting: Rc<Cell<bool>>,
 }
 
 impl ConversationsList {
@@ -170,11 +353,94 @@ impl ConversationsList {
         let root = gtk::Box::new(gtk::Orientation::Vertical, 0);
         root.set_css_classes(&["ai-chat-conversations-list"]);
 
+        let toolbar = gtk::Box::new(gtk::Orientation::Vertical, 4);
+        toolbar.set_css_classes(&["ai-chat-conversations-toolbar"]);
+
         let me = Self {
             root,
+            toolbar,
             conversations: Rc::new(RefCell::new(Vec::new())),
+            filter: Rc::new(RefCell::new(String::new())),
+            show_archived: Rc::new(Cell::new(false)),
+            selecting: Rc::new(Cell::new(false)),
         };
 
+        let search_entry = gtk::Entry::new();
+        search_entry.set_css_classes(&["ai-chat-conversations-search-input"]);
+        search_entry.set_placeholder_text(Some("Search titles, folders or #tags"));
+        search_entry.connect_changed(clone!(
+            #[weak] me,
+            move |entry| {
+                *me.filter.borrow_mut() = entry.text().to_string();
+                me.relayout();
+            }
+        ));
+        me.toolbar.append(&search_entry);
+
+        let buttons_box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+        me.toolbar.append(&buttons_box);
+
+        let archived_button = conversation_ui_header_button("inventory_2", "Archived");
+        archived_button.connect_clicked(clone!(
+            #[weak] me,
+            move |button| {
+                me.show_archived.set(!me.show_archived.get());
+                if me.show_archived.get() {
+                    button.add_css_class("active");
+                } else {
+                    button.remove_css_class("active");
+                }
+                me.relayout();
+            }
+        ));
+        buttons_box.append(&archived_button);
+
+        let select_button = conversation_ui_header_button("checklist", "Select");
+        buttons_box.append(&select_button);
+
+        let delete_selected_button = conversation_ui_header_button("delete", "Delete Selected");
+        delete_selected_button.set_visible(false);
+        delete_selected_button.connect_clicked(clone!(
+            #[weak] me,
+            #[weak] select_button,
+            move |_| {
+                let selected_ids = me.conversations.borrow().iter()
+                    .filter(|item| item.select_check.is_active())
+                    .map(|item| item.conversation.borrow().id)
+                    .collect::<Vec<_>>();
+
+                if !selected_ids.is_empty() {
+                    glib::spawn_future_local(async move {
+                        ai::conversation::delete_conversations(&selected_ids).await;
+                    });
+                }
+
+                select_button.emit_clicked();
+            }
+        ));
+        buttons_box.append(&delete_selected_button);
+
+        select_button.connect_clicked(clone!(
+            #[weak] me,
+            #[weak] delete_selected_button,
+            move |button| {
+                let selecting = !me.selecting.get();
+                me.selecting.set(selecting);
+                delete_selected_button.set_visible(selecting);
+
+                if selecting {
+                    button.add_css_class("active");
+                } else {
+                    button.remove_css_class("active");
+                }
+
+                for item in me.conversations.borrow().iter() {
+                    item.select_check.set_active(false);
+                    item.select_check.set_visible(selecting);
+                }
+            }
+        ));
+
         // Listen for events from the AI singleton channel
         let receiver = ai::CHANNEL.get().map(|channel| channel.subscribe());
         if let Some(mut receiver) = receiver {
@@ -184,17 +450,12 @@ impl ConversationsList {
                     while let Ok(message) = receiver.recv().await {
                         match message {
                             AiChannelMessage::ConversationAdded(conversation) => {
-                                let item = ConversationItem::new(conversation).await;
-                                me.root.append(&item.root);
-                                me.conversations.borrow_mut().push(item);
+                                me.add_item(conversation).await;
                             },
 
                             AiChannelMessage::ConversationDeleted(conversation_id) => {
-                                let mut conversations = me.conversations.borrow_mut();
-                                if let Some(pos) = conversations.iter().position(|item| item.conversation.borrow().id == conversation_id) {
-                                    let item = conversations.remove(pos);
-                                    me.root.remove(&item.root);
-                                }
+                                me.conversations.borrow_mut().retain(|item| item.conversation.borrow().id != conversation_id);
+                                me.relayout();
                             },
 
                             AiChannelMessage::ConversationRenamed(conversation_id, new_title) => {
@@ -207,26 +468,20 @@ impl ConversationsList {
                                 }
                             },
 
-                            AiChannelMessage::ConversationTrimmed(conversation_id, _) => {
+                            AiChannelMessage::ConversationUpdated(conversation) => {
                                 let conversations = me.conversations.borrow().clone();
-                                for item in &conversations {
-                                    if item.conversation.borrow().id == conversation_id {
-                                        let current_length = aichats::get_messages_length(conversation_id).await.unwrap_or(0);
-                                        item.set_length(current_length);
-                                        break;
-                                    }
+                                if let Some(item) = conversations.iter().find(|item| item.conversation.borrow().id == conversation.id) {
+                                    item.set_conversation(conversation);
                                 }
+                                me.relayout();
+                            },
+
+                            AiChannelMessage::ConversationTrimmed(conversation_id, _) => {
+                                me.refresh_activity(conversation_id).await;
                             },
 
                             AiChannelMessage::ConversationLoaded(conversation) => {
-                                let conversations = me.conversations.borrow().clone();
-                                for item in &conversations {
-                                    if item.conversation.borrow().id == conversation.id {
-                                        let current_length = aichats::get_messages_length(conversation.id).await.unwrap_or(0);
-                                        item.set_length(current_length);
-                                        break;
-                                    }
-                                }
+                                me.refresh_activity(conversation.id).await;
                             },
 
                             AiChannelMessage::CycleStarted |
@@ -235,14 +490,7 @@ impl ConversationsList {
                                     continue;
                                 };
 
-                                let conversations = me.conversations.borrow().clone();
-                                for item in &conversations {
-                                    if item.conversation.borrow().id == current_conversation_id {
-                                        let current_length = aichats::get_messages_length(current_conversation_id).await.unwrap_or(0);
-                                        item.set_length(current_length);
-                                        break;
-                                    }
-                                }
+                                me.refresh_activity(current_conversation_id).await;
                             }
 
                             _ => {},
@@ -253,20 +501,115 @@ impl ConversationsList {
         }
 
         // Add existing conversations from the database
-        glib::spawn_future_local({
-            let root = me.root.clone();
-            let conversations = me.conversations.clone();
+        glib::spawn_future_local(clone!(
+            #[weak] me,
             async move {
                 if let Ok(existing_conversations) = aichats::get_all_conversations().await {
                     for conversation in existing_conversations {
                         let item = ConversationItem::new(conversation).await;
-                        root.append(&item.root);
-                        conversations.borrow_mut().push(item);
+                        me.conversations.borrow_mut().push(item);
                     }
                 }
+
+                me.relayout();
             }
-        });
+        ));
 
         me
     }
-}
\ No newline at end of file
+
+    async fn add_item(&self, conversation: AiConversation) {
+        let item = ConversationItem::new(conversation).await;
+        item.select_check.set_visible(self.selecting.get());
+        self.conversations.borrow_mut().push(item);
+        self.relayout();
+    }
+
+    /// Refreshes a conversation's message count and last activity, which can move it in the list.
+    async fn refresh_activity(&self, conversation_id: i64) {
+        let conversations = self.conversations.borrow().clone();
+        let Some(item) = conversations.iter().find(|item| item.conversation.borrow().id == conversation_id) else {
+            return;
+        };
+
+        let current_length = aichats::get_messages_length(conversation_id).await.unwrap_or(0);
+        item.set_length(current_length);
+
+        if let Ok(conversation) = aichats::get_conversation(conversation_id).await {
+            item.set_conversation(conversation);
+            self.relayout();
+        }
+    }
+
+    /// Rebuilds the list: pinned conversations first, then one section per folder, then
+    /// everything else. Each section is ordered by last activity.
+    fn relayout(&self) {
+        for child in self.root.iter_children() {
+            self.root.remove(&child);
+        }
+
+        let filter = self.filter.borrow();
+        let show_archived = self.show_archived.get();
+
+        let mut visible = self.conversations.borrow().iter()
+            .filter(|item| {
+                let conversation = item.conversation.borrow();
+                conversation.archived == show_archived && matches_filter(&conversation, &filter)
+            })
+            .cloned()
+            .collect::<Vec<_>>();
+
+        visible.sort_by(|a, b| {
+            let (a, b) = (a.conversation.borrow(), b.conversation.borrow());
+            b.last_activity.cmp(&a.last_activity).then(b.id.cmp(&a.id))
+        });
+
+        if visible.is_empty() {
+            let empty_label = section_label(if show_archived {
+                "No archived conversations"
+            } else {
+                "No conversations"
+            });
+            self.root.append(&empty_label);
+            return;
+        }
+
+        let (pinned, unpinned): (Vec<_>, Vec<_>) = visible.into_iter()
+            .partition(|item| item.conversation.borrow().pinned);
+
+        let mut folders: Vec<(String, Vec<ConversationItem>)> = Vec::new();
+        let mut unfiled = Vec::new();
+        for item in unpinned {
+            let folder = item.conversation.borrow().folder.clone();
+            match folder {
+                Some(folder) => match folders.iter_mut().find(|(name, _)| *name == folder) {
+                    Some((_, items)) => items.push(item),
+                    None => folders.push((folder, vec![item])),
+                },
+                None => unfiled.push(item),
+            }
+        }
+        folders.sort_by_key(|(name, _)| name.to_lowercase());
+
+        let has_sections = !pinned.is_empty() || !folders.is_empty();
+        let append_section = |title: &str, items: &[ConversationItem]| {
+            if items.is_empty() {
+                return;
+            }
+
+            if has_sections {
+                self.root.append(&section_label(title));
+            }
+
+            for item in items {
+                self.root.append(&item.root);
+            }
+        };
+
+        append_section("Pinned", &pinned);
+        for (folder, items) in &folders {
+            append_section(folder, items);
+        }
+        append_section(if show_archived { "Archived" } else { "Conversations" }, &unfiled);
+    }
+}
@@ -1,5 +1,6 @@
 use std::rc::Rc;
 use std::cell::RefCell;
+use std::path::PathBuf;
 use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
 use gtk::prelude::*;
 use gtk::{glib, gio};
@@ -7,6 +8,8 @@ use uuid::Uuid;
 use base64::Engine as _;
 use image::{ImageBuffer, Rgba};
 
+use crate::services::ai::files;
+
 #[derive(Clone)]
 pub struct RawImage {
     pub width: i32,
@@ -19,6 +22,13 @@ struct ProcessedImage {
     base64: String,
 }
 
+struct ProcessedFile {
+    name: String,
+    mime_type: String,
+    data: Vec<u8>,
+    text: Option<String>,
+}
+
 impl RawImage {
     pub fn from_texture(texture: &gdk::Texture) -> Option<Self> {
         let width = texture.width();
@@ -79,30 +89,97 @@ impl RawImage {
     }
 }
 
+pub enum RawFile {
+    Path(PathBuf),
+    Data {
+        name: String,
+        data: Vec<u8>,
+    },
+}
+
+impl RawFile {
+    fn name(&self) -> String {
+        match self {
+            Self::Path(path) => path.file_name()
+                .map_or_else(|| "file".to_owned(), |name| name.to_string_lossy().to_string()),
+
+            Self::Data { name, .. } => name.clone(),
+        }
+    }
+
+    fn process_blocking(self) -> Result<ProcessedFile, anyhow::Error> {
+        let name = self.name();
+        let (path, data) = match self {
+            Self::Path(path) => {
+                let size = std::fs::metadata(&path)?.len();
+                if size > files::MAX_FILE_SIZE {
+                    anyhow::bail!("File is larger than {} MiB", files::MAX_FILE_SIZE / 1024 / 1024);
+                }
+
+                let data = std::fs::read(&path)?;
+                (Some(path), data)
+            },
+
+            Self::Data { data, .. } => {
+                if data.len() as u64 > files::MAX_FILE_SIZE {
+                    anyhow::bail!("File is larger than {} MiB", files::MAX_FILE_SIZE / 1024 / 1024);
+                }
+
+                (None, data)
+            },
+        };
+
+        let mime_type = files::guess_mime_type(&name, &data);
+        if !files::is_supported_mime_type(&mime_type) {
+            anyhow::bail!("Unsupported file type: {}", mime_type);
+        }
+
+        let text = files::extract_text(&mime_type, path.as_deref(), &data);
+
+        Ok(ProcessedFile {
+            name,
+            mime_type,
+            data,
+            text,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct ImageAttachment {
     pub base64: String,
 }
 
+#[derive(Clone)]
+pub struct FileAttachment {
+    pub name: String,
+    pub mime_type: String,
+    pub data: Rc<Vec<u8>>,
+    pub text: Option<String>,
+}
+
+#[derive(Clone)]
+pub enum Attachment {
+    Image(ImageAttachment),
+    File(FileAttachment),
+}
+
 #[derive(Clone)]
 enum AttachmentState {
     Loading,
-    Ready(ImageAttachment),
+    Ready(Attachment),
 }
 
 #[derive(Clone)]
-pub struct ImageAttachmentWidget {
+pub struct AttachmentWidget {
     state: Rc<RefCell<AttachmentState>>,
     cancelled: Arc<AtomicBool>,
     uuid: String,
     pub widget: gtk::Box,
 }
 
-impl ImageAttachmentWidget {
-    pub fn new_async(
-        attachments_ref: ImageAttachments,
-        raw_image: RawImage,
-    ) -> Self {
+impl AttachmentWidget {
+    fn new_frame(attachments_ref: Attachments) -> (Self, libadwaita::Clamp) {
         let uuid = Uuid::new_v4().to_string();
         let cancelled = Arc::new(AtomicBool::new(false));
         let state = Rc::new(RefCell::new(AttachmentState::Loading));
@@ -149,12 +226,29 @@ impl ImageAttachmentWidget {
         overlay.add_overlay(&remove_button);
 
         let attachment_widget = Self {
-            state: state.clone(),
-            cancelled: cancelled.clone(),
+            state,
+            cancelled,
             uuid,
             widget,
         };
 
+        (attachment_widget, h_clamp)
+    }
+
+    fn set_error(h_clamp: &libadwaita::Clamp, message: &str) {
+        let label = gtk::Label::new(Some("Error"));
+        label.set_tooltip_text(Some(message));
+        h_clamp.set_child(Some(&label));
+    }
+
+    pub fn new_image_async(
+        attachments_ref: Attachments,
+        raw_image: RawImage,
+    ) -> Self {
+        let (attachment_widget, h_clamp) = Self::new_frame(attachments_ref);
+        let state = attachment_widget.state.clone();
+        let cancelled = attachment_widget.cancelled.clone();
+
         glib::spawn_future_local(async move {
             let result = gio::spawn_blocking(move || {
                 raw_image.process_blocking()
@@ -173,9 +267,8 @@ impl ImageAttachmentWidget {
                         base64: processed.base64,
                     };
 
-                    *state.borrow_mut() = AttachmentState::Ready(attachment);
+                    *state.borrow_mut() = AttachmentState::Ready(Attachment::Image(attachment));
 
-                    spinner.set_visible(false);
                     let picture = gtk::Picture::new();
                     picture.set_paintable(thumbnail.as_ref());
                     picture.set_width_request(100);
@@ -183,15 +276,11 @@ impl ImageAttachmentWidget {
                 }
                 Ok(Err(e)) => {
                     error!(%e, "Failed to process image");
-                    spinner.set_visible(false);
-                    let label = gtk::Label::new(Some("Error"));
-                    h_clamp.set_child(Some(&label));
+                    Self::set_error(&h_clamp, &e.to_string());
                 }
                 Err(e) => {
                     error!(?e, "Background task failed");
-                    spinner.set_visible(false);
-                    let label = gtk::Label::new(Some("Error"));
-                    h_clamp.set_child(Some(&label));
+                    Self::set_error(&h_clamp, "Background task failed");
                 }
             }
         });
@@ -199,7 +288,68 @@ impl ImageAttachmentWidget {
         attachment_widget
     }
 
-    pub fn get_attachment(&self) -> Option<ImageAttachment> {
+    pub fn new_file_async(
+        attachments_ref: Attachments,
+        raw_file: RawFile,
+    ) -> Self {
+        let (attachment_widget, h_clamp) = Self::new_frame(attachments_ref);
+        let state = attachment_widget.state.clone();
+        let cancelled = attachment_widget.cancelled.clone();
+
+        glib::spawn_future_local(async move {
+            let result = gio::spawn_blocking(move || {
+                raw_file.process_blocking()
+            }).await;
+
+            if cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+
+            match result {
+                Ok(Ok(ProcessedFile { name, mime_type, data, text })) => {
+                    let file_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
+                    file_box.set_css_classes(&["ai-chat-input-attachment-file"]);
+                    file_box.set_valign(gtk::Align::Center);
+                    file_box.set_tooltip_text(Some(&format!("{} ({})", name, mime_type)));
+
+                    let icon = gtk::Label::new(Some(if mime_type == "application/pdf" {
+                        "picture_as_pdf"
+                    } else {
+                        "description"
+                    }));
+                    icon.set_css_classes(&["ai-chat-input-attachment-file-icon"]);
+                    file_box.append(&icon);
+
+                    let name_label = gtk::Label::new(Some(&name));
+                    name_label.set_css_classes(&["ai-chat-input-attachment-file-name"]);
+                    name_label.set_ellipsize(gtk::pango::EllipsizeMode::Middle);
+                    name_label.set_max_width_chars(12);
+                    file_box.append(&name_label);
+
+                    *state.borrow_mut() = AttachmentState::Ready(Attachment::File(FileAttachment {
+                        name,
+                        mime_type,
+                        data: Rc::new(data),
+                        text,
+                    }));
+
+                    h_clamp.set_child(Some(&file_box));
+                }
+                Ok(Err(e)) => {
+                    warn!(%e, "Failed to process file");
+                    Self::set_error(&h_clamp, &e.to_string());
+                }
+                Err(e) => {
+                    error!(?e, "Background task failed");
+                    Self::set_error(&h_clamp, "Background task failed");
+                }
+            }
+        });
+
+        attachment_widget
+    }
+
+    pub fn get_attachment(&self) -> Option<Attachment> {
         match &*self.state.borrow() {
             AttachmentState::Loading => None,
             AttachmentState::Ready(attachment) => Some(attachment.clone()),
@@ -216,13 +366,13 @@ impl ImageAttachmentWidget {
 }
 
 #[derive(Clone, glib::Downgrade)]
-pub struct ImageAttachments {
+pub struct Attachments {
     pub container: gtk::Revealer,
     pub bx: gtk::Box,
-    attachments: Rc<RefCell<Vec<ImageAttachmentWidget>>>,
+    attachments: Rc<RefCell<Vec<AttachmentWidget>>>,
 }
 
-impl Default for ImageAttachments {
+impl Default for Attachments {
     fn default() -> Self {
         let container = gtk::Revealer::new();
         container.set_transition_type(gtk::RevealerTransitionType::SlideUp);
@@ -244,8 +394,8 @@ impl Default for ImageAttachments {
     }
 }
 
-impl ImageAttachments {
-    pub fn get_attachments(&self) -> Vec<Option<ImageAttachment>> {
+impl Attachments {
+    pub fn get_attachments(&self) -> Vec<Option<Attachment>> {
         self.attachments
             .borrow()
             .iter()
@@ -257,12 +407,36 @@ impl ImageAttachments {
         self.attachments.borrow().iter().all(|w| w.is_ready())
     }
 
+    fn push_widget(&self, widget: AttachmentWidget) {
+        self.bx.append(&widget.widget);
+        self.attachments.borrow_mut().push(widget);
+        self.container.set_reveal_child(true);
+    }
+
     pub fn push_texture(&self, texture: &gdk::Texture) {
         if let Some(raw_image) = RawImage::from_texture(texture) {
-            let widget = ImageAttachmentWidget::new_async(self.clone(), raw_image);
-            self.bx.append(&widget.widget);
-            self.attachments.borrow_mut().push(widget);
-            self.container.set_reveal_child(true);
+            self.push_widget(AttachmentWidget::new_image_async(self.clone(), raw_image));
+        }
+    }
+
+    pub fn push_file(&self, raw_file: RawFile) {
+        self.push_widget(AttachmentWidget::new_file_async(self.clone(), raw_file));
+    }
+
+    /// Attaches a file from the filesystem, images are attached as images instead of files.
+    pub fn push_gio_file(&self, file: &gio::File) {
+        let Some(path) = file.path() else {
+            warn!(uri = %file.uri(), "Only local files can be attached");
+            return;
+        };
+
+        let (content_type, _) = gio::content_type_guess(Some(&path), None::<&[u8]>);
+        if content_type.starts_with("image/")
+            && let Ok(texture) = gdk::Texture::from_file(file)
+        {
+            self.push_texture(&texture);
+        } else {
+            self.push_file(RawFile::Path(path));
         }
     }
 
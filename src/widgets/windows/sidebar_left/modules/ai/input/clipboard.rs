@@ -0,0 +1,99 @@
+use gtk::prelude::*;
+use relm4::RelmIterChildrenExt as _;
+
+use crate::services::clipboard;
+use super::attachments::{Attachments, RawFile};
+
+const MAX_ENTRIES: usize = 25;
+
+#[derive(Clone, glib::Downgrade)]
+pub struct ClipboardHistoryPicker {
+    pub container: gtk::Revealer,
+    bx: gtk::Box,
+    attachments: Attachments,
+}
+
+impl ClipboardHistoryPicker {
+    pub fn new(attachments: &Attachments) -> Self {
+        let container = gtk::Revealer::new();
+        container.set_transition_type(gtk::RevealerTransitionType::SlideUp);
+        container.set_reveal_child(false);
+
+        let scrolled_window = gtk::ScrolledWindow::new();
+        scrolled_window.set_css_classes(&["ai-chat-input-clipboard-history"]);
+        scrolled_window.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+        scrolled_window.set_propagate_natural_height(true);
+        scrolled_window.set_max_content_height(150);
+        container.set_child(Some(&scrolled_window));
+
+        let bx = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        scrolled_window.set_child(Some(&bx));
+
+        Self {
+            container,
+            bx,
+            attachments: attachments.clone(),
+        }
+    }
+
+    pub fn toggle(&self) {
+        if self.container.reveals_child() {
+            self.container.set_reveal_child(false);
+        } else {
+            self.refresh();
+            self.container.set_reveal_child(true);
+        }
+    }
+
+    fn refresh(&self) {
+        self.bx.iter_children().for_each(|child| {
+            self.bx.remove(&child);
+        });
+
+        clipboard::refresh_clipboard_entries();
+        let mut previews = clipboard::get_all_previews().into_iter().collect::<Vec<_>>();
+        previews.sort_by_key(|(id, _)| std::cmp::Reverse(*id));
+
+        for (id, preview) in previews.into_iter().take(MAX_ENTRIES) {
+            let is_image = clipboard::images::is_an_image_clipboard_entry(&preview);
+
+            let button = gtk::Button::new();
+            button.set_css_classes(&["ai-chat-input-clipboard-history-entry"]);
+
+            let label = gtk::Label::new(Some(&preview));
+            label.set_xalign(0.0);
+            label.set_ellipsize(gtk::pango::EllipsizeMode::End);
+            label.set_single_line_mode(true);
+            button.set_child(Some(&label));
+
+            button.connect_clicked(clone!(
+                #[weak(rename_to = me)] self,
+                move |_| {
+                    me.attach_entry(id, is_image);
+                    me.container.set_reveal_child(false);
+                }
+            ));
+
+            self.bx.append(&button);
+        }
+    }
+
+    fn attach_entry(&self, id: i32, is_image: bool) {
+        let Some(data) = clipboard::decode_clipboard_entry(&id.to_string()) else {
+            warn!(id, "Failed to decode clipboard entry");
+            return;
+        };
+
+        if is_image {
+            match gdk::Texture::from_bytes(&glib::Bytes::from_owned(data)) {
+                Ok(texture) => self.attachments.push_texture(&texture),
+                Err(err) => warn!(?err, "Failed to load image from clipboard history"),
+            }
+        } else {
+            self.attachments.push_file(RawFile::Data {
+                name: format!("clipboard-{}.txt", id),
+                data,
+            });
+        }
+    }
+}
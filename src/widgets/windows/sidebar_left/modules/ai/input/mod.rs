@@ -1,19 +1,57 @@
 mod attachments;
+mod clipboard;
 
 use std::rc::Rc;
 use gtk::prelude::*;
 
 use crate::services::ai::{self, SESSION};
 use crate::services::ai::images::cache_image_data;
+use crate::services::ai::files::cache_file_data;
 use crate::widgets::windows;
 use crate::utils::allocation_watcher::{AllocationWatcher, AllocationWatcherOptions};
+use crate::utils::process;
 use super::chat::Chat;
 use super::chat::message::{ChatMessage, ChatRole};
-use self::attachments::ImageAttachments;
+use self::attachments::{Attachment, Attachments};
+use self::clipboard::ClipboardHistoryPicker;
 
 const MIN_INPUT_SCROLL_HEIGHT: i32 = 50;
 const MAX_INPUT_SCROLL_HEIGHT: i32 = 250;
 
+fn input_control_button(icon: &str, tooltip: &str) -> gtk::Button {
+    let button = gtk::Button::new();
+    button.set_css_classes(&["ai-chat-input-attach-button"]);
+    button.set_halign(gtk::Align::Start);
+    button.set_valign(gtk::Align::Start);
+    button.set_label(icon);
+    button.set_tooltip_text(Some(tooltip));
+    button
+}
+
+fn choose_file(title: &str, filter: &gtk::FileFilter, on_file: impl Fn(&gio::File) + 'static) {
+    let file_chooser = gtk::FileChooserNative::new(
+        Some(title),
+        None::<&gtk::Window>,
+        gtk::FileChooserAction::Open,
+        Some("Open"),
+        Some("Cancel"),
+    );
+    file_chooser.add_filter(filter);
+
+    file_chooser.connect_response(move |file_chooser, response| {
+        if response == gtk::ResponseType::Accept
+            && let Some(file) = file_chooser.file()
+        {
+            on_file(&file);
+        }
+
+        windows::show("left_sidebar");
+    });
+
+    windows::hide("left_sidebar");
+    file_chooser.show();
+}
+
 pub struct ChatInput {
     pub widget: gtk::Box,
     pub input_send_icon: gtk::Label,
@@ -28,9 +66,29 @@ impl ChatInput {
         let input_box = gtk::Box::new(gtk::Orientation::Vertical, 0);
         input_box.set_css_classes(&["ai-chat-input-box"]);
 
-        let input_attachments = ImageAttachments::default();
+        let input_attachments = Attachments::default();
         input_box.append(&input_attachments.container);
 
+        let clipboard_history = ClipboardHistoryPicker::new(&input_attachments);
+        input_box.append(&clipboard_history.container);
+
+        let drop_target = gtk::DropTarget::new(gdk::FileList::static_type(), gdk::DragAction::COPY);
+        drop_target.connect_drop(clone!(
+            #[weak] input_attachments,
+            #[upgrade_or] false,
+            move |_, value, _, _| {
+                let Ok(file_list) = value.get::<gdk::FileList>() else {
+                    return false;
+                };
+
+                for file in file_list.files() {
+                    input_attachments.push_gio_file(&file);
+                }
+                true
+            }
+        ));
+        input_box.add_controller(drop_target);
+
         let input_scrolled_window = gtk::ScrolledWindow::new();
         input_scrolled_window.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Never);
         input_scrolled_window.set_propagate_natural_height(true);
@@ -85,6 +143,8 @@ impl ChatInput {
                         {
                             *stop_flag = true;
                         }
+
+                        ai::confirmation::deny_all();
                     } else if input_attachments.get_attachments().is_empty() || input_attachments.all_ready() {
                         #[allow(clippy::if_then_some_else_none)]
                         let text_sent = if !text.is_empty() {
@@ -108,10 +168,18 @@ impl ChatInput {
                             .collect::<Vec<_>>();
     
                         for attachment in &ready_attachments {
-                            if let Ok(path) = cache_image_data(&attachment.base64) {
-                                let id = ai::send_user_image(&path).await;
-                                chat.assert_last_message_is_role(ChatRole::User, text_sent.or(Some(id)));
-                                chat.append_image_to_latest_message(&path);
+                            match attachment {
+                                Attachment::Image(image) => if let Ok(uuid) = cache_image_data(&image.base64) {
+                                    let id = ai::send_user_image(&uuid).await;
+                                    chat.assert_last_message_is_role(ChatRole::User, text_sent.or(Some(id)));
+                                    chat.append_image_to_latest_message(&uuid);
+                                },
+
+                                Attachment::File(file) => if let Ok(uuid) = cache_file_data(&file.data, file.text.as_deref()) {
+                                    let id = ai::send_user_file(&uuid, &file.name, &file.mime_type).await;
+                                    chat.assert_last_message_is_role(ChatRole::User, text_sent.or(Some(id)));
+                                    chat.append_file_to_latest_message(&file.name, &file.mime_type);
+                                },
                             }
                         }
     
@@ -183,7 +251,26 @@ impl ChatInput {
                 let clipboard = input.clipboard();
                 let formats = clipboard.formats();
                 
-                if formats.contains_type(gdk::Texture::static_type()) {
+                if formats.contains_type(gdk::FileList::static_type()) {
+                    clipboard.read_value_async(
+                        gdk::FileList::static_type(),
+                        glib::Priority::DEFAULT,
+                        None::<&gio::Cancellable>,
+                        move |result| match result.map(|value| value.get::<gdk::FileList>()) {
+                            Ok(Ok(file_list)) => for file in file_list.files() {
+                                input_attachments.push_gio_file(&file);
+                            },
+
+                            Ok(Err(err)) => {
+                                warn!(?err, "Clipboard did not contain a file list");
+                            },
+
+                            Err(err) => {
+                                warn!(?err, "Error reading files from clipboard");
+                            },
+                        }
+                    );
+                } else if formats.contains_type(gdk::Texture::static_type()) {
                     clipboard.read_texture_async(None::<&gio::Cancellable>, move |result| {
                         match result {
                             Ok(Some(texture)) => {
@@ -209,44 +296,52 @@ impl ChatInput {
         input_controls_box.set_css_classes(&["ai-chat-input-controls-box"]);
         input_box.append(&input_controls_box);
 
-        let input_attach_image_button = gtk::Button::new();
-        input_attach_image_button.set_css_classes(&["ai-chat-input-attach-image-button"]);
-        input_attach_image_button.set_halign(gtk::Align::Start);
-        input_attach_image_button.set_valign(gtk::Align::Start);
-        input_attach_image_button.set_label("image");
-        input_attach_image_button.connect_clicked(move |_| {
-            let file_chooser = gtk::FileChooserNative::new(
-                Some("Select Image"),
-                None::<&gtk::Window>,
-                gtk::FileChooserAction::Open,
-                Some("Open"),
-                Some("Cancel"),
-            );
-
-            let filter = gtk::FileFilter::new();
-            filter.add_mime_type("image/png");
-            filter.add_mime_type("image/jpeg");
-            filter.set_name(Some("Image Files"));
-            file_chooser.add_filter(&filter);
-
-            file_chooser.connect_response(clone!(
-                #[weak] input_attachments,
-                move |file_chooser, response| {
-                    if response == gtk::ResponseType::Accept
-                        && let Some(file) = file_chooser.file()
-                        && let Ok(texture) = gdk::Texture::from_file(&file)
-                    {
+        let input_attach_image_button = input_control_button("image", "Attach Image");
+        input_attach_image_button.connect_clicked(clone!(
+            #[weak] input_attachments,
+            move |_| {
+                let filter = gtk::FileFilter::new();
+                filter.add_mime_type("image/png");
+                filter.add_mime_type("image/jpeg");
+                filter.set_name(Some("Image Files"));
+
+                choose_file("Select Image", &filter, clone!(
+                    #[weak] input_attachments,
+                    move |file| if let Ok(texture) = gdk::Texture::from_file(file) {
                         input_attachments.push_texture(&texture);
                     }
+                ));
+            }
+        ));
+        input_controls_box.append(&input_attach_image_button);
 
-                    windows::show("left_sidebar");
-                }
-            ));
+        let input_attach_file_button = input_control_button("attach_file", "Attach File");
+        input_attach_file_button.connect_clicked(clone!(
+            #[weak] input_attachments,
+            move |_| {
+                let filter = gtk::FileFilter::new();
+                filter.add_mime_type("text/*");
+                filter.add_mime_type("application/pdf");
+                filter.add_mime_type("application/json");
+                filter.add_mime_type("application/xml");
+                filter.add_mime_type("application/toml");
+                filter.add_mime_type("application/x-yaml");
+                filter.add_mime_type("application/x-shellscript");
+                filter.set_name(Some("Text & PDF Files"));
 
-            windows::hide("left_sidebar");
-            file_chooser.show();
-        });
-        input_controls_box.append(&input_attach_image_button);
+                choose_file("Select File", &filter, clone!(
+                    #[weak] input_attachments,
+                    move |file| input_attachments.push_gio_file(file)
+                ));
+            }
+        ));
+        input_controls_box.append(&input_attach_file_button);
+
+        if process::is_command_available("cliphist") {
+            let input_clipboard_history_button = input_control_button("content_paste", "Attach From Clipboard History");
+            input_clipboard_history_button.connect_clicked(move |_| clipboard_history.toggle());
+            input_controls_box.append(&input_clipboard_history_button);
+        }
 
         let input_send_button = gtk::Button::new();
         input_send_button.set_css_classes(&["ai-chat-input-send-button"]);
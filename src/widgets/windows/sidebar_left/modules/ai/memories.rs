@@ -0,0 +1,192 @@
+use gtk::prelude::*;
+use relm4::RelmIterChildrenExt as _;
+
+use crate::services::ai::{self, AiChannelMessage, memory, types::AiMemory};
+use crate::utils::gesture;
+
+fn memory_control_button(icon_name: &str, tooltip: &str) -> gtk::Button {
+    let button = gtk::Button::new();
+    button.set_css_classes(&["ai-chat-memory-item-control-button"]);
+    button.set_label(icon_name);
+    button.set_tooltip_text(Some(tooltip));
+
+    button
+}
+
+fn memory_item(memory: &AiMemory) -> gtk::Box {
+    let root = gtk::Box::new(gtk::Orientation::Horizontal, 0);
+    root.set_css_classes(&["ai-chat-memory-item"]);
+
+    let info_box = gtk::Box::new(gtk::Orientation::Vertical, 4);
+    info_box.set_hexpand(true);
+    root.append(&info_box);
+
+    let content_label = gtk::Label::new(Some(&memory.content));
+    content_label.set_css_classes(&["ai-chat-memory-item-content-label"]);
+    content_label.set_xalign(0.0);
+    content_label.set_wrap(true);
+    content_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+    info_box.append(&content_label);
+
+    let content_input = gtk::Entry::new();
+    content_input.set_text(&memory.content);
+    content_input.set_css_classes(&["ai-chat-memory-item-content-input"]);
+    content_input.set_hexpand(true);
+    content_input.set_visible(false);
+    content_input.connect_activate(clone!(
+        #[strong(rename_to = memory_id)] memory.id,
+        #[strong(rename_to = old_content)] memory.content,
+        move |input| {
+            let new_content = input.text().to_string();
+            if !new_content.trim().is_empty() && new_content != old_content {
+                glib::spawn_future_local(async move {
+                    if let Err(err) = memory::update(memory_id, &new_content).await {
+                        error!(%err, "Failed to update AI memory");
+                    }
+                });
+            }
+        }
+    ));
+    info_box.append(&content_input);
+
+    let date_label = gtk::Label::new(Some(&memory.updated_at.format("%b %d, %Y").to_string()));
+    date_label.set_css_classes(&["ai-chat-memory-item-date-label"]);
+    date_label.set_xalign(0.0);
+    info_box.append(&date_label);
+
+    let controls_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    controls_box.set_css_classes(&["ai-chat-memory-item-controls-box"]);
+
+    let edit_button = memory_control_button("edit", "Edit Memory");
+    edit_button.connect_clicked(clone!(
+        #[weak] content_label,
+        #[weak] content_input,
+        move |_| {
+            let editing = WidgetExt::is_visible(&content_input);
+            content_label.set_visible(editing);
+            content_input.set_visible(!editing);
+            if !editing {
+                content_input.grab_focus();
+            }
+        }
+    ));
+    controls_box.append(&edit_button);
+
+    let delete_button = memory_control_button("close", "Forget Memory");
+    delete_button.connect_clicked(clone!(
+        #[strong(rename_to = memory_id)] memory.id,
+        move |_| {
+            glib::spawn_future_local(async move {
+                if let Err(err) = memory::forget(memory_id).await {
+                    error!(%err, "Failed to delete AI memory");
+                }
+            });
+        }
+    ));
+    controls_box.append(&delete_button);
+
+    let controls_revealer = gtk::Revealer::new();
+    controls_revealer.set_valign(gtk::Align::Start);
+    controls_revealer.set_transition_type(gtk::RevealerTransitionType::Crossfade);
+    controls_revealer.set_transition_duration(200);
+    controls_revealer.set_child(Some(&controls_box));
+    root.append(&controls_revealer);
+
+    root.add_controller(gesture::on_enter(clone!(
+        #[weak] controls_revealer,
+        move |_, _| {
+            controls_revealer.set_reveal_child(true);
+        }
+    )));
+
+    root.add_controller(gesture::on_leave(move || {
+        controls_revealer.set_reveal_child(false);
+    }));
+
+    root
+}
+
+#[derive(Debug, Clone, glib::Downgrade)]
+pub struct MemoriesList {
+    pub root: gtk::Box,
+    disabled_label: gtk::Label,
+    list: gtk::Box,
+}
+
+impl MemoriesList {
+    pub fn new() -> Self {
+        let root = gtk::Box::new(gtk::Orientation::Vertical, 8);
+        root.set_css_classes(&["ai-chat-memories-list"]);
+
+        let disabled_label = gtk::Label::new(Some(
+            "Memory is turned off. Set \"memory = true\" under [ai.features] to let the assistant use these."
+        ));
+        disabled_label.set_css_classes(&["ai-chat-memories-disabled-label"]);
+        disabled_label.set_xalign(0.0);
+        disabled_label.set_wrap(true);
+        root.append(&disabled_label);
+
+        let new_memory_input = gtk::Entry::new();
+        new_memory_input.set_css_classes(&["ai-chat-memories-new-input"]);
+        new_memory_input.set_placeholder_text(Some("Remember something..."));
+        new_memory_input.connect_activate(|input| {
+            let content = input.text().to_string();
+            if content.trim().is_empty() {
+                return;
+            }
+
+            input.set_text("");
+            glib::spawn_future_local(async move {
+                if let Err(err) = memory::remember(&content).await {
+                    error!(%err, "Failed to store AI memory");
+                }
+            });
+        });
+        root.append(&new_memory_input);
+
+        let list = gtk::Box::new(gtk::Orientation::Vertical, 0);
+        root.append(&list);
+
+        let me = Self {
+            root,
+            disabled_label,
+            list,
+        };
+
+        me.refresh();
+
+        if let Some(mut receiver) = ai::CHANNEL.get().map(|channel| channel.subscribe()) {
+            glib::spawn_future_local(clone!(
+                #[weak] me,
+                async move {
+                    while let Ok(message) = receiver.recv().await {
+                        if let AiChannelMessage::MemoriesChanged = message {
+                            me.refresh();
+                        }
+                    }
+                }
+            ));
+        }
+
+        me
+    }
+
+    pub fn refresh(&self) {
+        self.disabled_label.set_visible(!memory::is_enabled());
+
+        self.list.iter_children().for_each(|child| {
+            self.list.remove(&child);
+        });
+
+        let memories = memory::get_all();
+        if memories.is_empty() {
+            let empty_label = gtk::Label::new(Some("No memories stored yet."));
+            empty_label.set_css_classes(&["ai-chat-memories-empty-label"]);
+            self.list.append(&empty_label);
+        }
+
+        for memory in &memories {
+            self.list.append(&memory_item(memory));
+        }
+    }
+}
@@ -1,6 +1,7 @@
 mod chat;
 mod conversations;
 mod input;
+mod memories;
 
 use std::rc::Rc;
 use std::time::Duration;
@@ -87,6 +88,15 @@ pub fn chat_ui(stack: &gtk::Stack) -> gtk::Box {
     ));
     conversation_controls.append(&conversation_switch_button);
 
+    let memories_button = conversation_control_button("psychology", "Memory");
+    memories_button.connect_clicked(clone!(
+        #[weak] stack,
+        move |_| {
+            stack.set_visible_child_name("memories_ui");
+        }
+    ));
+    conversation_controls.append(&memories_button);
+
     let chat = Chat::default();
     let chat_window = gtk::ScrolledWindow::new();
     chat_window.set_policy(gtk::PolicyType::Automatic, gtk::PolicyType::Automatic);
@@ -161,6 +171,11 @@ pub fn chat_ui(stack: &gtk::Stack) -> gtk::Box {
                                     chat.append_image_to_latest_message(uuid);
                                 },
 
+                                AiConversationItemPayload::File { name, mime_type, .. } => {
+                                    chat.assert_last_message_is_role(ChatRole::User, Some(item.id));
+                                    chat.append_file_to_latest_message(name, mime_type);
+                                },
+
                                 AiConversationItemPayload::Reasoning { summary, .. } => {
                                     chat.assert_last_message_is_role(ChatRole::Assistant, Some(item.id));
                                     chat.append_thinking_block_to_latest_message(summary);
@@ -267,6 +282,10 @@ pub fn chat_ui(stack: &gtk::Stack) -> gtk::Box {
                         chat.append_tool_call_to_latest_message(&tool_name, &arguments);
                     },
 
+                    AiChannelMessage::ConfirmationRequested(id, description) => {
+                        chat.append_confirmation_to_latest_message(id, &description);
+                    },
+
                     _ => {},
                 }
 
@@ -302,6 +321,8 @@ pub fn conversations_ui(stack: &gtk::Stack) -> gtk::Box {
     header.append(&new_conversation_button);
 
     let conversations_list = conversations::ConversationsList::new();
+    widget.append(&conversations_list.toolbar);
+
     let conversations_window = gtk::ScrolledWindow::new();
     conversations_window.set_policy(gtk::PolicyType::Automatic, gtk::PolicyType::Automatic);
     conversations_window.set_vexpand(true);
@@ -312,6 +333,37 @@ pub fn conversations_ui(stack: &gtk::Stack) -> gtk::Box {
     widget
 }
 
+pub fn memories_ui(stack: &gtk::Stack) -> gtk::Box {
+    let widget = gtk::Box::new(gtk::Orientation::Vertical, 8);
+    widget.set_css_classes(&["ai-memories-ui"]);
+
+    let header = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+    header.set_css_classes(&["ai-conversations-ui-header"]);
+    widget.append(&header);
+
+    let back_button = conversation_ui_header_button("arrow_back", "Back");
+    back_button.connect_clicked(clone!(
+        #[weak] stack,
+        move |_| {
+            stack.set_visible_child_name("chat_ui");
+        }
+    ));
+    header.append(&back_button);
+
+    let memories_list = memories::MemoriesList::new();
+    let memories_window = gtk::ScrolledWindow::new();
+    memories_window.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    memories_window.set_vexpand(true);
+    memories_window.set_hexpand(true);
+    memories_window.set_child(Some(&memories_list.root));
+    widget.append(&memories_window);
+
+    // The config can change while the page is hidden
+    widget.connect_map(move |_| memories_list.refresh());
+
+    widget
+}
+
 pub fn new() -> gtk::Box {
     let widget = gtk::Box::new(gtk::Orientation::Vertical, 4);
     widget.set_css_classes(&["AiChat"]);
@@ -324,6 +376,7 @@ pub fn new() -> gtk::Box {
     ui_stack.set_transition_duration(150);
     ui_stack.add_named(&chat_ui(&ui_stack), Some("chat_ui"));
     ui_stack.add_named(&conversations_ui(&ui_stack), Some("conversations_ui"));
+    ui_stack.add_named(&memories_ui(&ui_stack), Some("memories_ui"));
     ui_stack.set_visible_child_name("chat_ui");
 
     widget.append(&ui_stack);
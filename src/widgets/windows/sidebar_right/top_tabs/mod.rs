@@ -1,4 +1,5 @@
 pub mod notifications;
+pub mod notification_history;
 pub mod audio;
 
 use crate::widgets::common::tabs::{Tabs, TabSize};
@@ -0,0 +1,302 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use gtk::prelude::*;
+use relm4::RelmIterChildrenExt as _;
+
+use crate::services::notifications::history;
+use crate::sql::wrappers::notifications::NotificationHistoryEntry;
+
+const RESULT_LIMIT: usize = 200;
+const ALL_APPS: &str = "All apps";
+
+fn format_time(time: chrono::DateTime<chrono::Local>) -> String {
+    if time.date_naive() == chrono::Local::now().date_naive() {
+        time.format("%I:%M %p").to_string()
+    } else {
+        time.format("%b %-d, %I:%M %p").to_string()
+    }
+}
+
+/// What happened to the notification after it was received.
+fn outcome(entry: &NotificationHistoryEntry) -> Option<String> {
+    if let Some(action) = &entry.invoked_action {
+        let name = entry.actions.iter()
+            .find(|(key, _)| key == action)
+            .map_or(action.as_str(), |(_, name)| name.as_str());
+
+        return Some(match entry.action_invoked_at {
+            Some(invoked_at) => format!("Clicked \"{}\" at {}", name, format_time(invoked_at)),
+            None => format!("Clicked \"{}\"", name),
+        });
+    }
+
+    let closed = match entry.close_reason? {
+        1 => "Expired",
+        2 => "Dismissed",
+        _ => "Closed by the app",
+    };
+
+    Some(match entry.closed_at {
+        Some(closed_at) => format!("{} at {}", closed, format_time(closed_at)),
+        None => closed.to_owned(),
+    })
+}
+
+fn status(entry: &NotificationHistoryEntry) -> Option<String> {
+    let Some(rule) = &entry.rule else {
+        return outcome(entry);
+    };
+
+    if entry.dropped {
+        return Some(format!("Dropped by rule \"{}\"", rule));
+    }
+
+    if entry.silent {
+        return Some(format!("Kept silent by rule \"{}\"", rule));
+    }
+
+    Some(match outcome(entry) {
+        Some(outcome) => format!("{} · Rule \"{}\"", outcome, rule),
+        None => format!("Rule \"{}\"", rule),
+    })
+}
+
+fn make_item(entry: &NotificationHistoryEntry) -> gtk::Box {
+    view! {
+        item = gtk::Box {
+            set_css_classes: &["notification", "notification-history-item"],
+            set_orientation: gtk::Orientation::Vertical,
+            set_hexpand: true,
+
+            gtk::Box {
+                set_css_classes: &["notification-content"],
+                set_orientation: gtk::Orientation::Vertical,
+                set_spacing: 4,
+
+                gtk::Box {
+                    set_css_classes: &["notification-header"],
+                    set_orientation: gtk::Orientation::Horizontal,
+                    set_spacing: 4,
+
+                    gtk::Label {
+                        set_label: &entry.app_name,
+                        set_css_classes: &["notification-history-app"],
+                        set_xalign: 0.0,
+                        set_ellipsize: gtk::pango::EllipsizeMode::End,
+                    },
+
+                    gtk::Label {
+                        set_css_classes: &["notification-timestamp"],
+                        set_label: &format_time(entry.received_at),
+                        set_xalign: 1.0,
+                        set_halign: gtk::Align::End,
+                        set_hexpand: true,
+                    }
+                },
+
+                gtk::Label {
+                    set_label: &entry.summary,
+                    set_css_classes: &["notification-summary"],
+                    set_xalign: 0.0,
+                    set_hexpand: true,
+                    set_ellipsize: gtk::pango::EllipsizeMode::End,
+                },
+
+                gtk::Label {
+                    set_label: &entry.body,
+                    set_css_classes: &["notification-body"],
+                    set_visible: !entry.body.is_empty(),
+                    set_xalign: 0.0,
+                    set_hexpand: true,
+                    set_wrap: true,
+                    set_wrap_mode: gtk::pango::WrapMode::WordChar,
+                    set_lines: 3,
+                    set_ellipsize: gtk::pango::EllipsizeMode::End,
+                },
+            }
+        }
+    }
+
+    if let Some(status) = status(entry) {
+        let label = gtk::Label::new(Some(&status));
+        label.set_css_classes(&["notification-history-status"]);
+        label.set_xalign(0.0);
+        item.append(&label);
+    }
+
+    item
+}
+
+pub fn new() -> gtk::Box {
+    let apps_model = gtk::StringList::new(&[ALL_APPS]);
+    let app_dropdown = gtk::DropDown::new(Some(apps_model.clone()), None::<gtk::Expression>);
+    app_dropdown.set_css_classes(&["notification-history-app-dropdown"]);
+
+    // Searches can finish out of order, so only the latest one is shown
+    let generation = Rc::new(Cell::new(0_u64));
+
+    view! {
+        search_entry = gtk::SearchEntry {
+            set_css_classes: &["notification-history-search-entry"],
+            set_placeholder_text: Some("Search notifications"),
+            set_hexpand: true,
+        },
+
+        dropped_button = gtk::ToggleButton {
+            set_css_classes: &["notification-tab-clear-button"],
+            set_tooltip_text: Some("Show notifications dropped by rules"),
+
+            gtk::Label {
+                set_css_classes: &["notification-tab-clear-button-icon"],
+                set_label: "block",
+            }
+        },
+
+        toolbar = gtk::Box {
+            set_css_classes: &["notification-history-toolbar"],
+            set_spacing: 4,
+            append: &search_entry,
+            append: &app_dropdown,
+            append: &dropped_button,
+
+            gtk::Button {
+                set_css_classes: &["notification-tab-clear-button"],
+                set_tooltip_text: Some("Clear history"),
+                connect_clicked: |_| {
+                    glib::spawn_future_local(history::clear());
+                },
+
+                gtk::Label {
+                    set_css_classes: &["notification-tab-clear-button-icon"],
+                    set_label: "delete_sweep",
+                }
+            },
+        },
+
+        empty_label = gtk::Label {
+            set_css_classes: &["notification-tab-empty-sub-label"],
+            set_label: "No notifications found",
+            set_valign: gtk::Align::Center,
+            set_vexpand: true,
+            set_visible: false,
+        },
+
+        items_box = gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 0,
+        },
+
+        root = gtk::Box {
+            set_orientation: gtk::Orientation::Vertical,
+            set_spacing: 8,
+            set_css_classes: &["notification-history"],
+            append: &toolbar,
+            append: &empty_label,
+
+            gtk::ScrolledWindow {
+                set_hscrollbar_policy: gtk::PolicyType::Never,
+                set_vscrollbar_policy: gtk::PolicyType::Automatic,
+                set_vexpand: true,
+                set_child: Some(&items_box)
+            },
+        },
+    }
+
+    let refresh_items = Rc::new(clone!(
+        #[weak] search_entry,
+        #[weak] app_dropdown,
+        #[weak] dropped_button,
+        #[weak] items_box,
+        #[weak] empty_label,
+        #[strong] generation,
+        move || {
+            let query = search_entry.text().to_string();
+            let app = app_dropdown.selected_item()
+                .and_downcast::<gtk::StringObject>()
+                .map(|app| app.string().to_string())
+                .filter(|app| app != ALL_APPS);
+            let include_dropped = dropped_button.is_active();
+
+            generation.set(generation.get() + 1);
+            let current = generation.get();
+
+            glib::spawn_future_local(clone!(
+                #[strong] generation,
+                async move {
+                    let entries = history::search(&query, app, include_dropped, RESULT_LIMIT).await;
+                    if generation.get() != current {
+                        return;
+                    }
+
+                    items_box.iter_children().for_each(|child| items_box.remove(&child));
+                    for entry in &entries {
+                        items_box.append(&make_item(entry));
+                    }
+
+                    empty_label.set_visible(entries.is_empty());
+                }
+            ));
+        }
+    ));
+
+    // Keeps the selected app selected as the list of apps changes
+    let refresh_apps = clone!(
+        #[weak] app_dropdown,
+        #[weak] apps_model,
+        move || {
+            glib::spawn_future_local(async move {
+                let selected = app_dropdown.selected_item()
+                    .and_downcast::<gtk::StringObject>()
+                    .map(|app| app.string().to_string());
+
+                let apps = history::apps().await;
+                let names = std::iter::once(ALL_APPS).chain(apps.iter().map(String::as_str)).collect::<Vec<_>>();
+                apps_model.splice(0, apps_model.n_items(), &names);
+
+                let index = selected
+                    .and_then(|selected| names.iter().position(|name| *name == selected))
+                    .unwrap_or(0);
+                app_dropdown.set_selected(index as u32);
+            });
+        }
+    );
+
+    search_entry.connect_search_changed(clone!(
+        #[strong] refresh_items,
+        move |_| refresh_items()
+    ));
+
+    app_dropdown.connect_selected_notify(clone!(
+        #[strong] refresh_items,
+        move |_| refresh_items()
+    ));
+
+    dropped_button.connect_toggled(clone!(
+        #[strong] refresh_items,
+        move |_| refresh_items()
+    ));
+
+    root.connect_map(clone!(
+        #[strong] refresh_items,
+        #[strong] refresh_apps,
+        move |_| {
+            refresh_apps();
+            refresh_items();
+        }
+    ));
+
+    let mut receiver = history::subscribe();
+    glib::spawn_future_local(clone!(
+        #[weak] root,
+        async move {
+            while receiver.recv().await.is_ok() {
+                if root.is_mapped() {
+                    refresh_apps();
+                    refresh_items();
+                }
+            }
+        }
+    ));
+
+    root
+}
@@ -5,6 +5,7 @@ use gtk::prelude::*;
 use crate::services::notifications::{self, clear_notifications};
 use crate::services::notifications::bus::BusEvent;
 use crate::widgets::notifications::notification::{self, NotificationWidget};
+use super::notification_history;
 
 pub fn new() -> gtk::Box {
     let widgets = Rc::new(RefCell::new(Vec::new()));
@@ -18,27 +19,41 @@ pub fn new() -> gtk::Box {
             set_valign: gtk::Align::Center,
         },
         
+        history_button_icon = gtk::Label {
+            set_css_classes: &["notification-tab-clear-button-icon"],
+            set_label: "history",
+        },
+        
+        history_button = gtk::ToggleButton {
+            set_css_classes: &["notification-tab-clear-button"],
+            set_tooltip_text: Some("History"),
+            set_child: Some(&history_button_icon),
+        },
+        
+        clear_button = gtk::Button {
+            set_css_classes: &["notification-tab-clear-button"],
+            connect_clicked: |_| clear_notifications(),
+            
+            gtk::Box {
+                set_spacing: 4,
+                
+                gtk::Label {
+                    set_css_classes: &["notification-tab-clear-button-icon"],
+                    set_label: "clear_all",
+                },
+                
+                gtk::Label {
+                    set_label: "Clear",
+                },
+            }
+        },
+        
         bx = gtk::Box {
             set_css_classes: &["notification-tab-header"],
+            set_spacing: 4,
             append: &header_counter,
-            
-            gtk::Button {
-                set_css_classes: &["notification-tab-clear-button"],
-                connect_clicked: |_| clear_notifications(),
-                
-                gtk::Box {
-                    set_spacing: 4,
-                    
-                    gtk::Label {
-                        set_css_classes: &["notification-tab-clear-button-icon"],
-                        set_label: "clear_all",
-                    },
-                    
-                    gtk::Label {
-                        set_label: "Clear",
-                    },
-                }
-            },
+            append: &history_button,
+            append: &clear_button,
         },
         
         notifications_empty_revealer = gtk::Revealer {
@@ -82,15 +97,34 @@ pub fn new() -> gtk::Box {
             set_child: Some(&notifications_empty_revealer),
         },
         
+        view_stack = gtk::Stack {
+            set_transition_type: gtk::StackTransitionType::Crossfade,
+            set_transition_duration: 175,
+            set_vexpand: true,
+            add_named: (&notifications_view, Some("notifications")),
+            add_named: (&notification_history::new(), Some("history")),
+        },
+        
         root = gtk::Box {
             set_orientation: gtk::Orientation::Vertical,
             set_spacing: 8,
             set_css_classes: &["notification-tab-root"],
             append: &bx,
-            append: &notifications_view,
+            append: &view_stack,
         },
     }
 
+    history_button.connect_toggled(clone!(
+        #[weak] view_stack,
+        #[weak] clear_button,
+        move |button| {
+            let showing_history = button.is_active();
+            view_stack.set_visible_child_name(if showing_history { "history" } else { "notifications" });
+            history_button_icon.set_label(if showing_history { "notifications" } else { "history" });
+            clear_button.set_visible(!showing_history);
+        }
+    ));
+
     let mut receiver = notifications::subscribe();
     glib::spawn_future_local(async move {
         while let Ok(event) = receiver.recv().await {
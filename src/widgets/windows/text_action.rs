@@ -0,0 +1,218 @@
+use std::cell::{Cell, RefCell};
+use std::rc::Rc;
+use std::time::Duration;
+use gtk::prelude::*;
+
+use crate::ipc;
+use crate::services::ai::{AiChannelMessage, types::AiConversationDelta};
+use crate::services::ai::text_actions::{self, TextSource};
+use crate::services::clipboard;
+use crate::services::hyprland::call_hyprctl_batch;
+use crate::utils::broadcast::BroadcastChannel;
+use crate::widgets::common::revealer::AdwRevealerDirection;
+use super::GmsWindow as _;
+use super::popup::{PopupWindow, PopupMargin, PopupOptions};
+
+fn text_action_button(icon: &str, label: &str) -> gtk::Button {
+    let button = gtk::Button::new();
+    button.set_css_classes(&["text-action-button"]);
+
+    let button_box = gtk::Box::new(gtk::Orientation::Horizontal, 4);
+    button.set_child(Some(&button_box));
+
+    let button_icon = gtk::Label::new(Some(icon));
+    button_icon.set_css_classes(&["text-action-button-icon"]);
+    button_box.append(&button_icon);
+
+    let button_label = gtk::Label::new(Some(label));
+    button_box.append(&button_label);
+
+    button
+}
+
+pub fn new(application: &libadwaita::Application) -> PopupWindow {
+    let root = gtk::Box::new(gtk::Orientation::Vertical, 8);
+    root.set_css_classes(&["text-action-box"]);
+
+    let title_label = gtk::Label::new(None);
+    title_label.set_css_classes(&["text-action-title"]);
+    title_label.set_xalign(0.0);
+    root.append(&title_label);
+
+    let result_label = gtk::Label::new(None);
+    result_label.set_css_classes(&["text-action-result"]);
+    result_label.set_xalign(0.0);
+    result_label.set_yalign(0.0);
+    result_label.set_wrap(true);
+    result_label.set_wrap_mode(gtk::pango::WrapMode::WordChar);
+    result_label.set_selectable(true);
+
+    let result_window = gtk::ScrolledWindow::new();
+    result_window.set_policy(gtk::PolicyType::Never, gtk::PolicyType::Automatic);
+    result_window.set_propagate_natural_height(true);
+    result_window.set_max_content_height(400);
+    result_window.set_child(Some(&result_label));
+    root.append(&result_window);
+
+    let buttons_box = gtk::Box::new(gtk::Orientation::Horizontal, 8);
+    buttons_box.set_halign(gtk::Align::End);
+    root.append(&buttons_box);
+
+    let copy_button = text_action_button("content_copy", "Copy");
+    buttons_box.append(&copy_button);
+
+    let replace_button = text_action_button("find_replace", "Replace");
+    replace_button.set_tooltip_text(Some("Copy the result and paste it over the selection"));
+    buttons_box.append(&replace_button);
+
+    let popup = PopupWindow::new(
+        application,
+        &["text-action-window"],
+        &root,
+        PopupOptions {
+            anchor_left: false,
+            anchor_right: false,
+            anchor_top: true,
+            anchor_bottom: false,
+        },
+        500,
+        100,
+        PopupMargin {
+            top: 0,
+            right: 0,
+            bottom: 0,
+            left: 0,
+        },
+        AdwRevealerDirection::Down,
+        250,
+    );
+
+    // The reply of the latest action, set once it has finished
+    let result: Rc<RefCell<Option<String>>> = Rc::new(RefCell::new(None));
+
+    // Incremented for every action so replies to older ones are ignored
+    let generation = Rc::new(Cell::new(0_u64));
+
+    copy_button.connect_clicked(clone!(
+        #[strong] result,
+        #[weak] popup,
+        move |_| if let Some(text) = result.borrow().as_deref() {
+            clipboard::copy_text(text);
+            popup.hide();
+        }
+    ));
+
+    replace_button.connect_clicked(clone!(
+        #[strong] result,
+        #[weak] popup,
+        move |_| if let Some(text) = result.borrow().as_deref() {
+            clipboard::copy_text(text);
+            popup.hide();
+
+            // Give focus time to return to the previous window before pasting into it
+            glib::timeout_add_local_once(Duration::from_millis(150), || {
+                if call_hyprctl_batch(&["dispatch sendshortcut CTRL,V,activewindow"]).is_none() {
+                    warn!("Failed to paste the text action result");
+                }
+            });
+        }
+    ));
+
+    let set_finished = clone!(
+        #[weak] copy_button,
+        #[weak] replace_button,
+        move |finished: bool| {
+            copy_button.set_sensitive(finished);
+            replace_button.set_sensitive(finished);
+        }
+    );
+
+    ipc::listen_for_messages_local(clone!(
+        #[weak] popup,
+        move |message| {
+            let mut split_whitespace_iterator = message.split_whitespace();
+            if split_whitespace_iterator.next() != Some("ai_text_action") {
+                return;
+            }
+
+            let Some(action) = split_whitespace_iterator.next().map(str::to_owned) else {
+                warn!("No text action given");
+                return;
+            };
+
+            let source = match split_whitespace_iterator.next().map(str::parse::<TextSource>) {
+                Some(Ok(source)) => source,
+                Some(Err(err)) => {
+                    warn!(%err, "Invalid text action source");
+                    return;
+                },
+                None => TextSource::PrimarySelection,
+            };
+
+            generation.set(generation.get() + 1);
+            let current_generation = generation.get();
+            *result.borrow_mut() = None;
+            set_finished(false);
+            title_label.set_text(&action);
+            result_label.set_text("Working...");
+            popup.show();
+
+            glib::spawn_future_local(clone!(
+                #[weak] result_label,
+                #[strong] result,
+                #[strong] generation,
+                #[strong] set_finished,
+                async move {
+                    let Some(text) = text_actions::fetch_text(source).await.filter(|text| !text.trim().is_empty()) else {
+                        result_label.set_text("Nothing is selected.");
+                        return;
+                    };
+
+                    let channel = BroadcastChannel::<AiChannelMessage>::new(100);
+                    let mut receiver = channel.subscribe();
+                    glib::spawn_future_local(clone!(
+                        #[weak] result_label,
+                        #[strong] generation,
+                        async move {
+                            let mut streamed = String::new();
+                            while let Ok(message) = receiver.recv().await {
+                                if generation.get() != current_generation {
+                                    break;
+                                }
+
+                                if let AiChannelMessage::StreamChunk(AiConversationDelta::Message(delta)) = message {
+                                    streamed.push_str(&delta);
+                                    result_label.set_text(&streamed);
+                                }
+                            }
+                        }
+                    ));
+
+                    let handle = tokio::spawn(async move {
+                        text_actions::run(&action, &text, &channel).await
+                    });
+
+                    let reply = handle.await.unwrap_or_else(|err| Err(anyhow::anyhow!(err)));
+                    if generation.get() != current_generation {
+                        return;
+                    }
+
+                    match reply {
+                        Ok(reply) => {
+                            result_label.set_text(&reply);
+                            *result.borrow_mut() = Some(reply);
+                            set_finished(true);
+                        },
+
+                        Err(err) => {
+                            error!(%err, "Text action failed");
+                            result_label.set_text(&format!("Failed: {}", err));
+                        },
+                    }
+                }
+            ));
+        }
+    ));
+
+    popup
+}